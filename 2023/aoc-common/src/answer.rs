@@ -0,0 +1,115 @@
+// What a part gives back. Most answers are numbers, but some puzzles answer
+// with a word or with letters drawn on a grid, read off the printed output.
+// Answers compare by how they print, since that's what gets submitted: the
+// number 46 and the text "46" are the same answer.
+
+use std::fmt;
+
+use crate::grid::Grid;
+
+/// A part's answer, printed as it would be submitted.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+///
+/// assert_eq!(Answer::from(46u64).to_string(), "46");
+/// assert_eq!(Answer::from(-3i64), Answer::from("-3"));
+/// let letters: aoc_common::grid::Grid<char> = "#.#\n###\n".parse().unwrap();
+/// assert_eq!(Answer::Grid(letters).to_string(), "#.#\n###");
+/// assert_eq!(Answer::from(46u64), "46");
+/// ```
+#[derive(Debug, Clone)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    String(String),
+    Grid(Grid<char>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::U64(number) => write!(f, "{}", number),
+            Answer::I64(number) => write!(f, "{}", number),
+            Answer::String(text) => write!(f, "{}", text),
+            Answer::Grid(grid) => write!(f, "{}", grid),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::U64(a), Answer::U64(b)) => a == b,
+            (Answer::I64(a), Answer::I64(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        let text = self.to_string();
+        text == other
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Answer {
+        Answer::U64(number.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(number: u64) -> Answer {
+        Answer::U64(number)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(number: i64) -> Answer {
+        Answer::I64(number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::String(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::String(text.to_string())
+    }
+}
+
+impl From<Grid<char>> for Answer {
+    fn from(grid: Grid<char>) -> Answer {
+        Answer::Grid(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(Answer::from(7u32), Answer::U64(7));
+        assert_eq!(Answer::U64(7), Answer::I64(7));
+        assert_ne!(Answer::U64(7), Answer::I64(-7));
+        assert_eq!(Answer::from("ABC"), Answer::String(String::from("ABC")));
+        let grid: Grid<char> = "##\n.#\n".parse().unwrap();
+        assert_eq!(Answer::from(grid.clone()), Answer::from("##\n.#"));
+        assert_ne!(Answer::from(grid), Answer::from("##.#"));
+    }
+}
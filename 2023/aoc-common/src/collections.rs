@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Counts how many times each item was seen, like Python's Counter. Items whose
+/// count drops to zero are removed, so `len` is the number of distinct items
+/// actually present.
+///
+/// ```
+/// use aoc_common::collections::Counter;
+///
+/// let mut counter: Counter<char> = "abracadabra".chars().collect();
+/// assert_eq!(counter.get(&'a'), 5);
+/// assert_eq!(counter.most_common(2), vec![(&'a', 5), (&'b', 2)]);
+/// counter.subtract(&'c', 1);
+/// assert_eq!((counter.len(), counter.total()), (4, 10));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Counter<T> {
+        Counter { counts: HashMap::new() }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Counter<T> {
+        Counter::default()
+    }
+
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+        }
+    }
+
+    // Takes away up to n, never going below zero
+    pub fn subtract(&mut self, item: &T, n: usize) {
+        if let Some(count) = self.counts.get_mut(item) {
+            *count = count.saturating_sub(n);
+            if *count == 0 {
+                self.counts.remove(item);
+            }
+        }
+    }
+
+    pub fn subtract_all(&mut self, other: &Counter<T>) {
+        for (item, count) in other.iter() {
+            self.subtract(item, count);
+        }
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    // Number of distinct items
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // Sum of every count
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // In no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+}
+
+impl<T: Eq + Hash + Ord> Counter<T> {
+    // Every item from the most to the least common, with ties in item order
+    // so the result doesn't depend on hashing
+    pub fn by_count(&self) -> Vec<(&T, usize)> {
+        let mut counts: Vec<(&T, usize)> = self.iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts
+    }
+
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut counts = self.by_count();
+        counts.truncate(n);
+        counts
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for Counter<T> {
+    fn eq(&self, other: &Counter<T>) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<T: Eq + Hash> Eq for Counter<T> {}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Counter<T> {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LruEntry<K, V> {
+    key: K,
+    value: V,
+    // towards the most recently used end
+    newer: Option<usize>,
+    // towards the least recently used end
+    older: Option<usize>,
+}
+
+/// A map holding at most `capacity` entries, which drops the least recently
+/// used one to make room. Entries live in a Vec linked in order of use, so
+/// every operation is O(1) without any unsafe pointers.
+///
+/// ```
+/// use aoc_common::collections::LruCache;
+///
+/// let mut cache = LruCache::new(2);
+/// cache.insert("a", 1);
+/// cache.insert("b", 2);
+/// assert_eq!(cache.get(&"a"), Some(&1));
+/// // "b" is now the least recently used
+/// assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+/// assert!(!cache.contains_key(&"b"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: Vec<LruEntry<K, V>>,
+    index: HashMap<K, usize>,
+    newest: Option<usize>,
+    oldest: Option<usize>,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        LruCache {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+            newest: None,
+            oldest: None,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    // Marks the entry as the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let idx = *self.index.get(key)?;
+        self.touch(idx);
+        Some(&self.entries[idx].value)
+    }
+
+    // Looks at the entry without counting it as a use
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|idx| &self.entries[*idx].value)
+    }
+
+    // Returns the entry that was pushed out to make room, if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.capacity == 0 {
+            return Some((key, value))
+        }
+        if let Some(idx) = self.index.get(&key) {
+            let idx = *idx;
+            self.entries[idx].value = value;
+            self.touch(idx);
+            return None
+        }
+
+        if self.entries.len() < self.capacity {
+            self.entries.push(LruEntry { key: key.clone(), value, newer: None, older: None });
+            let idx = self.entries.len() - 1;
+            self.index.insert(key, idx);
+            self.push_newest(idx);
+            return None
+        }
+
+        // full, so the oldest entry's slot is reused for the new one
+        let idx = self.oldest?;
+        self.unlink(idx);
+        let old_key = std::mem::replace(&mut self.entries[idx].key, key.clone());
+        let old_value = std::mem::replace(&mut self.entries[idx].value, value);
+        self.index.remove(&old_key);
+        self.index.insert(key, idx);
+        self.push_newest(idx);
+        Some((old_key, old_value))
+    }
+
+    // From the most to the least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let mut current = self.newest;
+        std::iter::from_fn(move || {
+            let entry = &self.entries[current?];
+            current = entry.older;
+            Some((&entry.key, &entry.value))
+        })
+    }
+
+    fn touch(&mut self, idx: usize) {
+        if self.newest != Some(idx) {
+            self.unlink(idx);
+            self.push_newest(idx);
+        }
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (newer, older) = (self.entries[idx].newer, self.entries[idx].older);
+        match newer {
+            Some(newer) => self.entries[newer].older = older,
+            None => self.newest = older,
+        }
+        match older {
+            Some(older) => self.entries[older].newer = newer,
+            None => self.oldest = newer,
+        }
+        self.entries[idx].newer = None;
+        self.entries[idx].older = None;
+    }
+
+    fn push_newest(&mut self, idx: usize) {
+        self.entries[idx].older = self.newest;
+        if let Some(newest) = self.newest {
+            self.entries[newest].newer = Some(idx);
+        }
+        self.newest = Some(idx);
+        if self.oldest.is_none() {
+            self.oldest = Some(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let mut counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(2), vec![(&'a', 5), (&'b', 2)]);
+        // 'b' and 'r' tie, and come out in item order
+        assert_eq!(counter.by_count(), vec![(&'a', 5), (&'b', 2), (&'r', 2), (&'c', 1), (&'d', 1)]);
+
+        counter.subtract(&'a', 2);
+        counter.subtract(&'c', 5);
+        counter.add_n('z', 3);
+        assert_eq!(counter.get(&'a'), 3);
+        assert_eq!(counter.get(&'c'), 0);
+        assert_eq!(counter.len(), 5);
+
+        counter.subtract_all(&"zzzbr".chars().collect());
+        assert_eq!(counter.by_count(), vec![(&'a', 3), (&'b', 1), (&'d', 1), (&'r', 1)]);
+    }
+
+    #[test]
+    fn test_hand_shapes() {
+        // the sorted counts of a hand of cards are enough to classify it
+        let shape = |hand: &str| -> Vec<usize> {
+            let counter: Counter<char> = hand.chars().collect();
+            counter.by_count().iter().map(|(_, count)| *count).collect()
+        };
+        assert_eq!(shape("AAAAA"), vec![5]);
+        assert_eq!(shape("AA8AA"), vec![4, 1]);
+        assert_eq!(shape("23332"), vec![3, 2]);
+        assert_eq!(shape("23432"), vec![2, 2, 1]);
+        assert_eq!(shape("23456"), vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache: LruCache<&str, u32> = LruCache::new(2);
+        assert_eq!(cache.insert("a", 1), None);
+        assert_eq!(cache.insert("b", 2), None);
+        // reading "a" makes "b" the least recently used
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+        assert!(!cache.contains_key(&"b"));
+        // updating an entry counts as a use too
+        assert_eq!(cache.insert("a", 10), None);
+        assert_eq!(cache.insert("d", 4), Some(("c", 3)));
+        assert_eq!(cache.iter().collect::<Vec<_>>(), vec![(&"d", &4), (&"a", &10)]);
+        // peeking doesn't
+        assert_eq!(cache.peek(&"a"), Some(&10));
+        assert_eq!(cache.insert("e", 5), Some(("a", 10)));
+        assert_eq!(cache.len(), 2);
+
+        let mut empty: LruCache<u8, u8> = LruCache::new(0);
+        assert_eq!(empty.insert(1, 1), Some((1, 1)));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_lru_matches_model() {
+        // a Vec kept in order of use, checked against the cache step by step
+        let mut cache: LruCache<u64, u64> = LruCache::new(5);
+        let mut model: Vec<(u64, u64)> = vec![];
+        let mut rng = crate::rng::Rng::new(0x1700);
+        for step in 0..5000 {
+            let draw = rng.next_u64();
+            let key = draw % 12;
+            if draw.is_multiple_of(3) {
+                let expected = model.iter().position(|(k, _)| *k == key).map(|idx| model.remove(idx));
+                if let Some(entry) = expected {
+                    model.insert(0, entry);
+                }
+                assert_eq!(cache.get(&key).copied(), expected.map(|(_, v)| v));
+            } else {
+                if let Some(idx) = model.iter().position(|(k, _)| *k == key) {
+                    model.remove(idx);
+                }
+                model.insert(0, (key, step));
+                let evicted = if model.len() > 5 { model.pop() } else { None };
+                assert_eq!(cache.insert(key, step), evicted);
+            }
+            let order: Vec<(u64, u64)> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+            assert_eq!(order, model);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Inputs archived compressed, as input.txt.gz or input.txt.zst, read as if
+// they weren't: `AocError::read` decompresses by the file's extension, so
+// every day's loader takes them, and the runner looks for a compressed input
+// where it'd look for a plain one.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use ruzstd::StreamingDecoder;
+
+/// The extensions of the compressed files that can be read: gzip's and
+/// Zstandard's.
+///
+/// ```
+/// assert!(aoc_common::compress::EXTENSIONS.contains(&"zst"));
+/// ```
+pub const EXTENSIONS: &[&str] = &["gz", "zst"];
+
+/// The file's contents, decompressed when its extension says they're
+/// compressed.
+///
+/// ```
+/// use aoc_common::compress::decompress;
+/// use std::path::Path;
+///
+/// assert_eq!(decompress(Path::new("input.txt"), b"seeds: 79".to_vec()).unwrap(), b"seeds: 79");
+/// // a Zstandard frame holding the text as it is
+/// let frame = [&[0x28, 0xb5, 0x2f, 0xfd, 0x20, 9, 0x49, 0, 0][..], b"seeds: 79"].concat();
+/// assert_eq!(decompress(Path::new("input.txt.zst"), frame).unwrap(), b"seeds: 79");
+/// assert!(decompress(Path::new("input.txt.gz"), b"seeds: 79".to_vec()).is_err());
+/// ```
+pub fn decompress(path: &Path, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    let mut out = vec![];
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz") => {
+            GzDecoder::new(&bytes[..]).read_to_end(&mut out)?;
+        }
+        Some("zst") => {
+            let mut decoder = StreamingDecoder::new(&bytes[..]).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+            decoder.read_to_end(&mut out)?;
+        }
+        _ => return Ok(bytes),
+    }
+    Ok(out)
+}
+
+/// The file and its compressed versions, in the order they're looked for.
+///
+/// ```
+/// use aoc_common::compress::variants;
+/// use std::path::{Path, PathBuf};
+///
+/// assert_eq!(
+///     variants(Path::new("day-5/input.txt")),
+///     ["day-5/input.txt", "day-5/input.txt.gz", "day-5/input.txt.zst"].map(PathBuf::from),
+/// );
+/// ```
+pub fn variants(path: &Path) -> Vec<PathBuf> {
+    let mut variants = vec![path.to_path_buf()];
+    for extension in EXTENSIONS {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(".");
+        name.push(extension);
+        variants.push(PathBuf::from(name));
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn test_gzip() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"LR\n\nAAA = (BBB, BBB)\n").unwrap();
+        let gzipped = encoder.finish().unwrap();
+        assert_eq!(decompress(Path::new("input.txt.gz"), gzipped.clone()).unwrap(), b"LR\n\nAAA = (BBB, BBB)\n");
+        // only the extension says what it is
+        assert_eq!(decompress(Path::new("input.txt"), gzipped.clone()).unwrap(), gzipped);
+    }
+
+    #[test]
+    fn test_zstd() {
+        // the frame header, then a last block that's raw, of the text's length
+        let text = b"Time: 7 15 30\n";
+        let block = (1 | (text.len() << 3)) as u32;
+        let frame = [&[0x28, 0xb5, 0x2f, 0xfd, 0x20, text.len() as u8][..], &block.to_le_bytes()[..3], text].concat();
+        assert_eq!(decompress(Path::new("input.txt.zst"), frame).unwrap(), text);
+        let err = decompress(Path::new("input.txt.zst"), b"not zstd".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
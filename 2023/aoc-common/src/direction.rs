@@ -0,0 +1,128 @@
+/// Compass directions on a grid where y grows downwards, so north is -y
+///
+/// ```
+/// use aoc_common::direction::Direction;
+///
+/// assert_eq!(Direction::N.offset(), (0, -1));
+/// assert_eq!(Direction::N.turn_right(), Direction::E);
+/// assert_eq!(Direction::NE.opposite(), Direction::SW);
+/// assert_eq!(Direction::try_from('^'), Ok(Direction::N));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    // Clockwise from north, matching grid::ORTHOGONAL
+    pub const CARDINAL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+    // Clockwise from north, matching grid::ADJACENT
+    pub const ALL: [Direction; 8] = [
+        Direction::N, Direction::NE, Direction::E, Direction::SE,
+        Direction::S, Direction::SW, Direction::W, Direction::NW,
+    ];
+
+    pub fn offset(&self) -> (i64, i64) {
+        match self {
+            Direction::N => (0, -1),
+            Direction::NE => (1, -1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, 1),
+            Direction::S => (0, 1),
+            Direction::SW => (-1, 1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.offset();
+        dx != 0 && dy != 0
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.rotate(4)
+    }
+
+    // A quarter turn, so diagonals stay diagonal
+    pub fn turn_left(&self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        self.rotate(2)
+    }
+
+    // Clockwise by the given number of eighth turns
+    fn rotate(&self, eighths: usize) -> Direction {
+        let idx = Direction::ALL.iter().position(|d| d == self).unwrap_or(0);
+        Direction::ALL[(idx + eighths) % 8]
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    // The letters puzzles use for moves, either as compass points or as
+    // up/down/left/right
+    fn try_from(c: char) -> Result<Direction, String> {
+        match c {
+            'N' | 'U' | '^' => Ok(Direction::N),
+            'E' | 'R' | '>' => Ok(Direction::E),
+            'S' | 'D' | 'v' => Ok(Direction::S),
+            'W' | 'L' | '<' => Ok(Direction::W),
+            _ => Err(format!("'{}' is not a direction", c)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{ADJACENT, ORTHOGONAL};
+    use crate::point::Point;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::NE.turn_left(), Direction::NW);
+        assert_eq!(Direction::SW.opposite(), Direction::NE);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            let (dx, dy) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-dx, -dy));
+        }
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+    }
+
+    #[test]
+    fn test_offsets_match_grid() {
+        let offsets: Vec<(i64, i64)> = Direction::CARDINAL.iter().map(|d| d.offset()).collect();
+        assert_eq!(offsets, ORTHOGONAL);
+        let offsets: Vec<(i64, i64)> = Direction::ALL.iter().map(|d| d.offset()).collect();
+        assert_eq!(offsets, ADJACENT);
+    }
+
+    #[test]
+    fn test_step() {
+        let start = Point::new(2, 3);
+        assert_eq!(start.step(Direction::N, 1), Point::new(2, 2));
+        assert_eq!(start.step(Direction::SE, 2), Point::new(4, 5));
+        assert_eq!(start.step(Direction::W, 0), start);
+        let path: Point = "RRDDLU".chars()
+            .map(|c| Direction::try_from(c).unwrap())
+            .fold(start, |point, direction| point.step(direction, 1));
+        assert_eq!(path, Point::new(3, 4));
+        assert!(Direction::try_from('x').is_err());
+    }
+}
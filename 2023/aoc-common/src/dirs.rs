@@ -0,0 +1,53 @@
+// Where aoc keeps what isn't source, following the XDG base directories:
+// downloaded inputs and puzzles and the answers it's found under
+// $XDG_CACHE_HOME/aoc (~/.cache/aoc), a folder per year and day, and its
+// settings, like the session cookie, under $XDG_CONFIG_HOME/aoc
+// (~/.config/aoc).
+
+use std::env;
+use std::path::PathBuf;
+
+// $<var>/aoc, or ~/<fallback>/aoc when the variable isn't set. XDG says a
+// relative path in the variable is to be ignored.
+fn base(var: &str, fallback: &str) -> Option<PathBuf> {
+    let dir = env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").filter(|home| !home.is_empty()).map(|home| PathBuf::from(home).join(fallback)))?;
+    Some(dir.join("aoc"))
+}
+
+/// aoc's cache directory, $XDG_CACHE_HOME/aoc or ~/.cache/aoc; None without
+/// either variable to go by
+///
+/// ```
+/// if let Some(dir) = aoc_common::dirs::cache_dir() {
+///     assert!(dir.ends_with("aoc"));
+/// }
+/// ```
+pub fn cache_dir() -> Option<PathBuf> {
+    base("XDG_CACHE_HOME", ".cache")
+}
+
+/// aoc's configuration directory, $XDG_CONFIG_HOME/aoc or ~/.config/aoc
+///
+/// ```
+/// if let Some(dir) = aoc_common::dirs::config_dir() {
+///     assert!(dir.ends_with("aoc"));
+/// }
+/// ```
+pub fn config_dir() -> Option<PathBuf> {
+    base("XDG_CONFIG_HOME", ".config")
+}
+
+/// The cache's folder for a day, <year>/day-<n>, which its input and puzzle
+/// go in
+///
+/// ```
+/// if let Some(dir) = aoc_common::dirs::day_dir(2023, 5) {
+///     assert!(dir.ends_with("aoc/2023/day-5"));
+/// }
+/// ```
+pub fn day_dir(year: u32, day: u32) -> Option<PathBuf> {
+    Some(cache_dir()?.join(year.to_string()).join(format!("day-{}", day)))
+}
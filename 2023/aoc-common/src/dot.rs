@@ -0,0 +1,169 @@
+use std::fmt::{Display, Write};
+
+use crate::graph::Graph;
+
+/// A node listed in a Graphviz export, with an optional label.
+///
+/// ```
+/// use aoc_common::dot::{DotEdge, DotNode, ToDot};
+///
+/// struct Pair;
+///
+/// impl ToDot for Pair {
+///     fn dot_nodes(&self) -> Vec<DotNode> {
+///         vec![DotNode { id: String::from("a"), label: Some(String::from("start")) }]
+///     }
+///
+///     fn dot_edges(&self) -> Vec<DotEdge> {
+///         vec![DotEdge::new("a", "b")]
+///     }
+/// }
+///
+/// assert!(Pair.to_dot().contains("    \"a\" [label=\"start\"];\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotNode {
+    pub id: String,
+    pub label: Option<String>,
+}
+
+/// An edge of a Graphviz export, between two node ids.
+///
+/// ```
+/// use aoc_common::dot::DotEdge;
+///
+/// let edge = DotEdge::labeled("AAA", "BBB", "L");
+/// assert_eq!((edge.from.as_str(), edge.to.as_str()), ("AAA", "BBB"));
+/// assert_eq!(edge.label.as_deref(), Some("L"));
+/// assert_eq!(DotEdge::new("AAA", "BBB").label, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotEdge {
+    pub from: String,
+    pub to: String,
+    pub label: Option<String>,
+}
+
+impl DotEdge {
+    pub fn new(from: &str, to: &str) -> DotEdge {
+        DotEdge { from: from.to_string(), to: to.to_string(), label: None }
+    }
+
+    pub fn labeled(from: &str, to: &str, label: &str) -> DotEdge {
+        DotEdge { from: from.to_string(), to: to.to_string(), label: Some(label.to_string()) }
+    }
+}
+
+/// Graphviz export for anything graph-shaped. Only the edges are required;
+/// nodes that appear in an edge don't need listing, so `dot_nodes` is for
+/// labels and for nodes without any edges.
+///
+/// ```text
+/// dot -Tsvg network.dot > network.svg
+/// ```
+///
+/// ```
+/// use aoc_common::dot::ToDot;
+/// use aoc_common::graph::Graph;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b);
+/// assert_eq!(
+///     graph.to_dot(),
+///     "digraph \"puzzle\" {\n    \"0\" [label=\"a\"];\n    \"1\" [label=\"b\"];\n    \"0\" -> \"1\";\n}\n",
+/// );
+/// ```
+pub trait ToDot {
+    fn dot_edges(&self) -> Vec<DotEdge>;
+
+    fn dot_nodes(&self) -> Vec<DotNode> {
+        vec![]
+    }
+
+    fn dot_name(&self) -> String {
+        String::from("puzzle")
+    }
+
+    fn dot_directed(&self) -> bool {
+        true
+    }
+
+    fn to_dot(&self) -> String {
+        let (keyword, arrow) = if self.dot_directed() { ("digraph", "->") } else { ("graph", "--") };
+        let mut out = String::new();
+        let _ = writeln!(out, "{} {} {{", keyword, quote(&self.dot_name()));
+        for node in self.dot_nodes() {
+            match node.label {
+                Some(label) => { let _ = writeln!(out, "    {} [label={}];", quote(&node.id), quote(&label)); }
+                None => { let _ = writeln!(out, "    {};", quote(&node.id)); }
+            }
+        }
+        for edge in self.dot_edges() {
+            let _ = write!(out, "    {} {} {}", quote(&edge.from), arrow, quote(&edge.to));
+            if let Some(label) = edge.label {
+                let _ = write!(out, " [label={}]", quote(&label));
+            }
+            let _ = writeln!(out, ";");
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+// DOT IDs are always quoted, so any node name is safe
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// Nodes are identified by index and labeled with their value
+impl<N: Display> ToDot for Graph<N> {
+    fn dot_nodes(&self) -> Vec<DotNode> {
+        self.nodes()
+            .map(|(id, node)| DotNode { id: id.to_string(), label: Some(node.to_string()) })
+            .collect()
+    }
+
+    fn dot_edges(&self) -> Vec<DotEdge> {
+        self.nodes()
+            .flat_map(|(id, _)| {
+                self.neighbors(id).iter().map(move |to| DotEdge::new(&id.to_string(), &to.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Ring(usize);
+
+    impl ToDot for Ring {
+        fn dot_edges(&self) -> Vec<DotEdge> {
+            (0..self.0).map(|i| DotEdge::new(&i.to_string(), &((i + 1) % self.0).to_string())).collect()
+        }
+
+        fn dot_directed(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_default_writer() {
+        assert_eq!(Ring(3).to_dot(), "graph \"puzzle\" {\n    \"0\" -- \"1\";\n    \"1\" -- \"2\";\n    \"2\" -- \"0\";\n}\n");
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a \"quoted\" node");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b);
+        assert_eq!(
+            graph.to_dot(),
+            "digraph \"puzzle\" {\n    \"0\" [label=\"a \\\"quoted\\\" node\"];\n    \"1\" [label=\"b\"];\n    \"0\" -> \"1\";\n}\n",
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::str::FromStr;
+
+/// Parsed puzzle models written out as JSON, so they can be inspected with
+/// external tools instead of through Debug output, or in a binary format for
+/// reading back into other Rust tools. The binary formats each need their own
+/// feature, bincode or msgpack.
+///
+/// ```
+/// use aoc_common::dump::DumpFormat;
+///
+/// let args = vec![String::from("--dump")];
+/// assert_eq!(DumpFormat::from_args(&args), Ok(Some(DumpFormat::Json)));
+/// assert_eq!(DumpFormat::from_args(&[]), Ok(None));
+/// assert_eq!("json".parse(), Ok(DumpFormat::Json));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DumpFormat {
+    #[default]
+    Json,
+    Bincode,
+    MessagePack,
+}
+
+impl FromStr for DumpFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<DumpFormat, String> {
+        match value {
+            "json" => Ok(DumpFormat::Json),
+            "bincode" if cfg!(feature = "bincode") => Ok(DumpFormat::Bincode),
+            "msgpack" if cfg!(feature = "msgpack") => Ok(DumpFormat::MessagePack),
+            "bincode" | "msgpack" => Err(format!("{} dumps need the {} feature", value, value)),
+            _ => Err(format!("Unknown dump format '{}', expected json, bincode or msgpack", value)),
+        }
+    }
+}
+
+impl DumpFormat {
+    // `--dump` on its own means JSON, and `--dump-format <format>` picks
+    // another. None when no dump was asked for.
+    pub fn from_args(args: &[String]) -> Result<Option<DumpFormat>, String> {
+        let format = match args.iter().position(|arg| arg == "--dump-format") {
+            Some(idx) => Some(args.get(idx + 1).ok_or("--dump-format needs a format")?.parse()?),
+            None => None,
+        };
+        if format.is_some() || args.iter().any(|arg| arg == "--dump") {
+            Ok(Some(format.unwrap_or_default()))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The value as pretty-printed JSON.
+///
+/// ```
+/// use aoc_common::dump::to_json;
+/// use aoc_common::point::Point;
+///
+/// assert_eq!(to_json(&Point::new(1, 2)).unwrap(), "{\n  \"x\": 1,\n  \"y\": 2\n}");
+/// ```
+pub fn to_json<T: serde::Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|err| err.to_string())
+}
+
+/// The value in the given format, as bytes.
+///
+/// ```
+/// use aoc_common::dump::{encode, DumpFormat};
+///
+/// assert_eq!(encode(&[1, 2], DumpFormat::Json).unwrap(), b"[\n  1,\n  2\n]");
+/// ```
+pub fn encode<T: serde::Serialize>(value: &T, format: DumpFormat) -> Result<Vec<u8>, String> {
+    match format {
+        DumpFormat::Json => to_json(value).map(|json| json.into_bytes()),
+        #[cfg(feature = "bincode")]
+        DumpFormat::Bincode => bincode::serialize(value).map_err(|err| err.to_string()),
+        // field names are kept, so readers don't depend on field order
+        #[cfg(feature = "msgpack")]
+        DumpFormat::MessagePack => rmp_serde::to_vec_named(value).map_err(|err| err.to_string()),
+        #[allow(unreachable_patterns)]
+        _ => Err(format!("{:?} dumps aren't enabled in this build", format)),
+    }
+}
+
+/// Writes the dump to stdout, with a trailing newline only for JSON
+///
+/// ```
+/// use aoc_common::dump::{write, DumpFormat};
+///
+/// write(&[1, 2, 3], DumpFormat::Json).unwrap();
+/// ```
+pub fn write<T: serde::Serialize>(value: &T, format: DumpFormat) -> Result<(), String> {
+    let mut bytes = encode(value, format)?;
+    if format == DumpFormat::Json {
+        bytes.push(b'\n');
+    }
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(&bytes).and_then(|_| stdout.flush()).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_args() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+        assert_eq!(DumpFormat::from_args(&args(&["input.txt"])), Ok(None));
+        assert_eq!(DumpFormat::from_args(&args(&["input.txt", "--dump"])), Ok(Some(DumpFormat::Json)));
+        assert_eq!(
+            DumpFormat::from_args(&args(&["--dump-format", "msgpack"])).is_ok(),
+            cfg!(feature = "msgpack"),
+        );
+        assert!(DumpFormat::from_args(&args(&["--dump-format", "yaml"])).is_err());
+        assert!(DumpFormat::from_args(&args(&["--dump-format"])).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let value = (vec![1u32, 2], "seeds");
+        assert_eq!(encode(&value, DumpFormat::Json), Ok(b"[\n  [\n    1,\n    2\n  ],\n  \"seeds\"\n]".to_vec()));
+        #[cfg(feature = "bincode")]
+        assert_eq!(
+            encode(&value, DumpFormat::Bincode),
+            Ok(vec![2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, b's', b'e', b'e', b'd', b's']),
+        );
+        #[cfg(feature = "msgpack")]
+        assert_eq!(
+            encode(&value, DumpFormat::MessagePack),
+            Ok(vec![0x92, 0x92, 1, 2, 0xa5, b's', b'e', b'e', b'd', b's']),
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// What goes wrong on the way to an answer: the input can't be read, it has
+// text a day's lexer can't take, or it doesn't fit together as the puzzle
+// describes, or the day doesn't solve the part asked for. Solvers return these
+// rather than panicking so the runner can say what happened. The rest of the
+// library passes errors around as strings, which an `AocError` turns into
+// with `?`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::compress;
+
+/// Why a day couldn't get an answer out of its input.
+///
+/// ```
+/// use aoc_common::error::AocError;
+///
+/// let err = AocError::Lex { line: 3, message: String::from("'½' isn't a part number") };
+/// assert_eq!(err.to_string(), "Line 3: '½' isn't a part number");
+/// let message: String = AocError::Parse(String::from("No seeds line")).into();
+/// assert_eq!(message, "No seeds line");
+/// assert_eq!(AocError::NoSolution { day: 8, part: 1 }.to_string(), "Day 8 has no solution for part 1");
+/// ```
+#[derive(Debug, Error)]
+pub enum AocError {
+    #[error("Could not read {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Line {line}: {message}")]
+    Lex { line: usize, message: String },
+    #[error("{0}")]
+    Parse(String),
+    #[error("Day {day} has no solution for part {part}")]
+    NoSolution { day: u32, part: u32 },
+    #[error("Day {day} part {part} gives {answer} for the example, not {expected}")]
+    WrongExample { day: u32, part: u32, answer: String, expected: String },
+}
+
+impl AocError {
+    /// Reads a puzzle input, decompressing a .gz or .zst one, naming the file
+    /// if it can't be.
+    ///
+    /// ```
+    /// use aoc_common::error::AocError;
+    /// use std::path::Path;
+    ///
+    /// let err = AocError::read(Path::new("no-such-input.txt")).unwrap_err();
+    /// assert!(err.to_string().starts_with("Could not read no-such-input.txt: "));
+    /// ```
+    pub fn read(path: &Path) -> Result<String, AocError> {
+        let io_error = |source| AocError::Io { path: path.to_path_buf(), source };
+        let bytes = compress::decompress(path, std::fs::read(path).map_err(io_error)?).map_err(io_error)?;
+        String::from_utf8(bytes).map_err(|_| io_error(io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")))
+    }
+}
+
+impl From<AocError> for String {
+    fn from(err: AocError) -> String {
+        err.to_string()
+    }
+}
@@ -0,0 +1,317 @@
+// What a solver did, recorded as it runs so it can be written to disk and
+// looked through later without solving again. Solvers mark the points worth
+// keeping with `event!`, which costs a thread local lookup while nothing is
+// recording; `record` collects them for one call.
+//
+// A recording is written as text, an event per line:
+//
+//     # aoc events 1
+//     <microseconds since recording started>\t<kind>\t<key>=<value>\t...
+//
+// with backslashes, tabs, newlines and `=` in keys and values escaped.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const HEADER: &str = "# aoc events 1";
+
+/// One thing a solver recorded: what happened, when, and the values that
+/// go with it.
+///
+/// ```
+/// use aoc_common::events::Event;
+/// use std::time::Duration;
+///
+/// let event = Event::new(Duration::from_micros(12), "parsed", vec![("seeds", String::from("4"))]);
+/// assert_eq!(event.field("seeds"), Some("4"));
+/// assert_eq!(event.field("maps"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub elapsed: Duration,
+    pub kind: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl Event {
+    pub fn new(elapsed: Duration, kind: &str, fields: Vec<(&str, String)>) -> Event {
+        let fields = fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect();
+        Event { elapsed, kind: kind.to_string(), fields }
+    }
+
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+
+    fn to_line(&self) -> String {
+        let mut line = format!("{}\t{}", self.elapsed.as_micros(), escape(&self.kind));
+        for (key, value) in self.fields.iter() {
+            line.push_str(&format!("\t{}={}", escape(key), escape(value)));
+        }
+        line
+    }
+
+    fn from_line(line: &str) -> Result<Event, String> {
+        let mut columns = line.split('\t');
+        let micros = columns.next().unwrap_or_default();
+        let micros: u64 = micros.parse().map_err(|_| format!("Invalid time '{}'", micros))?;
+        let kind = unescape(columns.next().ok_or("No event kind")?)?;
+        let fields = columns
+            .map(|column| {
+                let (key, value) = column.split_once('=').ok_or(format!("Invalid field '{}'", column))?;
+                Ok((unescape(key)?, unescape(value)?))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Event { elapsed: Duration::from_micros(micros), kind, fields })
+    }
+}
+
+// `=` is escaped too, so a key can hold one and still split from its value
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '=' => escaped.push_str("\\e"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('e') => unescaped.push('='),
+            _ => return Err(format!("Invalid escape in '{}'", text)),
+        }
+    }
+    Ok(unescaped)
+}
+
+/// The events recorded during one call of `record`, in the order they
+/// happened.
+///
+/// ```
+/// use aoc_common::events::Recording;
+///
+/// let (_, recording) = aoc_common::events::record(|| {
+///     aoc_common::event!("parsed", nodes = 3);
+/// });
+/// let text = recording.to_text();
+/// assert!(text.starts_with("# aoc events 1\n"));
+/// assert_eq!(Recording::parse(&text), Ok(recording));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Recording {
+    pub events: Vec<Event>,
+}
+
+impl Recording {
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        for event in self.events.iter() {
+            text.push_str(&event.to_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Recording, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(format!("Not an event recording, the first line should be '{}'", HEADER))
+        }
+        let events = lines
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| Event::from_line(line).map_err(|err| format!("line {}: {}", idx + 2, err)))
+            .collect::<Result<Vec<Event>, String>>()?;
+        Ok(Recording { events })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_text()).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+    }
+
+    pub fn read(path: &Path) -> Result<Recording, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        Recording::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    // The events of one kind, in order
+    pub fn of_kind<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a Event> + 'a {
+        self.events.iter().filter(move |event| event.kind == kind)
+    }
+
+    // Each event on a line, its time first, for reading through
+    pub fn replay(&self) -> String {
+        let mut out = String::new();
+        for event in self.events.iter() {
+            out.push_str(&format!("{:>10.3}ms {}", event.elapsed.as_secs_f64() * 1000.0, event.kind));
+            for (key, value) in event.fields.iter() {
+                out.push_str(&format!(" {}={}", key, value));
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    // How many events of each kind there are, in the order kinds first
+    // appear, and when the last one of each happened
+    pub fn summary(&self) -> String {
+        let mut kinds: Vec<(&str, usize, Duration)> = vec![];
+        for event in self.events.iter() {
+            match kinds.iter_mut().find(|(kind, _, _)| *kind == event.kind) {
+                Some((_, count, last)) => {
+                    *count += 1;
+                    *last = event.elapsed;
+                }
+                None => kinds.push((&event.kind, 1, event.elapsed)),
+            }
+        }
+        let mut out = String::new();
+        for (kind, count, last) in kinds {
+            out.push_str(&format!("{:<12} {:>8}  last at {:.3}ms\n", kind, count, last.as_secs_f64() * 1000.0));
+        }
+        out.push_str(&format!("{} events\n", self.events.len()));
+        out
+    }
+}
+
+thread_local! {
+    // set while `record` runs on this thread, with when it started
+    static RECORDING: RefCell<Option<(Instant, Vec<Event>)>> = const { RefCell::new(None) };
+}
+
+/// Whether `record` is running on this thread, so `event!` only builds its
+/// fields when they'll be kept.
+///
+/// ```
+/// assert!(!aoc_common::events::recording());
+/// let (inside, _) = aoc_common::events::record(aoc_common::events::recording);
+/// assert!(inside);
+/// ```
+pub fn recording() -> bool {
+    RECORDING.with(|recording| recording.borrow().is_some())
+}
+
+/// Adds an event to the recording on this thread, if there is one. `event!`
+/// is the shorthand for this.
+///
+/// ```
+/// use aoc_common::events;
+///
+/// let (_, recording) = events::record(|| events::push("step", vec![("node", String::from("AAA"))]));
+/// assert_eq!(recording.events[0].field("node"), Some("AAA"));
+/// ```
+pub fn push(kind: &str, fields: Vec<(&str, String)>) {
+    RECORDING.with(|recording| {
+        if let Some((started, events)) = recording.borrow_mut().as_mut() {
+            // only whole microseconds are written, so that's all that's kept
+            let elapsed = Duration::from_micros(started.elapsed().as_micros() as u64);
+            events.push(Event::new(elapsed, kind, fields));
+        }
+    });
+}
+
+/// Runs `f`, keeping the events it records on this thread. A recording
+/// already running is set aside until `f` returns.
+///
+/// ```
+/// let (answer, recording) = aoc_common::events::record(|| {
+///     for node in ["AAA", "BBB"] {
+///         aoc_common::event!("step", node = node);
+///     }
+///     2
+/// });
+/// assert_eq!(answer, 2);
+/// assert_eq!(recording.of_kind("step").count(), 2);
+/// ```
+pub fn record<R, F: FnOnce() -> R>(f: F) -> (R, Recording) {
+    let outer = RECORDING.with(|recording| recording.borrow_mut().replace((Instant::now(), vec![])));
+    let result = f();
+    let recorded = RECORDING.with(|recording| std::mem::replace(&mut *recording.borrow_mut(), outer));
+    let events = recorded.map(|(_, events)| events).unwrap_or_default();
+    (result, Recording { events })
+}
+
+/// Records an event of the given kind with its fields, each turned into a
+/// string only while recording, e.g. `event!("layer", from = "seed", ranges = 4)`.
+///
+/// ```
+/// let (_, recording) = aoc_common::events::record(|| {
+///     aoc_common::event!("parsed", seeds = 4, maps = 7);
+/// });
+/// assert_eq!(recording.events[0].kind, "parsed");
+/// assert_eq!(recording.events[0].field("maps"), Some("7"));
+/// ```
+#[macro_export]
+macro_rules! event {
+    ($kind:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::events::recording() {
+            $crate::events::push($kind, vec![$((stringify!($key), $value.to_string())),*]);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let event = Event::new(Duration::from_micros(1500), "odd\tkind", vec![("a=b", String::from("line\none\\two"))]);
+        let recording = Recording { events: vec![event] };
+        let text = recording.to_text();
+        assert_eq!(text, "# aoc events 1\n1500\todd\\tkind\ta\\eb=line\\none\\\\two\n");
+        assert_eq!(Recording::parse(&text), Ok(recording));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Recording::parse("").is_err());
+        assert_eq!(Recording::parse("# aoc events 1\nsoon\tparsed\n"), Err(String::from("line 2: Invalid time 'soon'")));
+        assert_eq!(Recording::parse("# aoc events 1\n5\tparsed\tseeds\n"), Err(String::from("line 2: Invalid field 'seeds'")));
+        assert_eq!(Recording::parse("# aoc events 1\n5\tbad\\q\n"), Err(String::from("line 2: Invalid escape in 'bad\\q'")));
+    }
+
+    #[test]
+    fn test_record() {
+        // nothing is kept outside a recording
+        crate::event!("lost");
+        let (_, outer) = record(|| {
+            crate::event!("outer", n = 1);
+            let (_, inner) = record(|| crate::event!("inner"));
+            assert_eq!(inner.events.len(), 1);
+            crate::event!("outer", n = 2);
+        });
+        let kinds: Vec<&str> = outer.events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["outer", "outer"]);
+        assert!(outer.events[0].elapsed <= outer.events[1].elapsed);
+    }
+
+    #[test]
+    fn test_replay_and_summary() {
+        let at = |micros: u64, kind: &str, node: &str| Event::new(Duration::from_micros(micros), kind, vec![("node", node.to_string())]);
+        let recording = Recording { events: vec![at(10, "parsed", "-"), at(1500, "step", "BBB"), at(2250, "step", "ZZZ")] };
+        assert_eq!(recording.replay(), "     0.010ms parsed node=-\n     1.500ms step node=BBB\n     2.250ms step node=ZZZ\n");
+        assert_eq!(
+            recording.summary(),
+            "parsed              1  last at 0.010ms\nstep                2  last at 2.250ms\n3 events\n",
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// Files tests read from the workspace: each day's example.txt and personal
+// input.txt, and the answers in answers.toml. Paths resolve from the
+// workspace root whichever crate the test lives in. Personal inputs are
+// looked for compressed and in aoc's cache too, where downloaded ones go, and
+// are skipped with a message rather than failing the test when there's none,
+// since not every checkout has them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::compress;
+use crate::error::AocError;
+use crate::manifest;
+
+/// The directory holding the workspace's Cargo.toml, found by walking up from
+/// this crate
+///
+/// ```
+/// let root = aoc_common::fixtures::workspace_root();
+/// assert!(root.join("answers.toml").exists());
+/// ```
+pub fn workspace_root() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.ancestors()
+        .find(|dir| fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|toml| toml.contains("[workspace]")))
+        .unwrap_or(manifest_dir)
+        .to_path_buf()
+}
+
+/// A path relative to the workspace root
+///
+/// ```
+/// let path = aoc_common::fixtures::path("day-5/example.txt");
+/// assert!(path.ends_with("day-5/example.txt"));
+/// assert!(path.exists());
+/// ```
+pub fn path<P: AsRef<Path>>(relative: P) -> PathBuf {
+    workspace_root().join(relative)
+}
+
+/// The day's example, which is always checked in, so a missing one panics
+///
+/// ```
+/// let example = aoc_common::fixtures::example(8);
+/// assert!(example.starts_with("LR\n"));
+/// ```
+pub fn example(day: u32) -> String {
+    let path = path(format!("day-{}/example.txt", day));
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("Could not read {}: {}", path.display(), err))
+}
+
+/// The day's personal input, or None with a message saying the test is
+/// skipping it when this checkout doesn't have one
+///
+/// ```
+/// use aoc_common::fixtures;
+///
+/// // day 6 isn't solved, so there's no input for it
+/// assert_eq!(fixtures::personal_input(6), None);
+/// let Some(input) = fixtures::personal_input(5) else { return };
+/// assert!(input.starts_with("seeds:"));
+/// ```
+pub fn personal_input(day: u32) -> Option<String> {
+    let path = path(format!("day-{}/input.txt", day));
+    // the workspace is 2023's
+    let cached = crate::dirs::day_dir(2023, day).map(|dir| dir.join("input.txt"));
+    let input = std::iter::once(&path).chain(&cached)
+        .flat_map(|path| compress::variants(path))
+        .find_map(|path| AocError::read(&path).ok());
+    if input.is_none() {
+        eprintln!("skipping day {}'s personal input: no {}", day, path.display());
+    }
+    input
+}
+
+/// The answer for the personal input recorded in answers.toml, if any
+///
+/// ```
+/// use aoc_common::fixtures;
+///
+/// assert_eq!(fixtures::expected(2023, 5, 2).as_deref(), Some("47909639"));
+/// assert_eq!(fixtures::expected(2023, 6, 1), None);
+/// ```
+pub fn expected(year: u32, day: u32, part: u32) -> Option<String> {
+    let path = path("answers.toml");
+    let text = fs::read_to_string(&path).unwrap_or_else(|err| panic!("Could not read {}: {}", path.display(), err));
+    let entries = manifest::parse(&text).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    entries.into_iter()
+        .find(|entry| entry.year == year && entry.day == day && entry.part == part && entry.input.is_none())
+        .and_then(|entry| entry.expected)
+}
@@ -0,0 +1,241 @@
+// Grid states recorded one after another, drawn a square of pixels per cell,
+// for watching a simulation back as numbered PNGs or an animated GIF
+// instead of as pages of text.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// A colour as red, green and blue
+///
+/// ```
+/// use aoc_common::frames::Rgb;
+///
+/// let gold: Rgb = [0xff, 0xd5, 0x4f];
+/// assert_eq!(gold[0], 255);
+/// ```
+pub type Rgb = [u8; 3];
+
+// One recorded state, its pixels row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+/// Collects frames of a grid simulation, asking a function for each cell's
+/// colour, and writes them out as PNGs or a GIF
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use aoc_common::frames::FrameRecorder;
+/// use aoc_common::grid::Grid;
+///
+/// let mut recorder = FrameRecorder::new(4);
+/// let mut grid: Grid<char> = "O.\n..".parse().unwrap();
+/// for _ in 0..2 {
+///     recorder.record(&grid, |_, c| if *c == 'O' { [0, 0, 0] } else { [255, 255, 255] });
+///     grid = grid.rotate_cw();
+/// }
+/// assert_eq!(recorder.len(), 2);
+/// assert!(recorder.encode_gif(Duration::from_millis(100)).unwrap().starts_with(b"GIF89a"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameRecorder {
+    cell_size: u32,
+    frames: Vec<Frame>,
+}
+
+impl FrameRecorder {
+    pub fn new(cell_size: u32) -> FrameRecorder {
+        FrameRecorder { cell_size: cell_size.max(1), frames: vec![] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn record<T, F>(&mut self, grid: &Grid<T>, color: F)
+    where
+        F: Fn(&Point, &T) -> Rgb
+    {
+        let (width, height) = (grid.width() as u32 * self.cell_size, grid.height() as u32 * self.cell_size);
+        let mut pixels = vec![0; (width * height * 3) as usize];
+        for (point, value) in grid.iter() {
+            let rgb = color(&point, value);
+            for dy in 0..self.cell_size {
+                let y = point.y as u32 * self.cell_size + dy;
+                let start = ((y * width + point.x as u32 * self.cell_size) * 3) as usize;
+                for pixel in pixels[start..start + (self.cell_size * 3) as usize].chunks_mut(3) {
+                    pixel.copy_from_slice(&rgb);
+                }
+            }
+        }
+        self.frames.push(Frame { width, height, pixels });
+    }
+
+    pub fn encode_png(&self, frame: usize) -> Result<Vec<u8>, String> {
+        let frame = self.frames.get(frame).ok_or(format!("No frame {}, there are {}", frame, self.frames.len()))?;
+        let mut out = vec![];
+        let mut encoder = png::Encoder::new(&mut out, frame.width, frame.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|err| format!("Could not encode PNG: {}", err))?;
+        writer.write_image_data(&frame.pixels).map_err(|err| format!("Could not encode PNG: {}", err))?;
+        writer.finish().map_err(|err| format!("Could not encode PNG: {}", err))?;
+        Ok(out)
+    }
+
+    // frame-0000.png, frame-0001.png and so on, in `dir`
+    pub fn write_pngs(&self, dir: &Path) -> Result<Vec<PathBuf>, String> {
+        fs::create_dir_all(dir).map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+        let mut paths = vec![];
+        for idx in 0..self.frames.len() {
+            let path = dir.join(format!("frame-{:04}.png", idx));
+            fs::write(&path, self.encode_png(idx)?).map_err(|err| format!("Could not write {}: {}", path.display(), err))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    // Every frame `delay` apart, looping. Grids rarely use more than a few
+    // colours, so they share one exact palette; past 256 each frame gets its
+    // own, approximated one.
+    pub fn encode_gif(&self, delay: Duration) -> Result<Vec<u8>, String> {
+        let first = self.frames.first().ok_or("No frames recorded")?;
+        if let Some((idx, frame)) = self.frames.iter().enumerate().find(|(_, frame)| (frame.width, frame.height) != (first.width, first.height)) {
+            return Err(format!("Frame {} is {}x{}, the first is {}x{}", idx, frame.width, frame.height, first.width, first.height))
+        }
+        let width = u16::try_from(first.width).map_err(|_| format!("Frames {} pixels wide are too wide for a GIF", first.width))?;
+        let height = u16::try_from(first.height).map_err(|_| format!("Frames {} pixels high are too high for a GIF", first.height))?;
+        // in hundredths of a second
+        let delay = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+        let mut colors: HashMap<&[u8], u8> = HashMap::new();
+        for pixel in self.frames.iter().flat_map(|frame| frame.pixels.chunks(3)) {
+            let next = colors.len();
+            if next > 256 {
+                break
+            }
+            colors.entry(pixel).or_insert(next as u8);
+        }
+        let palette: Vec<u8> = if colors.len() <= 256 {
+            let mut entries: Vec<(&[u8], u8)> = colors.iter().map(|(color, idx)| (*color, *idx)).collect();
+            entries.sort_by_key(|(_, idx)| *idx);
+            entries.into_iter().flat_map(|(color, _)| color.iter().copied()).collect()
+        } else {
+            vec![]
+        };
+
+        let mut out = vec![];
+        {
+            let error = |err: gif::EncodingError| format!("Could not encode GIF: {}", err);
+            let mut encoder = gif::Encoder::new(&mut out, width, height, &palette).map_err(error)?;
+            encoder.set_repeat(gif::Repeat::Infinite).map_err(error)?;
+            for frame in self.frames.iter() {
+                let mut gif_frame = if palette.is_empty() {
+                    gif::Frame::from_rgb_speed(width, height, &frame.pixels, 10)
+                } else {
+                    let indexed: Vec<u8> = frame.pixels.chunks(3).map(|pixel| colors[pixel]).collect();
+                    gif::Frame::from_indexed_pixels(width, height, &indexed, None)
+                };
+                gif_frame.delay = delay;
+                encoder.write_frame(&gif_frame).map_err(error)?;
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn write_gif(&self, path: &Path, delay: Duration) -> Result<(), String> {
+        fs::write(path, self.encode_gif(delay)?).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb = [0, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    fn recorder() -> FrameRecorder {
+        let mut recorder = FrameRecorder::new(2);
+        let grid: Grid<char> = "#.\n.#\n..".parse().unwrap();
+        recorder.record(&grid, |_, c| if *c == '#' { BLACK } else { WHITE });
+        recorder.record(&grid, |_, _| WHITE);
+        recorder
+    }
+
+    #[test]
+    fn test_record() {
+        let recorder = recorder();
+        let frame = &recorder.frames[0];
+        assert_eq!((frame.width, frame.height), (4, 6));
+        // the top left cell is two pixels black on each of its two rows
+        assert_eq!(&frame.pixels[..12], &[0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]);
+        assert_eq!(&frame.pixels[12..24], &frame.pixels[..12]);
+        assert!(recorder.frames[1].pixels.iter().all(|value| *value == 255));
+    }
+
+    #[test]
+    fn test_png() {
+        let recorder = recorder();
+        let png = recorder.encode_png(0).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, recorder.frames[0].pixels);
+        assert_eq!(recorder.encode_png(2), Err(String::from("No frame 2, there are 2")));
+    }
+
+    #[test]
+    fn test_gif() {
+        let recorder = recorder();
+        let gif = recorder.encode_gif(Duration::from_millis(250)).unwrap();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (4, 6, 25));
+            let rgb: Vec<u8> = frame.buffer.chunks(4).flat_map(|pixel| pixel[..3].to_vec()).collect();
+            assert_eq!(rgb, recorder.frames[frames].pixels);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+
+        assert_eq!(FrameRecorder::new(1).encode_gif(Duration::ZERO), Err(String::from("No frames recorded")));
+        let mut recorder = recorder;
+        recorder.record(&Grid::filled(1, 1, ()), |_, _| BLACK);
+        assert_eq!(recorder.encode_gif(Duration::ZERO), Err(String::from("Frame 2 is 2x2, the first is 4x6")));
+    }
+
+    #[test]
+    fn test_many_colors() {
+        let mut recorder = FrameRecorder::new(1);
+        let grid = Grid::from_fn(20, 20, |x, y| (x, y));
+        recorder.record(&grid, |_, (x, y)| [*x as u8 * 12, *y as u8 * 12, 0]);
+        assert!(recorder.encode_gif(Duration::ZERO).unwrap().starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn test_write_pngs() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path().join("frames");
+        let paths = recorder().write_pngs(&dir).unwrap();
+        assert_eq!(paths, vec![dir.join("frame-0000.png"), dir.join("frame-0001.png")]);
+        assert!(paths.iter().all(|path| path.exists()));
+    }
+}
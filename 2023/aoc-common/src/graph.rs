@@ -0,0 +1,789 @@
+use std::cmp::{min, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+/// Index of a node in a Graph, in insertion order.
+///
+/// ```
+/// use aoc_common::graph::{Graph, NodeId};
+///
+/// let mut graph = Graph::new();
+/// let first: NodeId = graph.add_node('a');
+/// assert_eq!(first, 0);
+/// assert_eq!(graph.add_node('b'), 1);
+/// ```
+pub type NodeId = usize;
+
+/// Directed graph over nodes of type N, addressed by their insertion index
+///
+/// ```
+/// use aoc_common::graph::Graph;
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// graph.add_edge(a, b);
+/// assert_eq!(graph.len(), 2);
+/// assert_eq!(graph.neighbors(a), &[b]);
+/// assert_eq!(*graph.node(b), "b");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    edges: Vec<Vec<NodeId>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Graph<N> {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn add_node(&mut self, node: N) -> NodeId {
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges[from].push(to);
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.edges[id]
+    }
+}
+
+/// Builds a graph from keyed nodes, e.g. the named nodes of a day-8 network
+///
+/// ```
+/// use aoc_common::graph::GraphBuilder;
+///
+/// let mut builder = GraphBuilder::new();
+/// builder.edge("AAA", "BBB");
+/// builder.edge("BBB", "AAA");
+/// builder.node("ZZZ");
+/// let graph = builder.build();
+/// assert_eq!(graph.len(), 3);
+/// assert_eq!(graph.neighbors(0), &[1]);
+/// ```
+#[derive(Debug)]
+pub struct GraphBuilder<N> {
+    graph: Graph<N>,
+    ids: HashMap<N, NodeId>,
+}
+
+impl<N: Clone + Eq + Hash> Default for GraphBuilder<N> {
+    fn default() -> GraphBuilder<N> {
+        GraphBuilder {
+            graph: Graph::new(),
+            ids: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> GraphBuilder<N> {
+    pub fn new() -> GraphBuilder<N> {
+        GraphBuilder::default()
+    }
+
+    pub fn node(&mut self, node: N) -> NodeId {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+        let id = self.graph.add_node(node.clone());
+        self.ids.insert(node, id);
+        id
+    }
+
+    pub fn edge(&mut self, from: N, to: N) {
+        let from = self.node(from);
+        let to = self.node(to);
+        self.graph.add_edge(from, to);
+    }
+
+    pub fn build(self) -> Graph<N> {
+        self.graph
+    }
+}
+
+/// Tarjan's algorithm, done with an explicit call stack so large puzzle
+/// graphs can't overflow the real one. Components come out in reverse
+/// topological order: nothing in a component points at a later one.
+///
+/// ```
+/// use aoc_common::graph::{strongly_connected_components, Graph};
+///
+/// let mut graph = Graph::new();
+/// for node in 0..3 {
+///     graph.add_node(node);
+/// }
+/// graph.add_edge(0, 1);
+/// graph.add_edge(1, 0);
+/// graph.add_edge(1, 2);
+/// let mut components = strongly_connected_components(&graph);
+/// for component in components.iter_mut() {
+///     component.sort();
+/// }
+/// assert_eq!(components, vec![vec![2], vec![0, 1]]);
+/// ```
+pub fn strongly_connected_components<N>(graph: &Graph<N>) -> Vec<Vec<NodeId>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; graph.len()];
+    let mut low = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut stack: Vec<NodeId> = vec![];
+    let mut components: Vec<Vec<NodeId>> = vec![];
+    let mut next_index = 0;
+
+    for root in 0..graph.len() {
+        if index[root] != UNVISITED {
+            continue
+        }
+
+        // (node, position of the next edge to follow)
+        let mut call_stack: Vec<(NodeId, usize)> = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&(node, edge)) = call_stack.last() {
+            if let Some(&next) = graph.neighbors(node).get(edge) {
+                if let Some(frame) = call_stack.last_mut() {
+                    frame.1 += 1;
+                }
+                if index[next] == UNVISITED {
+                    index[next] = next_index;
+                    low[next] = next_index;
+                    next_index += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    call_stack.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = min(low[node], index[next]);
+                }
+                continue
+            }
+
+            call_stack.pop();
+            if let Some(&(parent, _)) = call_stack.last() {
+                low[parent] = min(low[parent], low[node]);
+            }
+            if low[node] == index[node] {
+                let mut component: Vec<NodeId> = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// The DAG left by `condensation`, and the component each original node
+/// ended up in.
+///
+/// ```
+/// use aoc_common::graph::{condensation, Graph};
+///
+/// let mut graph = Graph::new();
+/// for node in 0..3 {
+///     graph.add_node(node);
+/// }
+/// graph.add_edge(0, 1);
+/// graph.add_edge(1, 0);
+/// graph.add_edge(2, 2);
+/// let condensed = condensation(&graph);
+/// assert_eq!(condensed.graph.len(), 2);
+/// assert_eq!(condensed.component_of[0], condensed.component_of[1]);
+/// assert!(condensed.is_cyclic(&graph, condensed.component_of[0]));
+/// assert!(condensed.is_cyclic(&graph, condensed.component_of[2]));
+/// ```
+#[derive(Debug)]
+pub struct Condensation {
+    // one node per component, holding its members
+    pub graph: Graph<Vec<NodeId>>,
+    pub component_of: Vec<usize>,
+}
+
+impl Condensation {
+    // A component is cyclic if it has more than one member or its only
+    // member points at itself
+    pub fn is_cyclic<N>(&self, original: &Graph<N>, component: usize) -> bool {
+        let members = self.graph.node(component);
+        members.len() > 1 || original.neighbors(members[0]).contains(&members[0])
+    }
+}
+
+/// Collapses every strongly connected component into a single node, leaving
+/// a DAG with an edge wherever any member of one component points at another
+///
+/// ```
+/// use aoc_common::graph::{condensation, Graph};
+///
+/// let mut graph = Graph::new();
+/// for node in 0..3 {
+///     graph.add_node(node);
+/// }
+/// graph.add_edge(0, 1);
+/// graph.add_edge(1, 0);
+/// graph.add_edge(1, 2);
+/// let condensed = condensation(&graph);
+/// let (cycle, tail) = (condensed.component_of[0], condensed.component_of[2]);
+/// assert_eq!(condensed.graph.node(cycle).len(), 2);
+/// assert_eq!(condensed.graph.neighbors(cycle), &[tail]);
+/// ```
+pub fn condensation<N>(graph: &Graph<N>) -> Condensation {
+    let components = strongly_connected_components(graph);
+    let mut component_of = vec![0; graph.len()];
+    for (component, members) in components.iter().enumerate() {
+        for member in members {
+            component_of[*member] = component;
+        }
+    }
+
+    let mut condensed: Graph<Vec<NodeId>> = Graph::new();
+    for members in components {
+        condensed.add_node(members);
+    }
+    for component in 0..condensed.len() {
+        let mut targets: Vec<usize> = condensed.node(component)
+            .iter()
+            .flat_map(|member| graph.neighbors(*member))
+            .map(|next| component_of[*next])
+            .filter(|target| *target != component)
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        for target in targets {
+            condensed.add_edge(component, target);
+        }
+    }
+
+    Condensation { graph: condensed, component_of }
+}
+
+/// Returned by `topo_sort` when the graph isn't a DAG.
+///
+/// ```
+/// use aoc_common::graph::{topo_sort, Graph};
+///
+/// let mut graph = Graph::new();
+/// let a = graph.add_node('a');
+/// let b = graph.add_node('b');
+/// graph.add_edge(a, b);
+/// graph.add_edge(b, a);
+/// let err = topo_sort(&graph).unwrap_err();
+/// assert_eq!(err.cycles.len(), 1);
+/// assert_eq!(err.to_string(), "graph contains 1 cycle(s); 0 -> 1 -> 0");
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct CycleError {
+    // one cycle per cyclic component, as a path whose last node points
+    // back at its first
+    pub cycles: Vec<Vec<NodeId>>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "graph contains {} cycle(s)", self.cycles.len())?;
+        for cycle in &self.cycles {
+            let path: Vec<String> = cycle.iter().map(|n| n.to_string()).collect();
+            write!(f, "; {} -> {}", path.join(" -> "), cycle[0])?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for CycleError {}
+
+/// Kahn's algorithm, orders nodes so every edge points forward. Nodes that
+/// become ready together keep their id order, so the result is stable.
+///
+/// ```
+/// use aoc_common::graph::{topo_sort, Graph};
+///
+/// let mut graph = Graph::new();
+/// let tie = graph.add_node("tie");
+/// let shirt = graph.add_node("shirt");
+/// let socks = graph.add_node("socks");
+/// graph.add_edge(shirt, tie);
+/// assert_eq!(topo_sort(&graph), Ok(vec![shirt, socks, tie]));
+/// ```
+pub fn topo_sort<N>(graph: &Graph<N>) -> Result<Vec<NodeId>, CycleError> {
+    let mut in_degree = vec![0; graph.len()];
+    for node in 0..graph.len() {
+        for next in graph.neighbors(node) {
+            in_degree[*next] += 1;
+        }
+    }
+
+    let mut ready: VecDeque<NodeId> = (0..graph.len())
+        .filter(|node| in_degree[*node] == 0)
+        .collect();
+    let mut order: Vec<NodeId> = Vec::with_capacity(graph.len());
+    while let Some(node) = ready.pop_front() {
+        order.push(node);
+        for next in graph.neighbors(node) {
+            in_degree[*next] -= 1;
+            if in_degree[*next] == 0 {
+                ready.push_back(*next);
+            }
+        }
+    }
+
+    if order.len() == graph.len() {
+        return Ok(order);
+    }
+
+    let condensed = condensation(graph);
+    let mut cycles: Vec<Vec<NodeId>> = (0..condensed.graph.len())
+        .filter(|component| condensed.is_cyclic(graph, *component))
+        .map(|component| find_cycle(graph, condensed.graph.node(component)))
+        .collect();
+    cycles.sort();
+    Err(CycleError { cycles })
+}
+
+// Walks edges that stay inside a strongly connected component until a node
+// repeats; everything from its first visit onwards is a cycle
+fn find_cycle<N>(graph: &Graph<N>, members: &[NodeId]) -> Vec<NodeId> {
+    let inside: HashSet<NodeId> = members.iter().copied().collect();
+    let mut position: HashMap<NodeId, usize> = HashMap::new();
+    let mut path: Vec<NodeId> = vec![];
+    let mut current = *members.iter().min().unwrap();
+    while !position.contains_key(&current) {
+        position.insert(current, path.len());
+        path.push(current);
+        current = *graph.neighbors(current)
+            .iter()
+            .find(|next| inside.contains(next))
+            .unwrap();
+    }
+    path.split_off(position[&current])
+}
+
+/// A cheapest path found by `astar` or `dijkstra`.
+///
+/// ```
+/// use aoc_common::graph::dijkstra;
+///
+/// // a step of one costs 1 and a jump of three costs 2
+/// let result = dijkstra(0, |n| *n == 7, |n| [(n + 1, 1), (n + 3, 2)]).unwrap();
+/// assert_eq!(result.cost, 5);
+/// assert_eq!(result.path.len(), 4);
+/// assert_eq!((result.path[0], result.path[3]), (0, 7));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S> {
+    pub cost: u64,
+    // from the start state to the goal, both included
+    pub path: Vec<S>,
+    // how many states were taken off the queue, for comparing heuristics
+    pub expanded: usize,
+}
+
+/// Cheapest path from `start` to any state accepted by `is_goal`, where
+/// `neighbors` yields each reachable state with the cost of the move. States
+/// are discovered lazily, so this works for implicit graphs like grids with
+/// extra search state. The heuristic must never overestimate the remaining
+/// cost, or the path found may not be the cheapest.
+///
+/// ```
+/// use aoc_common::graph::astar;
+/// use aoc_common::point::Point;
+///
+/// let goal = Point::new(3, 2);
+/// let result = astar(
+///     Point::new(0, 0),
+///     |point| *point == goal,
+///     |point| [(1, 0), (0, 1)].map(|offset| (*point + Point::from(offset), 1)),
+///     |point| point.manhattan(&goal) as u64,
+/// ).unwrap();
+/// assert_eq!(result.cost, 5);
+/// assert_eq!(result.path.last(), Some(&goal));
+/// ```
+pub fn astar<S, G, N, I, H>(start: S, is_goal: G, neighbors: N, heuristic: H) -> Option<SearchResult<S>>
+where
+    S: Clone + Eq + Hash,
+    G: Fn(&S) -> bool,
+    N: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+    H: Fn(&S) -> u64,
+{
+    // states are numbered as they're found so the heap doesn't need S: Ord
+    let mut states: Vec<S> = vec![start.clone()];
+    let mut ids: HashMap<S, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut best: Vec<u64> = vec![0];
+    let mut parent: Vec<Option<usize>> = vec![None];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0u64, 0usize))]);
+    let mut expanded = 0;
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > best[id] {
+            continue
+        }
+        expanded += 1;
+
+        if is_goal(&states[id]) {
+            let mut path: Vec<S> = vec![];
+            let mut current = Some(id);
+            while let Some(node) = current {
+                path.push(states[node].clone());
+                current = parent[node];
+            }
+            path.reverse();
+            return Some(SearchResult { cost, path, expanded });
+        }
+
+        for (next, step_cost) in neighbors(&states[id]) {
+            let next_cost = cost + step_cost;
+            let next_id = match ids.get(&next) {
+                Some(next_id) if best[*next_id] <= next_cost => continue,
+                Some(next_id) => *next_id,
+                None => {
+                    states.push(next.clone());
+                    ids.insert(next.clone(), states.len() - 1);
+                    best.push(next_cost);
+                    parent.push(None);
+                    states.len() - 1
+                }
+            };
+            best[next_id] = next_cost;
+            parent[next_id] = Some(id);
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, next_id)));
+        }
+    }
+
+    None
+}
+
+/// `astar` without a heuristic, for when nothing useful is known about the
+/// remaining cost.
+///
+/// ```
+/// use aoc_common::graph::dijkstra;
+///
+/// // the cheapest way to 10 by doubling for 3 or adding one for 1
+/// let result = dijkstra(1u32, |n| *n == 10, |n| [(n * 2, 3), (n + 1, 1)]).unwrap();
+/// assert_eq!(result.cost, 7);
+/// assert_eq!(result.path, vec![1, 2, 3, 4, 5, 10]);
+/// ```
+pub fn dijkstra<S, G, N, I>(start: S, is_goal: G, neighbors: N) -> Option<SearchResult<S>>
+where
+    S: Clone + Eq + Hash,
+    G: Fn(&S) -> bool,
+    N: Fn(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, is_goal, neighbors, |_| 0)
+}
+
+/// Every state reachable from the start of a breadth-first search, with how
+/// many moves it takes to get there and the state it was first reached from
+///
+/// ```
+/// use aoc_common::graph::bfs_with_parents;
+///
+/// let tree = bfs_with_parents(0i64, |n| [n - 1, n + 1].into_iter().filter(|n| n.abs() <= 3));
+/// assert_eq!((tree.len(), *tree.start()), (7, 0));
+/// assert_eq!(tree.distance(&-3), Some(3));
+/// assert_eq!(tree.parent(&2), Some(&1));
+/// assert!(!tree.contains(&4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BfsTree<S> {
+    start: S,
+    distances: HashMap<S, usize>,
+    parents: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> BfsTree<S> {
+    pub fn start(&self) -> &S {
+        &self.start
+    }
+
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.distances.contains_key(state)
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        &self.distances
+    }
+
+    pub fn parent(&self, state: &S) -> Option<&S> {
+        self.parents.get(state)
+    }
+
+    // A shortest path from the start to the goal, both included
+    pub fn path_to(&self, goal: &S) -> Option<Vec<S>> {
+        if !self.contains(goal) {
+            return None
+        }
+        let mut path: Vec<S> = vec![goal.clone()];
+        let mut current = goal;
+        while let Some(parent) = self.parents.get(current) {
+            path.push(parent.clone());
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Explores everything reachable from `start` where every move costs the same,
+/// keeping enough to rebuild a shortest path to any of it afterwards
+///
+/// ```
+/// use aoc_common::graph::bfs_with_parents;
+///
+/// // numbers up to 20, reached by doubling or adding one
+/// let tree = bfs_with_parents(1u32, |n| [n * 2, n + 1].into_iter().filter(|n| *n <= 20));
+/// assert_eq!(tree.distance(&20), Some(5));
+/// assert_eq!(tree.path_to(&20), Some(vec![1, 2, 4, 5, 10, 20]));
+/// ```
+pub fn bfs_with_parents<S, N, I>(start: S, neighbors: N) -> BfsTree<S>
+where
+    S: Clone + Eq + Hash,
+    N: Fn(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances: HashMap<S, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut queue: VecDeque<S> = VecDeque::from([start.clone()]);
+
+    while let Some(state) = queue.pop_front() {
+        let distance = distances[&state];
+        for next in neighbors(&state) {
+            if distances.contains_key(&next) {
+                continue
+            }
+            distances.insert(next.clone(), distance + 1);
+            parents.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+
+    BfsTree { start, distances, parents }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::point::Point;
+
+    fn sorted(mut components: Vec<Vec<NodeId>>) -> Vec<Vec<NodeId>> {
+        for component in components.iter_mut() {
+            component.sort();
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut graph: Graph<char> = Graph::new();
+        for node in "abcdefgh".chars() {
+            graph.add_node(node);
+        }
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (6, 5), (6, 7), (7, 6)] {
+            graph.add_edge(from, to);
+        }
+
+        let components = strongly_connected_components(&graph);
+        assert_eq!(sorted(components.clone()), vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+        // {3, 4, 5} is reachable from both other components, so it's found first
+        let mut first = components[0].clone();
+        first.sort();
+        assert_eq!(first, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_condensation() {
+        let mut builder: GraphBuilder<&str> = GraphBuilder::new();
+        builder.edge("AAA", "BBB");
+        builder.edge("BBB", "AAA");
+        builder.edge("BBB", "ZZZ");
+        builder.edge("ZZZ", "ZZZ");
+        builder.edge("CCC", "ZZZ");
+        let graph = builder.build();
+
+        let condensed = condensation(&graph);
+        assert_eq!(condensed.graph.len(), 3);
+
+        let a = condensed.component_of[0];
+        let z = condensed.component_of[2];
+        let c = condensed.component_of[3];
+        assert_eq!(condensed.component_of[1], a);
+        assert_eq!(condensed.graph.neighbors(a), &[z]);
+        assert_eq!(condensed.graph.neighbors(c), &[z]);
+        assert!(condensed.graph.neighbors(z).is_empty());
+
+        assert!(condensed.is_cyclic(&graph, a));
+        assert!(condensed.is_cyclic(&graph, z));
+        assert!(!condensed.is_cyclic(&graph, c));
+    }
+
+    #[test]
+    fn test_long_chain_does_not_overflow() {
+        let mut graph: Graph<usize> = Graph::new();
+        for node in 0..200_000 {
+            graph.add_node(node);
+        }
+        for node in 1..200_000 {
+            graph.add_edge(node - 1, node);
+        }
+        graph.add_edge(199_999, 0);
+        assert_eq!(strongly_connected_components(&graph).len(), 1);
+    }
+
+    #[test]
+    fn test_topo_sort() {
+        let mut builder: GraphBuilder<&str> = GraphBuilder::new();
+        builder.edge("shirt", "tie");
+        builder.edge("tie", "jacket");
+        builder.edge("trousers", "shoes");
+        builder.edge("trousers", "belt");
+        builder.edge("belt", "jacket");
+        builder.edge("socks", "shoes");
+        let graph = builder.build();
+
+        let order = topo_sort(&graph).unwrap();
+        assert_eq!(order.len(), graph.len());
+        for node in 0..graph.len() {
+            let position = order.iter().position(|n| *n == node).unwrap();
+            for next in graph.neighbors(node) {
+                assert!(order.iter().position(|n| n == next).unwrap() > position);
+            }
+        }
+        assert_eq!(topo_sort(&Graph::<()>::new()), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_topo_sort_reports_cycles() {
+        let mut graph: Graph<()> = Graph::new();
+        for _ in 0..6 {
+            graph.add_node(());
+        }
+        for (from, to) in [(0, 1), (1, 2), (2, 1), (2, 3), (4, 4), (3, 5)] {
+            graph.add_edge(from, to);
+        }
+
+        let error = topo_sort(&graph).unwrap_err();
+        assert_eq!(error.cycles, vec![vec![1, 2], vec![4]]);
+        assert_eq!(error.to_string(), "graph contains 2 cycle(s); 1 -> 2 -> 1; 4 -> 4");
+    }
+
+    #[test]
+    fn test_astar_on_weighted_grid() {
+        // entering a cell costs its weight: open floor costs 1, and walls with
+        // occasional gaps cost 9 to wade through
+        let grid = Grid::from_fn(40, 40, |x, y| if x % 8 == 4 && y % 10 != 7 { 9 } else { 1 });
+        let goal = Point::new(39, 39);
+        let neighbors = |point: &Point| -> Vec<(Point, u64)> {
+            grid.neighbors4(point)
+                .map(|next| (next, *grid.get(&next).unwrap()))
+                .collect()
+        };
+
+        let slow = dijkstra(Point::new(0, 0), |p| *p == goal, neighbors).unwrap();
+        let fast = astar(Point::new(0, 0), |p| *p == goal, neighbors, |p| p.manhattan(&goal) as u64).unwrap();
+
+        assert_eq!(fast.cost, slow.cost);
+        assert!(fast.expanded < slow.expanded, "{} vs {}", fast.expanded, slow.expanded);
+        assert_eq!(fast.path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(fast.path.last(), Some(&goal));
+        let path_cost: u64 = fast.path.iter().skip(1).map(|p| grid.get(p).unwrap()).sum();
+        assert_eq!(path_cost, fast.cost);
+    }
+
+    #[test]
+    fn test_dijkstra_unreachable() {
+        let result = dijkstra(0u32, |n| *n == 10, |n| {
+            if *n < 5 { vec![(n + 1, 1)] } else { vec![] }
+        });
+        assert_eq!(result, None);
+
+        let result = dijkstra(0u32, |n| *n == 3, |n| vec![(n + 1, 2), (n + 3, 7)]).unwrap();
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_bfs_with_parents() {
+        let maze: Grid<char> = "S.#..#..\n.##.##..\n...#...G\n.#...#..".parse().unwrap();
+        let start = Point::new(0, 0);
+        let goal = Point::new(7, 2);
+        let tree = bfs_with_parents(start, |point: &Point| -> Vec<Point> {
+            maze.neighbors4(point)
+                .filter(|next| maze.get(next) != Some(&'#'))
+                .collect()
+        });
+
+        assert_eq!(tree.distance(&start), Some(0));
+        assert_eq!(tree.distance(&goal), Some(11));
+        let path = tree.path_to(&goal).unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        // each step moves to an open neighbor, one further from the start
+        for (steps, pair) in path.windows(2).enumerate() {
+            assert_eq!(pair[0].manhattan(&pair[1]), 1);
+            assert_eq!(tree.distance(&pair[1]), Some(steps + 1));
+        }
+
+        // the walled off corner is never reached
+        assert!(!tree.contains(&Point::new(3, 0)));
+        assert_eq!(tree.path_to(&Point::new(3, 0)), None);
+        assert_eq!(tree.path_to(&start), Some(vec![start]));
+        assert_eq!(tree.len(), maze.iter().filter(|(_, c)| **c != '#').count() - 3);
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::point::Point;
+
+/// Neighbor offsets as (dx, dy), with y growing downwards
+///
+/// ```
+/// use aoc_common::grid::ORTHOGONAL;
+/// use aoc_common::point::Point;
+///
+/// // clockwise from up
+/// let up = Point::new(2, 2) + Point::from(ORTHOGONAL[0]);
+/// assert_eq!(up, Point::new(2, 1));
+/// ```
+pub const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// The four diagonal offsets, clockwise from up and right.
+///
+/// ```
+/// use aoc_common::grid::DIAGONAL;
+///
+/// assert!(DIAGONAL.iter().all(|(dx, dy)| dx.abs() == 1 && dy.abs() == 1));
+/// ```
+pub const DIAGONAL: [(i64, i64); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+/// All eight neighbor offsets, clockwise from up.
+///
+/// ```
+/// use aoc_common::grid::{ADJACENT, DIAGONAL, ORTHOGONAL};
+///
+/// assert!(ORTHOGONAL.iter().chain(DIAGONAL.iter()).all(|offset| ADJACENT.contains(offset)));
+/// ```
+pub const ADJACENT: [(i64, i64); 8] = [
+    (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1),
+];
+/// The moves of a chess knight.
+///
+/// ```
+/// use aoc_common::grid::KNIGHT;
+///
+/// assert!(KNIGHT.iter().all(|(dx, dy)| dx.abs() + dy.abs() == 3));
+/// ```
+pub const KNIGHT: [(i64, i64); 8] = [
+    (1, -2), (2, -1), (2, 1), (1, 2), (-1, 2), (-2, 1), (-2, -1), (-1, -2),
+];
+
+/// Rectangular grid stored row by row, addressed with (0, 0) at the top left
+///
+/// ```
+/// use aoc_common::grid::Grid;
+/// use aoc_common::point::Point;
+///
+/// let mut grid = Grid::filled(3, 2, '.');
+/// *grid.get_mut(&Point::new(2, 0)).unwrap() = '#';
+/// assert_eq!(grid.to_string(), "..#\n...");
+/// assert_eq!(grid.get(&Point::new(3, 0)), None);
+/// assert_eq!(grid.neighbors4(&Point::new(0, 0)).count(), 2);
+/// assert_eq!(grid.rotate_cw().render(|c| *c), "..\n..\n.#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if cells.len() != width * height {
+            return None
+        }
+        Some(Grid { width, height, cells })
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Grid<T>
+    where
+        F: Fn(usize, usize) -> T
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero size, and a zero width grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells.iter()
+            .enumerate()
+            .map(move |(idx, cell)| (Point::new((idx % width) as i64, (idx / width) as i64), cell))
+    }
+
+    pub fn neighbors4<'a>(&'a self, point: &Point) -> impl Iterator<Item = Point> + 'a {
+        self.neighbors_by_offsets(point, &ORTHOGONAL)
+    }
+
+    pub fn neighbors8<'a>(&'a self, point: &Point) -> impl Iterator<Item = Point> + 'a {
+        self.neighbors_by_offsets(point, &ADJACENT)
+    }
+
+    // Points at each offset from the given one, skipping any that fall
+    // outside the grid
+    pub fn neighbors_by_offsets<'a>(
+        &'a self,
+        point: &Point,
+        offsets: &'a [(i64, i64)]
+    ) -> impl Iterator<Item = Point> + 'a {
+        let point = *point;
+        offsets.iter()
+            .map(move |(dx, dy)| Point::new(point.x + dx, point.y + dy))
+            .filter(|neighbor| self.contains(neighbor))
+    }
+
+    // Draws the grid one character per cell, rows separated by newlines
+    pub fn render<F>(&self, f: F) -> String
+    where
+        F: Fn(&T) -> char
+    {
+        let rows: Vec<String> = self.rows()
+            .map(|row| row.iter().map(&f).collect())
+            .collect();
+        rows.join("\n")
+    }
+
+    fn index_of(&self, point: &Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        if x >= self.width || y >= self.height {
+            return None
+        }
+        Some(y * self.width + x)
+    }
+
+    fn at(&self, x: usize, y: usize) -> &T {
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    // Rows become columns, so a W x H grid turns into an H x W one
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self.at(y, x).clone())
+    }
+
+    pub fn rotate_cw(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self.at(y, self.height - 1 - x).clone())
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self.at(self.width - 1 - y, x).clone())
+    }
+
+    // Mirrors left to right
+    pub fn flip_h(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self.at(self.width - 1 - x, y).clone())
+    }
+
+    // Mirrors top to bottom
+    pub fn flip_v(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self.at(x, self.height - 1 - y).clone())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One row per line, every line the same width.
+///
+/// ```
+/// use aoc_common::grid::Grid;
+/// use aoc_common::point::Point;
+///
+/// let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid.get(&Point::new(1, 1)), Some(&'#'));
+/// assert!("#..\n.#\n".parse::<Grid<char>>().is_err());
+/// ```
+impl FromStr for Grid<char> {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Grid<char>, String> {
+        let mut width: Option<usize> = None;
+        let mut cells: Vec<char> = vec![];
+        for (y, line) in input.lines().enumerate() {
+            let row_width = line.chars().count();
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(format!("Line {} has width {}, expected {}", y + 1, row_width, width));
+                }
+                _ => {}
+            }
+            cells.extend(line.chars());
+        }
+        let width = width.unwrap_or(0);
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        Ok(Grid { width, height, cells })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_get() {
+        let grid = grid("abc\ndef\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(&Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(&Point::new(3, 0)), None);
+        assert_eq!(grid.get(&Point::new(-1, 0)), None);
+        assert!("ab\nc".parse::<Grid<char>>().is_err());
+        assert_eq!("".parse::<Grid<char>>().unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let original = grid("abc\ndef");
+        assert_eq!(original.transpose(), grid("ad\nbe\ncf"));
+        assert_eq!(original.rotate_cw(), grid("da\neb\nfc"));
+        assert_eq!(original.rotate_ccw(), grid("cf\nbe\nad"));
+        assert_eq!(original.flip_h(), grid("cba\nfed"));
+        assert_eq!(original.flip_v(), grid("def\nabc"));
+    }
+
+    #[test]
+    fn test_transformation_identities() {
+        let original = grid("abcd\nefgh\nijkl");
+        assert_eq!(original.rotate_cw().rotate_ccw(), original);
+        assert_eq!(original.rotate_cw().rotate_cw(), original.flip_h().flip_v());
+        assert_eq!(original.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), original);
+        assert_eq!(original.transpose().transpose(), original);
+        assert_eq!(original.transpose().flip_h(), original.rotate_cw());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid("abc\ndef\nghi");
+        let values = |points: Vec<Point>| -> String {
+            points.iter().map(|p| grid.get(p).unwrap()).collect()
+        };
+
+        assert_eq!(values(grid.neighbors4(&Point::new(1, 1)).collect()), "bfhd");
+        assert_eq!(values(grid.neighbors8(&Point::new(1, 1)).collect()), "bcfihgda");
+        assert_eq!(values(grid.neighbors4(&Point::new(0, 0)).collect()), "bd");
+        assert_eq!(values(grid.neighbors8(&Point::new(2, 2)).collect()), "fhe");
+        assert_eq!(values(grid.neighbors_by_offsets(&Point::new(0, 0), &KNIGHT).collect()), "fh");
+        assert_eq!(values(grid.neighbors_by_offsets(&Point::new(1, 1), &DIAGONAL).collect()), "ciga");
+        assert_eq!(grid.neighbors4(&Point::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn test_render_and_display() {
+        let original = grid("#..\n.#.\n..#");
+        assert_eq!(original.to_string(), "#..\n.#.\n..#");
+        assert_eq!(grid(&original.rotate_cw().to_string()), original.rotate_cw());
+
+        let digits = Grid::from_fn(3, 2, |x, y| x + y * 3);
+        assert_eq!(digits.to_string(), "012\n345");
+        let odd = digits.render(|n| if n % 2 == 1 { '#' } else { '.' });
+        assert_eq!(odd, ".#.\n#.#");
+    }
+}
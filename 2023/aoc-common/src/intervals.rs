@@ -0,0 +1,288 @@
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
+use std::ops::{Add, Range, Sub};
+
+/// Numeric types usable as interval endpoints; Default is taken as zero
+///
+/// ```
+/// use aoc_common::intervals::DisjointIntervalSet;
+///
+/// let set: DisjointIntervalSet<i64> = [-5..0, 0..2].into_iter().collect();
+/// assert_eq!(set.covered_len(), 7);
+/// ```
+pub trait IntervalBound: Ord + Copy + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Ord + Copy + Default + Add<Output = T> + Sub<Output = T>> IntervalBound for T {}
+
+/// Half-open intervals kept sorted and non-overlapping. Inserting a range that
+/// overlaps or touches existing ones merges them into a single interval.
+///
+/// ```
+/// use aoc_common::intervals::DisjointIntervalSet;
+///
+/// let mut set = DisjointIntervalSet::new();
+/// set.insert(0..5);
+/// set.insert(10..15);
+/// set.insert(5..7);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..7, 10..15]);
+/// assert_eq!(set.covered_len(), 12);
+/// assert_eq!(set.gaps(), vec![7..10]);
+/// assert!(set.contains(&12) && !set.contains(&7));
+/// assert_eq!(set.complement(&(0..20)).into_ranges(), vec![7..10, 15..20]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisjointIntervalSet<T> {
+    // start -> end
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T: IntervalBound> Default for DisjointIntervalSet<T> {
+    fn default() -> DisjointIntervalSet<T> {
+        DisjointIntervalSet { intervals: BTreeMap::new() }
+    }
+}
+
+impl<T: IntervalBound> DisjointIntervalSet<T> {
+    pub fn new() -> DisjointIntervalSet<T> {
+        DisjointIntervalSet::default()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return
+        }
+
+        let mut start = range.start;
+        let mut end = range.end;
+        // sorted and disjoint, so walking back from the last interval that
+        // starts before our end finds every one we touch
+        let touching: Vec<T> = self.intervals.range(..=end)
+            .rev()
+            .take_while(|(_, other_end)| **other_end >= start)
+            .map(|(other_start, _)| *other_start)
+            .collect();
+        for other_start in touching {
+            if let Some(other_end) = self.intervals.remove(&other_start) {
+                start = min(start, other_start);
+                end = max(end, other_end);
+            }
+        }
+        self.intervals.insert(start, end);
+    }
+
+    // Number of separate intervals, not the number of covered values
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn covered_len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::default(), |total, (start, end)| total + (*end - *start))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.interval_at(value).is_some()
+    }
+
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true
+        }
+        self.interval_at(&range.start)
+            .is_some_and(|interval| interval.end >= range.end)
+    }
+
+    // The interval holding the value, if any
+    pub fn interval_at(&self, value: &T) -> Option<Range<T>> {
+        let (start, end) = self.intervals.range(..=*value).next_back()?;
+        if end > value {
+            Some(*start..*end)
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().map(|(start, end)| *start..*end)
+    }
+
+    // The uncovered stretches between consecutive intervals
+    pub fn gaps(&self) -> Vec<Range<T>> {
+        let intervals: Vec<Range<T>> = self.iter().collect();
+        intervals.windows(2)
+            .map(|pair| pair[0].end..pair[1].start)
+            .collect()
+    }
+
+    // Everything inside the bounds that this set doesn't cover
+    pub fn complement(&self, bounds: &Range<T>) -> DisjointIntervalSet<T> {
+        let mut complement = DisjointIntervalSet::new();
+        let mut position = bounds.start;
+        for interval in self.iter() {
+            if position >= bounds.end {
+                break
+            }
+            if interval.start > position {
+                complement.insert(position..min(interval.start, bounds.end));
+            }
+            position = max(position, interval.end);
+        }
+        if position < bounds.end {
+            complement.insert(position..bounds.end);
+        }
+        complement
+    }
+
+    // Everything covered by this set but not by the other
+    pub fn subtract(&self, other: &DisjointIntervalSet<T>) -> DisjointIntervalSet<T> {
+        self.iter()
+            .flat_map(|interval| other.complement(&interval).into_ranges())
+            .collect()
+    }
+
+    pub fn into_ranges(self) -> Vec<Range<T>> {
+        self.intervals.into_iter().map(|(start, end)| start..end).collect()
+    }
+}
+
+impl<T: IntervalBound> Extend<Range<T>> for DisjointIntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: IntervalBound> FromIterator<Range<T>> for DisjointIntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> DisjointIntervalSet<T> {
+        let mut set = DisjointIntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set: DisjointIntervalSet<u64> = DisjointIntervalSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        set.insert(5..5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![10..20, 30..40]);
+
+        // touching ends coalesce
+        set.insert(20..25);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![10..25, 30..40]);
+
+        // spanning several intervals swallows them
+        set.insert(0..35);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..40]);
+
+        set.insert(50..60);
+        set.insert(45..46);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.covered_len(), 51);
+        assert_eq!(set.gaps(), vec![40..45, 46..50]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: DisjointIntervalSet<i64> = vec![-10..-5, 0..10, 3..4].into_iter().collect();
+        assert!(set.contains(&-10));
+        assert!(!set.contains(&-5));
+        assert!(set.contains(&9));
+        assert!(!set.contains(&10));
+        assert_eq!(set.interval_at(&5), Some(0..10));
+        assert!(set.contains_range(&(2..10)));
+        assert!(!set.contains_range(&(-6..1)));
+        assert!(set.contains_range(&(100..100)));
+        assert_eq!(set.into_ranges(), vec![-10..-5, 0..10]);
+    }
+
+    #[test]
+    fn test_matches_point_oracle() {
+        // every subset of small ranges, checked value by value
+        let ranges = [0..3, 2..5, 7..8, 8..9, 4..4, 10..12, 6..11];
+        for mask in 0..(1u32 << ranges.len()) {
+            let chosen: Vec<Range<u32>> = ranges.iter()
+                .enumerate()
+                .filter(|(idx, _)| mask & (1 << idx) != 0)
+                .map(|(_, range)| range.clone())
+                .collect();
+            let set: DisjointIntervalSet<u32> = chosen.iter().cloned().collect();
+            let covered = |value: &u32| chosen.iter().any(|r| r.contains(value));
+
+            for value in 0..14 {
+                assert_eq!(set.contains(&value), covered(&value), "{:?} {}", chosen, value);
+            }
+            assert_eq!(set.covered_len() as usize, (0..14).filter(covered).count());
+            let intervals: Vec<Range<u32>> = set.iter().collect();
+            assert!(intervals.windows(2).all(|pair| pair[0].end < pair[1].start));
+        }
+    }
+
+    fn set(ranges: &[(u32, u32)]) -> DisjointIntervalSet<u32> {
+        ranges.iter().map(|(start, end)| *start..*end).collect()
+    }
+
+    #[test]
+    fn test_complement() {
+        let intervals = set(&[(2, 4), (6, 8)]);
+        assert_eq!(intervals.complement(&(0..10)).into_ranges(), vec![0..2, 4..6, 8..10]);
+        // bounds ending inside an interval or on a touching end
+        assert_eq!(intervals.complement(&(3..7)).into_ranges(), vec![4..6]);
+        assert_eq!(intervals.complement(&(4..6)).into_ranges(), vec![4..6]);
+        assert_eq!(intervals.complement(&(2..8)).into_ranges(), vec![4..6]);
+        // nested inside a single interval
+        assert!(intervals.complement(&(6..8)).is_empty());
+        assert!(intervals.complement(&(7..7)).is_empty());
+        assert_eq!(set(&[]).complement(&(1..5)).into_ranges(), vec![1..5]);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = set(&[(0, 10), (20, 30)]);
+        // identical
+        assert!(a.subtract(&a).is_empty());
+        // nested
+        assert_eq!(a.subtract(&set(&[(2, 4)])).into_ranges(), vec![0..2, 4..10, 20..30]);
+        assert!(set(&[(2, 4)]).subtract(&a).is_empty());
+        // touching ends remove nothing
+        assert_eq!(a.subtract(&set(&[(10, 20), (30, 40)])), a);
+        // overlapping both ends
+        assert_eq!(a.subtract(&set(&[(5, 25)])).into_ranges(), vec![0..5, 25..30]);
+        assert_eq!(a.subtract(&set(&[])), a);
+    }
+
+    #[test]
+    fn test_subtract_matches_point_oracle() {
+        let ranges = [0..4, 2..6, 4..5, 6..9, 9..10, 1..1];
+        for a_mask in 0..(1u32 << ranges.len()) {
+            for b_mask in 0..(1u32 << ranges.len()) {
+                let pick = |mask: u32| -> Vec<Range<u32>> {
+                    ranges.iter()
+                        .enumerate()
+                        .filter(|(idx, _)| mask & (1 << idx) != 0)
+                        .map(|(_, range)| range.clone())
+                        .collect()
+                };
+                let (a, b): (DisjointIntervalSet<u32>, DisjointIntervalSet<u32>) =
+                    (pick(a_mask).into_iter().collect(), pick(b_mask).into_iter().collect());
+                let difference = a.subtract(&b);
+                let complement = a.complement(&(1..8));
+                for value in 0..11 {
+                    assert_eq!(difference.contains(&value), a.contains(&value) && !b.contains(&value));
+                    assert_eq!(complement.contains(&value), (1..8).contains(&value) && !a.contains(&value));
+                }
+            }
+        }
+    }
+}
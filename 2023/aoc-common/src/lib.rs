@@ -0,0 +1,85 @@
+pub mod answer;
+pub mod collections;
+pub mod compress;
+pub mod direction;
+pub mod dirs;
+pub mod dot;
+#[cfg(feature = "serde")]
+pub mod dump;
+pub mod error;
+pub mod events;
+pub mod fixtures;
+#[cfg(feature = "images")]
+pub mod frames;
+pub mod graph;
+pub mod grid;
+pub mod intervals;
+pub mod locale;
+pub mod logging;
+pub mod manifest;
+pub mod mem;
+pub mod memo;
+pub mod output;
+pub mod plugin;
+pub mod point;
+pub mod polygon;
+pub mod prefix_sum;
+pub mod progress;
+pub mod registry;
+pub mod rng;
+pub mod solution;
+pub mod spatial;
+pub mod strings;
+pub mod svg;
+pub mod verify;
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    const ITEMS: &[&str] = &["pub struct ", "pub enum ", "pub trait ", "pub fn ", "pub type ", "pub const ", "#[macro_export]"];
+
+    // Whether any code block in the docs is Rust, which is what rustdoc runs;
+    // `text` blocks and the like don't count
+    fn has_example(docs: &[&str]) -> bool {
+        let mut in_block = false;
+        for line in docs {
+            let Some(info) = line.strip_prefix("```") else { continue };
+            if !in_block && matches!(info, "" | "rust" | "no_run" | "should_panic") {
+                return true
+            }
+            in_block = !in_block;
+        }
+        false
+    }
+
+    // Every public item of the library shows how it's used in a doctest, so
+    // the examples get compiled and run along with everything else
+    #[test]
+    fn test_public_items_have_examples() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut missing: Vec<String> = vec![];
+        for file in fs::read_dir(&src).unwrap() {
+            let path = file.unwrap().path();
+            let text = fs::read_to_string(&path).unwrap();
+            let lines: Vec<&str> = text.lines().collect();
+            for (idx, line) in lines.iter().enumerate() {
+                if !ITEMS.iter().any(|item| line.starts_with(item)) {
+                    continue
+                }
+                let mut docs: Vec<&str> = lines[..idx].iter()
+                    .rev()
+                    .skip_while(|line| line.starts_with("#[") && !line.starts_with("#[macro_export]"))
+                    .take_while(|line| line.starts_with("///"))
+                    .map(|line| line.trim_start_matches("///").trim())
+                    .collect();
+                docs.reverse();
+                if !has_example(&docs) {
+                    missing.push(format!("{}:{}: {}", path.file_name().unwrap().to_string_lossy(), idx + 1, line));
+                }
+            }
+        }
+        assert!(missing.is_empty(), "public items without a doctest:\n{}", missing.join("\n"));
+    }
+}
@@ -0,0 +1,165 @@
+// The words puzzles are read in and the runner's messages written in, kept
+// as data so another language is a file rather than a code change. A locale
+// file is the same small subset of TOML as the manifests, a table of the
+// words for each digit and one of message templates:
+//
+//     [digits]
+//     eins = 1
+//     zwei = 2
+//
+//     [messages]
+//     part = "Teil {part}"
+//
+// Messages a locale leaves out are written in English.
+
+use std::fs;
+use std::path::Path;
+
+use crate::manifest::{parse_value, strip_comment};
+use crate::strings::Trie;
+
+// Every message the runner writes, with its English template
+const MESSAGES: &[(&str, &str)] = &[
+    ("part", "part {part}"),
+    ("took", "took {time}"),
+    ("answer", "{year} day {day} part {part}: {answer}"),
+    ("parse", "parse"),
+    ("allocated", "allocated {bytes} in {count} allocations, peak {peak}"),
+    ("peak_rss", "peak resident set {rss}"),
+];
+
+const ENGLISH_DIGITS: &[&str] = &["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Digit words and message templates for one language, English built in and
+/// others read from locale files.
+///
+/// ```
+/// use aoc_common::locale::Locale;
+///
+/// let german = Locale::parse("de", "[digits]\neins = 1\nzwei = 2\n\n[messages]\npart = \"Teil {part}\"\n").unwrap();
+/// assert_eq!(german.digits, vec![(String::from("eins"), 1), (String::from("zwei"), 2)]);
+/// assert_eq!(german.message("part", &[("part", "2")]), "Teil 2");
+/// // left out, so in English
+/// assert_eq!(german.message("took", &[("time", "3ms")]), "took 3ms");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locale {
+    pub code: String,
+    pub digits: Vec<(String, u32)>,
+    messages: Vec<(String, String)>,
+}
+
+impl Locale {
+    pub fn english() -> Locale {
+        let digits = ENGLISH_DIGITS.iter().zip(1..).map(|(word, digit)| (word.to_string(), digit)).collect();
+        Locale { code: String::from("en"), digits, messages: vec![] }
+    }
+
+    pub fn is_english(&self) -> bool {
+        self.code == "en"
+    }
+
+    pub fn parse(code: &str, text: &str) -> Result<Locale, String> {
+        let mut locale = Locale { code: code.to_string(), digits: vec![], messages: vec![] };
+        let mut table = None;
+        for (idx, line) in text.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                if !["digits", "messages"].contains(&name) {
+                    return Err(format!("Line {}: unknown table [{}], expected [digits] or [messages]", line_number, name))
+                }
+                table = Some(name);
+                continue
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or(format!("Line {}: expected `key = value`, [digits] or [messages]", line_number))?;
+            let (key, value) = (key.trim(), parse_value(value.trim(), line_number)?);
+            match table {
+                Some("digits") => {
+                    let digit = value.parse::<u32>()
+                        .ok()
+                        .filter(|digit| *digit <= 9)
+                        .ok_or(format!("Line {}: '{}' must be a digit from 0 to 9", line_number, key))?;
+                    locale.digits.push((key.to_string(), digit));
+                }
+                Some(_) => {
+                    if !MESSAGES.iter().any(|(name, _)| *name == key) {
+                        return Err(format!("Line {}: unknown message '{}'", line_number, key))
+                    }
+                    locale.messages.push((key.to_string(), value));
+                }
+                None => return Err(format!("Line {}: `{}` is outside of a table", line_number, key)),
+            }
+        }
+        if locale.digits.is_empty() {
+            return Err(String::from("No [digits] words"))
+        }
+        Ok(locale)
+    }
+
+    // Reads a locale file, the locale's code being the file's name without
+    // its extension
+    pub fn load(path: &Path) -> Result<Locale, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        let code = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        Locale::parse(&code, &text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    // The digit words, for matching them anywhere in a line
+    pub fn digit_words(&self) -> Trie<u32> {
+        self.digits.iter().map(|(word, digit)| (word.as_str(), *digit)).collect()
+    }
+
+    // The message's template with each `{name}` filled in from `args`
+    pub fn message(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let template = self.messages.iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, template)| template.as_str())
+            .or_else(|| MESSAGES.iter().find(|(name, _)| *name == key).map(|(_, template)| *template))
+            .unwrap_or(key);
+        args.iter().fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Locale {
+        Locale::english()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english() {
+        let english = Locale::english();
+        assert!(english.is_english());
+        assert_eq!(english.digits.len(), 9);
+        assert_eq!(english.digit_words().get("seven"), Some(&7));
+        let args = [("year", "2023"), ("day", "8"), ("part", "2"), ("answer", "6")];
+        assert_eq!(english.message("answer", &args), "2023 day 8 part 2: 6");
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "# French\n[digits]\nun = 1\nune = 1 # both genders\ndeux = 2\n\n[messages]\ntook = \"a pris {time}\"\n";
+        let french = Locale::parse("fr", text).unwrap();
+        assert_eq!(french.digit_words().get("une"), Some(&1));
+        assert_eq!(french.message("took", &[("time", "5ms")]), "a pris 5ms");
+        assert!(!french.is_english());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Locale::parse("xx", ""), Err(String::from("No [digits] words")));
+        assert_eq!(Locale::parse("xx", "un = 1\n"), Err(String::from("Line 1: `un` is outside of a table")));
+        assert_eq!(Locale::parse("xx", "[words]\n"), Err(String::from("Line 1: unknown table [words], expected [digits] or [messages]")));
+        assert_eq!(Locale::parse("xx", "[digits]\ndix = 10\n"), Err(String::from("Line 2: 'dix' must be a digit from 0 to 9")));
+        assert_eq!(Locale::parse("xx", "[messages]\nhello = \"salut\"\n"), Err(String::from("Line 2: unknown message 'hello'")));
+    }
+}
@@ -0,0 +1,80 @@
+// Diagnostics for the day binaries, through tracing to stderr so piping a run
+// only ever captures answers. `-v` turns on debug and `-vv` trace, otherwise
+// RUST_LOG decides, and only warnings and errors show without either.
+
+use tracing::level_filters::LevelFilter;
+
+/// Whether the argument is `-v`, `-vv` and so on, which argument parsers skip
+/// like they skip other flags
+///
+/// ```
+/// use aoc_common::logging::is_verbose_flag;
+///
+/// assert!(is_verbose_flag("-v") && is_verbose_flag("-vv"));
+/// assert!(!is_verbose_flag("--verbose") && !is_verbose_flag("input.txt"));
+/// ```
+pub fn is_verbose_flag(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v')
+}
+
+/// The level `-v`, `-vv` and so on ask for, each v one level past info
+///
+/// ```
+/// use aoc_common::logging::verbosity;
+/// use tracing::level_filters::LevelFilter;
+///
+/// let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+/// assert_eq!(verbosity(&args(&["input.txt"])), None);
+/// assert_eq!(verbosity(&args(&["-v", "input.txt"])), Some(LevelFilter::DEBUG));
+/// assert_eq!(verbosity(&args(&["-v", "-v"])), Some(LevelFilter::TRACE));
+/// ```
+pub fn verbosity(args: &[String]) -> Option<LevelFilter> {
+    let count: usize = args.iter().filter(|arg| is_verbose_flag(arg)).map(|arg| arg.len() - 1).sum();
+    match count {
+        0 => None,
+        1 => Some(LevelFilter::DEBUG),
+        _ => Some(LevelFilter::TRACE),
+    }
+}
+
+/// Installs the subscriber for the rest of the run, at the level the
+/// arguments or RUST_LOG ask for
+///
+/// ```
+/// let args = vec![String::from("-v"), String::from("input.txt")];
+/// aoc_common::logging::init(&args);
+/// assert!(tracing::enabled!(tracing::Level::DEBUG));
+/// ```
+#[cfg(feature = "logging")]
+pub fn init(args: &[String]) {
+    use tracing_subscriber::filter::EnvFilter;
+
+    let filter = match verbosity(args) {
+        Some(level) => EnvFilter::default().add_directive(level.into()),
+        None => EnvFilter::builder().with_default_directive(LevelFilter::WARN.into()).from_env_lossy(),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .without_time()
+        .init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_verbosity() {
+        assert!(is_verbose_flag("-v") && is_verbose_flag("-vv"));
+        assert!(!is_verbose_flag("-") && !is_verbose_flag("--verbose") && !is_verbose_flag("5"));
+        assert_eq!(verbosity(&args(&["5", "--time"])), None);
+        assert_eq!(verbosity(&args(&["-v", "5"])), Some(LevelFilter::DEBUG));
+        assert_eq!(verbosity(&args(&["-vv"])), Some(LevelFilter::TRACE));
+        assert_eq!(verbosity(&args(&["-v", "-v"])), Some(LevelFilter::TRACE));
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+/// One run listed in a manifest, as read by `aoc batch` and the golden answer
+/// test, and the budgets `aoc verify --budget` holds runs to. Without an
+/// input the day's default input is used, and without an expected answer the
+/// run only has to succeed.
+///
+/// ```
+/// use aoc_common::manifest::{self, Entry};
+///
+/// let runs = manifest::parse("[[run]]\nyear = 2023\nday = 1\npart = 2\n").unwrap();
+/// assert_eq!(runs, vec![Entry { year: 2023, day: 1, part: 2, input: None, algorithm: None, expected: None, budget_ms: None }]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: Option<PathBuf>,
+    pub algorithm: Option<String>,
+    pub expected: Option<String>,
+    pub budget_ms: Option<u64>,
+}
+
+/// Reads the manifest, a small subset of TOML: a `[[run]]` table per run with
+/// `key = value` lines, values being integers or double quoted strings.
+///
+/// ```text
+/// [[run]]
+/// year = 2023
+/// day = 5
+/// part = 2
+/// input = "stress/day-5-large.txt"
+/// algorithm = "range-map"
+/// expected = "47909639"
+/// budget_ms = 50
+/// ```
+///
+/// ```
+/// use aoc_common::manifest;
+///
+/// let text = r#"
+/// [[run]]
+/// year = 2023
+/// day = 5
+/// part = 2
+/// expected = "46" # the example's answer
+/// budget_ms = 10
+/// "#;
+/// let runs = manifest::parse(text).unwrap();
+/// assert_eq!(runs[0].expected.as_deref(), Some("46"));
+/// assert_eq!(runs[0].budget_ms, Some(10));
+/// assert!(manifest::parse("year = 2023\n").is_err());
+/// ```
+pub fn parse(text: &str) -> Result<Vec<Entry>, String> {
+    let mut tables: Vec<Vec<(String, String, usize)>> = vec![];
+    for (idx, line) in text.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue
+        }
+        if line == "[[run]]" {
+            tables.push(vec![]);
+            continue
+        }
+        let (key, value) = line.split_once('=')
+            .ok_or(format!("Line {}: expected `key = value` or [[run]]", line_number))?;
+        let table = tables.last_mut()
+            .ok_or(format!("Line {}: `{}` is outside of a [[run]] table", line_number, key.trim()))?;
+        table.push((key.trim().to_string(), parse_value(value.trim(), line_number)?, line_number));
+    }
+    tables.iter().map(|table| to_run(table)).collect()
+}
+
+// A `#` starts a comment unless it's inside a string
+pub(crate) fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => (),
+        }
+    }
+    line
+}
+
+pub(crate) fn parse_value(value: &str, line_number: usize) -> Result<String, String> {
+    if let Some(string) = value.strip_prefix('"') {
+        return string.strip_suffix('"')
+            .filter(|string| !string.contains('"'))
+            .map(|string| string.to_string())
+            .ok_or(format!("Line {}: unterminated string", line_number))
+    }
+    value.parse::<u64>()
+        .map(|number| number.to_string())
+        .map_err(|_| format!("Line {}: expected a number or a string, got '{}'", line_number, value))
+}
+
+fn to_run(table: &[(String, String, usize)]) -> Result<Entry, String> {
+    let mut run = Entry {
+        year: 0,
+        day: 0,
+        part: 0,
+        input: None,
+        algorithm: None,
+        expected: None,
+        budget_ms: None,
+    };
+    for (key, value, line_number) in table.iter() {
+        let number = || value.parse::<u32>()
+            .map_err(|_| format!("Line {}: {} must be a number", line_number, key));
+        match key.as_str() {
+            "year" => run.year = number()?,
+            "day" => run.day = number()?,
+            "part" => run.part = number()?,
+            "input" => run.input = Some(PathBuf::from(value)),
+            "algorithm" => run.algorithm = Some(value.clone()),
+            "expected" => run.expected = Some(value.clone()),
+            "budget_ms" => run.budget_ms = Some(value.parse::<u64>()
+                .map_err(|_| format!("Line {}: {} must be a number", line_number, key))?),
+            _ => return Err(format!("Line {}: unknown key '{}'", line_number, key)),
+        }
+    }
+    let line_number = table.first().map(|(_, _, line_number)| *line_number).unwrap_or(0);
+    for (name, value) in [("year", run.year), ("day", run.day), ("part", run.part)] {
+        if value == 0 {
+            return Err(format!("Run starting on line {} is missing {}", line_number, name));
+        }
+    }
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "# regression sweep
+[[run]]
+year = 2023
+day = 4
+part = 1
+input = \"stress/day #4.txt\" # a comment
+expected = \"13\"
+
+[[run]]
+year = 2023
+day = 4
+part = 2
+algorithm = \"copy-counts\"
+";
+
+    #[test]
+    fn test_parse() {
+        let runs = parse(MANIFEST).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0], Entry {
+            year: 2023,
+            day: 4,
+            part: 1,
+            input: Some(PathBuf::from("stress/day #4.txt")),
+            algorithm: None,
+            expected: Some(String::from("13")),
+            budget_ms: None,
+        });
+        assert_eq!(runs[1].algorithm.as_deref(), Some("copy-counts"));
+        assert_eq!(runs[1].expected, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("day = 1"), Err(String::from("Line 1: `day` is outside of a [[run]] table")));
+        assert_eq!(parse("[[run]]\nday = one"), Err(String::from("Line 2: expected a number or a string, got 'one'")));
+        assert_eq!(parse("[[run]]\nday = \"1"), Err(String::from("Line 2: unterminated string")));
+        assert_eq!(parse("[[run]]\nyear = 2023\nday = 1"), Err(String::from("Run starting on line 2 is missing part")));
+        assert_eq!(parse("[[run]]\nspeed = 1"), Err(String::from("Line 2: unknown key 'speed'")));
+        assert_eq!(parse("[[run]]\nbudget_ms = \"fast\""), Err(String::from("Line 2: budget_ms must be a number")));
+    }
+}
@@ -0,0 +1,204 @@
+// Where a solution's memory goes. A binary that wants to know installs
+// `CountingAllocator` as its global allocator, which counts what each thread
+// allocates and the most it had live at once, so a run can see what it
+// allocated without another thread's allocations getting mixed in. Without it
+// installed every count stays at zero. The resident set size comes from the
+// OS instead, on Linux.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs;
+
+/// The system allocator, counting each thread's allocations. Install it with
+/// `#[global_allocator]` for `counted` to see anything.
+///
+/// ```
+/// use aoc_common::mem::{counted, CountingAllocator};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// fn main() {
+///     let (_, stats) = counted(|| vec![0u8; 64]);
+///     assert_eq!((stats.count, stats.bytes), (1, 64));
+/// }
+/// ```
+pub struct CountingAllocator;
+
+thread_local! {
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    // can go below zero on a thread freeing what another one allocated
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), layout.size() as i64);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), layout.size() as i64);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, new_size as i64 - layout.size() as i64);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = LIVE.try_with(|live| live.set(live.get() - layout.size() as i64));
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record(size: usize, grown: i64) {
+    // try_with, since the thread locals may already be gone while a thread
+    // is shutting down
+    let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+    let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size as u64));
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + grown);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+/// What a thread allocated: how many allocations, their bytes in all, and the
+/// most bytes it had allocated and not yet freed at any one time.
+///
+/// ```
+/// use aoc_common::mem::AllocStats;
+///
+/// let stats = AllocStats { count: 2, bytes: 96, peak: 64 };
+/// assert!(stats.peak <= stats.bytes);
+/// assert_eq!(AllocStats::default().count, 0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    pub count: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+/// Runs `f`, with what it allocated on this thread. The peak is over what was
+/// live when `f` started.
+///
+/// ```
+/// use aoc_common::mem::counted;
+///
+/// // nothing's counted without the allocator installed
+/// let (sum, stats) = counted(|| (1..=4).sum::<u32>());
+/// assert_eq!(sum, 10);
+/// assert_eq!(stats.count, 0);
+/// ```
+pub fn counted<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+    let (count, bytes) = (COUNT.with(Cell::get), BYTES.with(Cell::get));
+    let live = LIVE.with(Cell::get);
+    let outer_peak = PEAK.with(|peak| peak.replace(live));
+    let result = f();
+    let peak = PEAK.with(|peak| peak.replace(outer_peak.max(peak.get())));
+    let stats = AllocStats {
+        count: COUNT.with(Cell::get) - count,
+        bytes: BYTES.with(Cell::get) - bytes,
+        peak: (peak - live).max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// The process's resident set size in bytes, now and at its highest.
+///
+/// ```
+/// if let Some(rss) = aoc_common::mem::rss() {
+///     assert!(rss.current > 0 && rss.current <= rss.peak);
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rss {
+    pub current: u64,
+    pub peak: u64,
+}
+
+/// Samples the resident set size, from /proc/self/status; None where there
+/// isn't one.
+///
+/// ```
+/// let rss = aoc_common::mem::rss();
+/// assert_eq!(rss.is_some(), cfg!(target_os = "linux"));
+/// ```
+pub fn rss() -> Option<Rss> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let field = |name: &str| -> Option<u64> {
+        let line = status.lines().find(|line| line.starts_with(name))?;
+        let kb: u64 = line[name.len()..].trim().trim_end_matches("kB").trim().parse().ok()?;
+        Some(kb * 1024)
+    };
+    Some(Rss { current: field("VmRSS:")?, peak: field("VmHWM:")? })
+}
+
+/// A number of bytes as people read them, in KiB, MiB and so on.
+///
+/// ```
+/// use aoc_common::mem::human_bytes;
+///
+/// assert_eq!(human_bytes(512), "512 B");
+/// assert_eq!(human_bytes(1536), "1.5 KiB");
+/// assert_eq!(human_bytes(3 * 1024 * 1024), "3.0 MiB");
+/// ```
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes)
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // installed for the library's tests, so they count
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_counted() {
+        let (values, stats) = counted(|| vec![0u64; 16]);
+        assert_eq!(values.len(), 16);
+        assert_eq!(stats, AllocStats { count: 1, bytes: 128, peak: 128 });
+        let (_, stats) = counted(|| 1 + 1);
+        assert_eq!(stats, AllocStats::default());
+    }
+
+    #[test]
+    fn test_counted_peak() {
+        // two 1 KiB buffers live at once, then freed, then one more
+        let (_, stats) = counted(|| {
+            let first = vec![0u8; 1024];
+            let second = vec![0u8; 1024];
+            drop((first, second));
+            vec![0u8; 512]
+        });
+        assert_eq!(stats, AllocStats { count: 3, bytes: 2560, peak: 2048 });
+        // an inner count doesn't lower the outer one's peak
+        let (_, outer) = counted(|| {
+            drop(vec![0u8; 4096]);
+            counted(|| vec![0u8; 8]).1
+        });
+        assert_eq!(outer.peak, 4096);
+    }
+
+    #[test]
+    fn test_rss() {
+        let Some(rss) = rss() else { return };
+        assert!(rss.peak >= rss.current);
+    }
+}
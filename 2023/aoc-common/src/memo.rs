@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::collections::LruCache;
+
+/// Where a Memo keeps its results
+///
+/// ```
+/// use std::collections::HashMap;
+/// use aoc_common::memo::{Memo, MemoBackend};
+///
+/// let mut memo: Memo<u64, u64> = Memo::with_backend(HashMap::new());
+/// memo.call(3, |_, n| n * n);
+/// let mut backend = memo.backend().clone();
+/// assert_eq!(backend.lookup(&3), Some(9));
+/// ```
+pub trait MemoBackend<K, V> {
+    fn lookup(&mut self, key: &K) -> Option<V>;
+    fn store(&mut self, key: K, value: V);
+}
+
+impl<K: Eq + Hash, V: Clone> MemoBackend<K, V> for HashMap<K, V> {
+    fn lookup(&mut self, key: &K) -> Option<V> {
+        self.get(key).cloned()
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> MemoBackend<K, V> for LruCache<K, V> {
+    fn lookup(&mut self, key: &K) -> Option<V> {
+        self.get(key).cloned()
+    }
+
+    fn store(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
+}
+
+/// Remembers the results of a recursive function. The function gets the memo
+/// back as its first argument so it can recurse through it:
+///
+/// ```text
+/// fn fib(memo: &mut Memo<u64, u64>, n: &u64) -> u64 {
+///     if *n < 2 { return *n }
+///     memo.call(n - 1, fib) + memo.call(n - 2, fib)
+/// }
+/// ```
+///
+/// Everything is kept by default; when the state space is too big for that,
+/// an LruCache backend keeps only the most recently used results.
+///
+/// ```
+/// use aoc_common::memo::Memo;
+///
+/// fn fibonacci(memo: &mut Memo<u64, u64>, n: &u64) -> u64 {
+///     match *n {
+///         0 | 1 => *n,
+///         n => memo.call(n - 1, fibonacci) + memo.call(n - 2, fibonacci),
+///     }
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(memo.call(50, fibonacci), 12586269025);
+/// assert_eq!(memo.misses(), 51);
+/// ```
+#[derive(Debug)]
+pub struct Memo<K, V, B = HashMap<K, V>> {
+    backend: B,
+    hits: usize,
+    misses: usize,
+    _types: std::marker::PhantomData<(K, V)>,
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::with_backend(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V, LruCache<K, V>> {
+    pub fn bounded(capacity: usize) -> Memo<K, V, LruCache<K, V>> {
+        Memo::with_backend(LruCache::new(capacity))
+    }
+}
+
+impl<K, V: Clone, B: MemoBackend<K, V>> Memo<K, V, B> {
+    pub fn with_backend(backend: B) -> Memo<K, V, B> {
+        Memo { backend, hits: 0, misses: 0, _types: std::marker::PhantomData }
+    }
+
+    pub fn call<F>(&mut self, key: K, f: F) -> V
+    where
+        F: Fn(&mut Memo<K, V, B>, &K) -> V
+    {
+        if let Some(value) = self.backend.lookup(&key) {
+            self.hits += 1;
+            return value
+        }
+        self.misses += 1;
+        let value = f(self, &key);
+        self.backend.store(key, value.clone());
+        value
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: &u64) -> u64 {
+        if *n < 2 {
+            return *n
+        }
+        memo.call(n - 1, fib) + memo.call(n - 2, fib)
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.call(90, fib), 2880067194370816120);
+        // each value is computed once
+        assert_eq!(memo.misses(), 91);
+        assert_eq!(memo.backend().len(), 91);
+    }
+
+    // ways to climb a staircase taking one, two or three steps at a time
+    fn stairs(memo: &mut Memo<u64, u64, LruCache<u64, u64>>, n: &u64) -> u64 {
+        if *n == 0 {
+            return 1
+        }
+        (1..=3).filter(|step| step <= n)
+            .map(|step| memo.call(n - step, stairs))
+            .sum()
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(4);
+        assert_eq!(memo.call(40, stairs), 23837527729);
+        assert!(memo.backend().len() <= 4);
+        // the recursion only looks back three steps, so a tiny cache is
+        // enough to keep it linear
+        assert_eq!(memo.misses(), 41);
+        assert_eq!(Memo::<u64, u64, LruCache<u64, u64>>::bounded(4).call(0, stairs), 1);
+    }
+}
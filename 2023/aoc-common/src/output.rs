@@ -0,0 +1,252 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+// With `--output json` every answer is printed as one JSON object per line:
+//
+//     {"year":2023,"day":5,"part":1,"answer":"226172555","duration_ns":81234,
+//      "algorithm":"flatten","input_hash":"fnv1a64:6c62272e07bb0142"}
+//
+// year, day, part: numbers
+// answer: always a string, whatever the puzzle's answer looks like
+// duration_ns: time spent solving the part, not counting reading the input
+// algorithm: which variant of the solution produced the answer
+// input_hash: FNV-1a of the input file, prefixed with the hash used, so
+//     results from different inputs are never compared by mistake
+//
+// Fields are only ever added to this, never renamed or removed.
+
+/// How answers are printed, chosen with `--output`.
+///
+/// ```
+/// use aoc_common::output::OutputFormat;
+///
+/// let args = vec![String::from("--output"), String::from("json")];
+/// assert_eq!(OutputFormat::from_args(&args), Ok(OutputFormat::Json));
+/// assert_eq!(OutputFormat::from_args(&[]), Ok(OutputFormat::Text));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<OutputFormat, String> {
+        match value {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("Unknown output format '{}', expected text or json", value)),
+        }
+    }
+}
+
+impl OutputFormat {
+    // Finds `--output <format>` among the arguments, defaulting to text
+    pub fn from_args(args: &[String]) -> Result<OutputFormat, String> {
+        match args.iter().position(|arg| arg == "--output") {
+            Some(idx) => args.get(idx + 1)
+                .ok_or("--output needs a format")?
+                .parse(),
+            None => Ok(OutputFormat::default()),
+        }
+    }
+}
+
+/// One answer, with what it takes to compare it across runs. Each is a line
+/// of `--output json`.
+///
+/// ```
+/// use std::time::Duration;
+/// use aoc_common::output::{AnswerPrinter, OutputFormat};
+///
+/// let printer = AnswerPrinter::new(OutputFormat::Json, 2023, 1, "1abc2\n");
+/// let record = printer.record(1, "trie", 12, Duration::from_nanos(500));
+/// assert!(record.to_json().starts_with("{\"year\":2023,\"day\":1,\"part\":1,\"answer\":\"12\",\"duration_ns\":500,"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub duration: Duration,
+    pub algorithm: String,
+    pub input_hash: String,
+}
+
+impl AnswerRecord {
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"duration_ns\":{},\"algorithm\":{},\"input_hash\":{}}}",
+            self.year,
+            self.day,
+            self.part,
+            json_string(&self.answer),
+            self.duration.as_nanos(),
+            json_string(&self.algorithm),
+            json_string(&self.input_hash),
+        )
+    }
+}
+
+/// Prints a day's answers in the chosen format. Text keeps each day's own
+/// wording, so plain runs look the same as they always did.
+///
+/// ```
+/// use std::time::Duration;
+/// use aoc_common::output::{AnswerPrinter, OutputFormat};
+///
+/// let printer = AnswerPrinter::new(OutputFormat::Text, 2023, 4, "Card 1: 41 48 | 83 86 41\n");
+/// let record = printer.record(1, "winning-set", 1, Duration::from_millis(1));
+/// printer.print("Total points", &record);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnswerPrinter {
+    format: OutputFormat,
+    year: u32,
+    day: u32,
+    input_hash: String,
+}
+
+impl AnswerPrinter {
+    pub fn new(format: OutputFormat, year: u32, day: u32, input: &str) -> AnswerPrinter {
+        AnswerPrinter { format, year, day, input_hash: input_hash(input) }
+    }
+
+    pub fn record<T: fmt::Display>(
+        &self,
+        part: u32,
+        algorithm: &str,
+        answer: T,
+        duration: Duration
+    ) -> AnswerRecord {
+        AnswerRecord {
+            year: self.year,
+            day: self.day,
+            part,
+            answer: answer.to_string(),
+            duration,
+            algorithm: algorithm.to_string(),
+            input_hash: self.input_hash.clone(),
+        }
+    }
+
+    pub fn print(&self, label: &str, record: &AnswerRecord) {
+        match self.format {
+            OutputFormat::Text => println!("{}: {}", label, record.answer),
+            OutputFormat::Json => println!("{}", record.to_json()),
+        }
+    }
+}
+
+/// Runs `f`, returning its result along with how long it took
+///
+/// ```
+/// use aoc_common::output::timed;
+///
+/// let (sum, duration) = timed(|| (1..=100).sum::<u32>());
+/// assert_eq!(sum, 5050);
+/// assert!(duration.as_secs() < 1);
+/// ```
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// A short fingerprint of the input that stays the same across builds.
+///
+/// ```
+/// use aoc_common::output::input_hash;
+///
+/// assert_eq!(input_hash(""), "fnv1a64:cbf29ce484222325");
+/// assert_ne!(input_hash("a"), input_hash("b"));
+/// ```
+pub fn input_hash(input: &str) -> String {
+    // FNV-1a: tiny, stable across platforms and Rust versions, and only
+    // needs to tell inputs apart rather than resist tampering
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("fnv1a64:{:016x}", hash)
+}
+
+/// The value quoted and escaped as a JSON string
+///
+/// ```
+/// use aoc_common::output::json_string;
+///
+/// assert_eq!(json_string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+/// ```
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_json() {
+        let record = AnswerRecord {
+            year: 2023,
+            day: 5,
+            part: 1,
+            answer: String::from("226172555"),
+            duration: Duration::from_nanos(1500),
+            algorithm: String::from("flatten"),
+            input_hash: input_hash("seeds: 79 14 55 13\n"),
+        };
+        assert_eq!(
+            record.to_json(),
+            format!(
+                "{{\"year\":2023,\"day\":5,\"part\":1,\"answer\":\"226172555\",\"duration_ns\":1500,\"algorithm\":\"flatten\",\"input_hash\":\"{}\"}}",
+                record.input_hash,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_input_hash() {
+        // reference values for FNV-1a 64
+        assert_eq!(input_hash(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(input_hash("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_ne!(input_hash("abc\n"), input_hash("abc"));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a \"b\"\\\n"), "\"a \\\"b\\\"\\\\\\n\"");
+        assert_eq!(json_string("\u{1}é"), "\"\\u0001é\"");
+    }
+
+    #[test]
+    fn test_format_from_args() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+        assert_eq!(OutputFormat::from_args(&args(&["input.txt"])), Ok(OutputFormat::Text));
+        assert_eq!(OutputFormat::from_args(&args(&["input.txt", "--output", "json"])), Ok(OutputFormat::Json));
+        assert!(OutputFormat::from_args(&args(&["--output", "xml"])).is_err());
+        assert!(OutputFormat::from_args(&args(&["--output"])).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+// The C ABI a day exports when built as a plugin, so the runner can load it
+// from a plugins directory at run time instead of linking it in. A day opts
+// in behind its `plugin` feature:
+//
+//     #[cfg(feature = "plugin")]
+//     aoc_common::export_plugin!(2023, 5, &[1, 2], solve);
+//
+// and is built as a shared library with
+//
+//     cargo rustc -p day-5 --lib --release --features plugin --crate-type cdylib
+//
+// Only plain integers and a caller owned buffer cross the boundary, as with
+// aoc-ffi, so the runner and the plugin needn't share an allocator.
+
+use std::slice;
+
+/// Bumped whenever the exported functions change, so a stale plugin is
+/// refused rather than called wrongly.
+///
+/// ```
+/// assert_eq!(aoc_common::plugin::PLUGIN_ABI, 1);
+/// ```
+pub const PLUGIN_ABI: u32 = 1;
+
+/// `aoc_plugin_solve` wrote the answer.
+///
+/// ```
+/// use aoc_common::plugin::{PLUGIN_NO_SOLUTION, PLUGIN_OK};
+///
+/// assert_ne!(PLUGIN_OK, PLUGIN_NO_SOLUTION);
+/// ```
+pub const PLUGIN_OK: i32 = 0;
+
+/// `aoc_plugin_solve` wrote an error message instead of an answer.
+///
+/// ```
+/// assert_eq!(aoc_common::plugin::PLUGIN_NO_SOLUTION, 1);
+/// ```
+pub const PLUGIN_NO_SOLUTION: i32 = 1;
+
+/// The buffer couldn't hold the text, whose length was left in `len` so the
+/// call can be repeated with a bigger one.
+///
+/// ```
+/// assert_eq!(aoc_common::plugin::PLUGIN_BUFFER_TOO_SMALL, 2);
+/// ```
+pub const PLUGIN_BUFFER_TOO_SMALL: i32 = 2;
+
+/// A null pointer or an input that isn't UTF-8.
+///
+/// ```
+/// assert!(aoc_common::plugin::PLUGIN_INVALID_ARGUMENT < 0);
+/// ```
+pub const PLUGIN_INVALID_ARGUMENT: i32 = -1;
+
+/// Text going back to the caller, in memory the caller allocated
+///
+/// ```
+/// use aoc_common::plugin::PluginBuffer;
+///
+/// let mut data = vec![0u8; 16];
+/// let buffer = PluginBuffer { data: data.as_mut_ptr(), len: 0, capacity: data.len() };
+/// assert_eq!(buffer.capacity, 16);
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+/// The parts a plugin solves as a bit mask, bit 0 for part 1.
+///
+/// ```
+/// use aoc_common::plugin::{parts_from_mask, parts_mask};
+///
+/// assert_eq!(parts_mask(&[1, 2]), 0b11);
+/// assert_eq!(parts_from_mask(0b10), vec![2]);
+/// ```
+pub fn parts_mask(parts: &[u32]) -> u32 {
+    parts.iter().filter(|part| (1..=32).contains(*part)).fold(0, |mask, part| mask | 1 << (part - 1))
+}
+
+/// The parts in a mask from `parts_mask`, in order.
+///
+/// ```
+/// use aoc_common::plugin::parts_from_mask;
+///
+/// assert_eq!(parts_from_mask(0b11), vec![1, 2]);
+/// assert!(parts_from_mask(0).is_empty());
+/// ```
+pub fn parts_from_mask(mask: u32) -> Vec<u32> {
+    (1..=32).filter(|part| mask & 1 << (part - 1) != 0).collect()
+}
+
+/// What `aoc_plugin_solve` does for `export_plugin!`: checks the arguments,
+/// runs `solve` without letting a panic unwind into the caller and writes
+/// its answer or error into the buffer.
+///
+/// # Safety
+///
+/// `input_ptr` must point at `len` readable bytes, and `out_buf` at a
+/// PluginBuffer whose `data` has `capacity` writable bytes.
+///
+/// ```
+/// use aoc_common::plugin::{solve_into, PluginBuffer, PLUGIN_OK};
+///
+/// let solve = |part: u32, input: &str| Ok(format!("{} {}", part, input.len()));
+/// let mut data = vec![0u8; 8];
+/// let mut buffer = PluginBuffer { data: data.as_mut_ptr(), len: 0, capacity: 8 };
+/// let status = unsafe { solve_into(solve, 2, "abc".as_ptr(), 3, &mut buffer) };
+/// assert_eq!((status, &data[..buffer.len]), (PLUGIN_OK, &b"2 3"[..]));
+/// ```
+pub unsafe fn solve_into<F>(solve: F, part: u32, input_ptr: *const u8, len: usize, out_buf: *mut PluginBuffer) -> i32
+where
+    F: Fn(u32, &str) -> Result<String, String> + std::panic::RefUnwindSafe
+{
+    let Some(out_buf) = out_buf.as_mut() else {
+        return PLUGIN_INVALID_ARGUMENT
+    };
+    if (input_ptr.is_null() && len > 0) || (out_buf.data.is_null() && out_buf.capacity > 0) {
+        return PLUGIN_INVALID_ARGUMENT
+    }
+    let bytes = if len == 0 { &[][..] } else { slice::from_raw_parts(input_ptr, len) };
+    let Ok(input) = std::str::from_utf8(bytes) else {
+        return PLUGIN_INVALID_ARGUMENT
+    };
+
+    let result = std::panic::catch_unwind(|| solve(part, input))
+        .unwrap_or_else(|_| Err(String::from("Solution panicked")));
+    let (status, text) = match result {
+        Ok(answer) => (PLUGIN_OK, answer),
+        Err(err) => (PLUGIN_NO_SOLUTION, err),
+    };
+    out_buf.len = text.len();
+    if text.len() > out_buf.capacity {
+        return PLUGIN_BUFFER_TOO_SMALL
+    }
+    if !text.is_empty() {
+        std::ptr::copy_nonoverlapping(text.as_ptr(), out_buf.data, text.len());
+    }
+    status
+}
+
+/// Exports a day's `solve` as a plugin: the ABI version, the year, day and
+/// parts it solves, and `aoc_plugin_solve` running it. Only one crate in a
+/// build can export them, so days do it behind their `plugin` feature.
+///
+/// ```
+/// fn solve(part: u32, _input: &str) -> Result<String, String> {
+///     Ok(part.to_string())
+/// }
+///
+/// aoc_common::export_plugin!(2023, 25, &[1], solve);
+///
+/// assert_eq!(aoc_plugin_abi(), aoc_common::plugin::PLUGIN_ABI);
+/// assert_eq!((aoc_plugin_year(), aoc_plugin_day(), aoc_plugin_parts()), (2023, 25, 1));
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    ($year:expr, $day:expr, $parts:expr, $solve:path) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi() -> u32 {
+            $crate::plugin::PLUGIN_ABI
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_year() -> u32 {
+            $year
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_day() -> u32 {
+            $day
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_parts() -> u32 {
+            $crate::plugin::parts_mask($parts)
+        }
+
+        /// # Safety
+        ///
+        /// As for `aoc_common::plugin::solve_into`.
+        #[no_mangle]
+        pub unsafe extern "C" fn aoc_plugin_solve(
+            part: u32,
+            input_ptr: *const u8,
+            len: usize,
+            out_buf: *mut $crate::plugin::PluginBuffer
+        ) -> i32 {
+            $crate::plugin::solve_into($solve, part, input_ptr, len, out_buf)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(part: u32, input: &str, capacity: usize) -> (i32, String, usize) {
+        let solve = |part: u32, input: &str| match part {
+            1 => Ok(input.to_uppercase()),
+            2 => panic!("part 2 panics"),
+            _ => Err(format!("No part {}", part)),
+        };
+        let mut data = vec![0u8; capacity];
+        let mut buffer = PluginBuffer { data: data.as_mut_ptr(), len: 0, capacity };
+        let status = unsafe { solve_into(solve, part, input.as_ptr(), input.len(), &mut buffer) };
+        let written = String::from_utf8_lossy(&data[..buffer.len.min(capacity)]).into_owned();
+        (status, written, buffer.len)
+    }
+
+    #[test]
+    fn test_solve_into() {
+        assert_eq!(call(1, "abc", 8), (PLUGIN_OK, String::from("ABC"), 3));
+        assert_eq!(call(3, "", 16), (PLUGIN_NO_SOLUTION, String::from("No part 3"), 9));
+        assert_eq!(call(2, "", 32), (PLUGIN_NO_SOLUTION, String::from("Solution panicked"), 17));
+        assert_eq!(call(1, "abcdef", 2).0, PLUGIN_BUFFER_TOO_SMALL);
+        assert_eq!(call(1, "abcdef", 2).2, 6);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut buffer = PluginBuffer { data: std::ptr::null_mut(), len: 0, capacity: 0 };
+        let solve = |_: u32, _: &str| Ok(String::new());
+        let invalid = [0xffu8, 0xfe];
+        unsafe {
+            assert_eq!(solve_into(solve, 1, invalid.as_ptr(), invalid.len(), &mut buffer), PLUGIN_INVALID_ARGUMENT);
+            assert_eq!(solve_into(solve, 1, std::ptr::null(), 4, &mut buffer), PLUGIN_INVALID_ARGUMENT);
+            assert_eq!(solve_into(solve, 1, invalid.as_ptr(), 0, std::ptr::null_mut()), PLUGIN_INVALID_ARGUMENT);
+        }
+    }
+
+    #[test]
+    fn test_parts_mask() {
+        assert_eq!(parts_mask(&[2]), 0b10);
+        // parts past 32 don't fit
+        assert_eq!(parts_mask(&[0, 33]), 0);
+        assert_eq!(parts_from_mask(parts_mask(&[1, 2, 32])), vec![1, 2, 32]);
+    }
+}
@@ -0,0 +1,201 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::direction::Direction;
+
+/// A cell of a 2D grid, with y growing downwards.
+///
+/// ```
+/// use aoc_common::direction::Direction;
+/// use aoc_common::point::Point;
+///
+/// let point = Point::new(2, 3);
+/// assert_eq!(point.step(Direction::N, 2), Point::new(2, 1));
+/// assert_eq!(point.manhattan(&Point::new(0, 0)), 5);
+/// assert_eq!(point + Point::from((1, 1)), Point::new(3, 4));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // The point `n` moves away in the given direction
+    pub fn step(&self, direction: Direction, n: i64) -> Point {
+        let (dx, dy) = direction.offset();
+        Point { x: self.x + dx * n, y: self.y + dy * n }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Point {
+        Point { x, y }
+    }
+}
+
+/// A point with any number of integer coordinates, for puzzles in three or
+/// more dimensions
+///
+/// ```
+/// use aoc_common::point::PointN;
+///
+/// let point = PointN::new([1, 2, 3]);
+/// assert_eq!(point.manhattan(&PointN::default()), 6);
+/// assert_eq!(point.orthogonal_neighbors().len(), 6);
+/// assert_eq!(point.neighbors().len(), 26);
+/// assert_eq!(point * 2 - point, point);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointN<const N: usize> {
+    pub coords: [i64; N],
+}
+
+impl<const N: usize> Default for PointN<N> {
+    fn default() -> PointN<N> {
+        PointN { coords: [0; N] }
+    }
+}
+
+impl<const N: usize> PointN<N> {
+    pub fn new(coords: [i64; N]) -> PointN<N> {
+        PointN { coords }
+    }
+
+    pub fn manhattan(&self, other: &PointN<N>) -> i64 {
+        self.coords.iter()
+            .zip(other.coords.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    }
+
+    // The 2 * N points one step along a single axis
+    pub fn orthogonal_neighbors(&self) -> Vec<PointN<N>> {
+        let mut neighbors: Vec<PointN<N>> = Vec::with_capacity(2 * N);
+        for axis in 0..N {
+            for delta in [-1, 1] {
+                let mut neighbor = *self;
+                neighbor.coords[axis] += delta;
+                neighbors.push(neighbor);
+            }
+        }
+        neighbors
+    }
+
+    // All 3^N - 1 points that differ by at most one in every coordinate
+    pub fn neighbors(&self) -> Vec<PointN<N>> {
+        let mut neighbors: Vec<PointN<N>> = vec![*self];
+        for axis in 0..N {
+            neighbors = neighbors.into_iter()
+                .flat_map(|point| [-1, 0, 1].map(|delta| {
+                    let mut next = point;
+                    next.coords[axis] += delta;
+                    next
+                }))
+                .collect();
+        }
+        neighbors.retain(|point| point != self);
+        neighbors
+    }
+}
+
+impl<const N: usize> Add for PointN<N> {
+    type Output = PointN<N>;
+
+    fn add(self, other: PointN<N>) -> PointN<N> {
+        PointN { coords: std::array::from_fn(|idx| self.coords[idx] + other.coords[idx]) }
+    }
+}
+
+impl<const N: usize> Sub for PointN<N> {
+    type Output = PointN<N>;
+
+    fn sub(self, other: PointN<N>) -> PointN<N> {
+        PointN { coords: std::array::from_fn(|idx| self.coords[idx] - other.coords[idx]) }
+    }
+}
+
+impl<const N: usize> Mul<i64> for PointN<N> {
+    type Output = PointN<N>;
+
+    fn mul(self, factor: i64) -> PointN<N> {
+        PointN { coords: self.coords.map(|coord| coord * factor) }
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for PointN<N> {
+    fn from(coords: [i64; N]) -> PointN<N> {
+        PointN { coords }
+    }
+}
+
+// serde only implements Serialize for arrays up to a fixed length, so the
+// coordinates go out as a sequence instead
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for PointN<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(self.coords.as_slice(), serializer)
+    }
+}
+
+impl From<Point> for PointN<2> {
+    fn from(point: Point) -> PointN<2> {
+        PointN { coords: [point.x, point.y] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_point_n_arithmetic() {
+        let a = PointN::new([1, -2, 3]);
+        let b = PointN::new([4, 0, -1]);
+        assert_eq!(a + b, PointN::new([5, -2, 2]));
+        assert_eq!(a - b, PointN::new([-3, -2, 4]));
+        assert_eq!(a * 2, PointN::new([2, -4, 6]));
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(PointN::<3>::default(), PointN::new([0, 0, 0]));
+        assert_eq!(PointN::from(Point::new(3, 4)), PointN::new([3, 4]));
+    }
+
+    #[test]
+    fn test_point_n_neighbors() {
+        let origin = PointN::new([0, 0, 0, 0]);
+        let neighbors = origin.neighbors();
+        assert_eq!(neighbors.len(), 80);
+        assert_eq!(neighbors.iter().collect::<HashSet<_>>().len(), 80);
+        assert!(neighbors.iter().all(|n| n.coords.iter().all(|c| c.abs() <= 1)));
+
+        let orthogonal = PointN::new([5, 5, 5]).orthogonal_neighbors();
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.iter().all(|n| n.manhattan(&PointN::new([5, 5, 5])) == 1));
+        assert_eq!(PointN::new([7]).neighbors(), vec![PointN::new([6]), PointN::new([8])]);
+    }
+}
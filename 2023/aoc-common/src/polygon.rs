@@ -0,0 +1,199 @@
+use crate::point::Point;
+
+// Polygons are given as their vertices in order, either winding direction,
+// with the closing edge from the last vertex back to the first left implied
+
+/// Where a point lies relative to a polygon, as found by `locate`.
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::{locate, Location};
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+/// assert_eq!(locate(&square, &Point::new(4, 1)), Location::Boundary);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the enclosed area, from the shoelace formula. Doubling keeps it an
+/// integer, since a polygon on integer coordinates can have a half area.
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::double_area;
+///
+/// let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+/// assert_eq!(double_area(&triangle), 9);
+/// ```
+pub fn double_area(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// Number of integer points lying on the edges
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::boundary_points;
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+/// assert_eq!(boundary_points(&square), 16);
+/// ```
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+        .sum()
+}
+
+/// Number of integer points strictly inside, by Pick's theorem:
+/// area = interior + boundary / 2 - 1
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::interior_points;
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+/// assert_eq!(interior_points(&square), 9);
+/// ```
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    if vertices.len() < 3 {
+        return 0
+    }
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Interior and boundary together, i.e. every cell a dug out trench encloses
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::lattice_points;
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+/// assert_eq!(lattice_points(&square), 25);
+/// ```
+pub fn lattice_points(vertices: &[Point]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// Whether the point is inside the polygon, on its boundary or outside it.
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::polygon::{locate, Location};
+///
+/// let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+/// assert_eq!(locate(&square, &Point::new(2, 2)), Location::Inside);
+/// assert_eq!(locate(&square, &Point::new(0, 4)), Location::Boundary);
+/// assert_eq!(locate(&square, &Point::new(5, 2)), Location::Outside);
+/// ```
+pub fn locate(vertices: &[Point], point: &Point) -> Location {
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+        let within_x = a.x.min(b.x) <= point.x && point.x <= a.x.max(b.x);
+        let within_y = a.y.min(b.y) <= point.y && point.y <= a.y.max(b.y);
+        if cross == 0 && within_x && within_y {
+            return Location::Boundary
+        }
+
+        // cast a ray towards +x and count the edges it crosses, treating each
+        // edge as half-open in y so a ray through a vertex counts it once
+        if (a.y > point.y) != (b.y > point.y) {
+            // the sign of the cross product says which side of the edge the
+            // point is on, flipped for edges going down
+            if (cross > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside { Location::Inside } else { Location::Outside }
+}
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (&Point, &Point)> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if a == 0 {
+        return b
+    }
+    gcd(b % a, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(i64, i64)]) -> Vec<Point> {
+        points.iter().map(|p| Point::from(*p)).collect()
+    }
+
+    #[test]
+    fn test_square() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(lattice_points(&square), 25);
+        // winding the other way gives the same results
+        let reversed: Vec<Point> = square.iter().rev().cloned().collect();
+        assert_eq!(double_area(&reversed), 32);
+        assert_eq!(locate(&reversed, &Point::new(1, 3)), Location::Inside);
+    }
+
+    #[test]
+    fn test_triangle_with_half_area() {
+        // area 5.5, with no lattice points along the edges besides the corners
+        let triangle = polygon(&[(0, 0), (3, 1), (1, 4)]);
+        assert_eq!(double_area(&triangle), 11);
+        assert_eq!(boundary_points(&triangle), 3);
+        assert_eq!(interior_points(&triangle), 5);
+        // one edge here passes through (2, 2)
+        assert_eq!(boundary_points(&polygon(&[(0, 0), (3, 1), (1, 3)])), 4);
+    }
+
+    #[test]
+    fn test_concave() {
+        // an L shape: a 4x4 square with the top right 2x2 cut out
+        let shape = polygon(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)]);
+        assert_eq!(double_area(&shape), 24);
+        assert_eq!(boundary_points(&shape), 16);
+        assert_eq!(interior_points(&shape), 5);
+
+        assert_eq!(locate(&shape, &Point::new(1, 1)), Location::Inside);
+        assert_eq!(locate(&shape, &Point::new(3, 3)), Location::Inside);
+        assert_eq!(locate(&shape, &Point::new(3, 1)), Location::Outside);
+        assert_eq!(locate(&shape, &Point::new(2, 1)), Location::Boundary);
+        assert_eq!(locate(&shape, &Point::new(4, 4)), Location::Boundary);
+        assert_eq!(locate(&shape, &Point::new(5, 3)), Location::Outside);
+        // a ray through the (2, 2) and (4, 2) vertices
+        assert_eq!(locate(&shape, &Point::new(-1, 2)), Location::Outside);
+        assert_eq!(locate(&shape, &Point::new(1, 2)), Location::Inside);
+    }
+
+    #[test]
+    fn test_locate_matches_pick() {
+        let shape = polygon(&[(0, 0), (6, 0), (6, 5), (3, 2), (0, 5)]);
+        let located = |location: Location| -> i64 {
+            (-1..8)
+                .flat_map(|y| (-1..8).map(move |x| Point::new(x, y)))
+                .filter(|point| locate(&shape, point) == location)
+                .count() as i64
+        };
+        assert_eq!(located(Location::Inside), interior_points(&shape));
+        assert_eq!(located(Location::Boundary), boundary_points(&shape));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(double_area(&[]), 0);
+        assert_eq!(interior_points(&polygon(&[(0, 0), (5, 0)])), 0);
+        assert_eq!(locate(&[], &Point::new(0, 0)), Location::Outside);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::grid::Grid;
+use crate::spatial::Region;
+
+/// Summed-area table over a grid: after one pass to build it, the sum of any
+/// rectangle of cells comes out of four lookups
+///
+/// ```
+/// use aoc_common::grid::Grid;
+/// use aoc_common::point::Point;
+/// use aoc_common::prefix_sum::PrefixSum2D;
+/// use aoc_common::spatial::Region;
+///
+/// let grid = Grid::from_fn(3, 3, |x, y| (y * 3 + x) as u64);
+/// let sums = PrefixSum2D::new(&grid);
+/// assert_eq!(sums.total(), 36);
+/// assert_eq!(sums.sum(&Region::new(Point::new(1, 1), 2, 2)), 24);
+/// // the part outside the grid adds nothing
+/// assert_eq!(sums.sum(&Region::new(Point::new(2, 2), 5, 5)), 8);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSum2D {
+    width: usize,
+    height: usize,
+    // sums[y * (width + 1) + x] is the sum of every cell above and left of
+    // (x, y), so the first row and column are all zero
+    sums: Vec<u64>,
+}
+
+impl PrefixSum2D {
+    pub fn new(grid: &Grid<u64>) -> PrefixSum2D {
+        let (width, height) = (grid.width(), grid.height());
+        let stride = width + 1;
+        let mut sums = vec![0; stride * (height + 1)];
+        for (y, row) in grid.rows().take(height).enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                sums[(y + 1) * stride + x + 1] = value
+                    + sums[y * stride + x + 1]
+                    + sums[(y + 1) * stride + x]
+                    - sums[y * stride + x];
+            }
+        }
+        PrefixSum2D { width, height, sums }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn total(&self) -> u64 {
+        self.sums[self.sums.len() - 1]
+    }
+
+    // Sum of the cells in the region, ignoring any part of it that falls
+    // outside the grid
+    pub fn sum(&self, region: &Region) -> u64 {
+        let clamp = |value: i64, max: usize| value.clamp(0, max as i64) as usize;
+        let (left, right) = (clamp(region.left(), self.width), clamp(region.right(), self.width));
+        let (top, bottom) = (clamp(region.top(), self.height), clamp(region.bottom(), self.height));
+        if left >= right || top >= bottom {
+            return 0
+        }
+        let stride = self.width + 1;
+        self.sums[bottom * stride + right] + self.sums[top * stride + left]
+            - self.sums[top * stride + right]
+            - self.sums[bottom * stride + left]
+    }
+}
+
+impl From<&Grid<u64>> for PrefixSum2D {
+    fn from(grid: &Grid<u64>) -> PrefixSum2D {
+        PrefixSum2D::new(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+
+    #[test]
+    fn test_matches_brute_force() {
+        let grid = Grid::from_fn(7, 5, |x, y| ((x * 31 + y * 17) % 11) as u64);
+        let sums = PrefixSum2D::new(&grid);
+        assert_eq!(sums.total(), grid.iter().map(|(_, value)| value).sum::<u64>());
+
+        // every region, including ones hanging off each edge of the grid
+        for top in -2..7 {
+            for left in -2..9 {
+                for height in 0..5 {
+                    for width in 0..5 {
+                        let region = Region::new(Point::new(left, top), width, height);
+                        let expected: u64 = region.points()
+                            .filter_map(|point| grid.get(&point))
+                            .sum();
+                        assert_eq!(sums.sum(&region), expected, "{:?}", region);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let sums = PrefixSum2D::from(&Grid::filled(0, 0, 1));
+        assert_eq!(sums.total(), 0);
+        assert_eq!(sums.sum(&Region::new(Point::new(0, 0), 3, 3)), 0);
+    }
+}
@@ -0,0 +1,243 @@
+// How far a long run has got and roughly how long it has left, for brute
+// forces that can take minutes on a real input. The estimate assumes the
+// rest goes as fast as what's done so far. Nothing is shown for the first
+// second, so quick runs, like the brute forces on examples, stay quiet.
+
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// Before the first report and between reports
+const FIRST_REPORT: Duration = Duration::from_secs(1);
+const BAR_INTERVAL: Duration = Duration::from_millis(200);
+const LOG_INTERVAL: Duration = Duration::from_secs(10);
+const BAR_WIDTH: usize = 30;
+
+/// Where progress goes: a bar redrawn in place on stderr, or an info level
+/// log line now and then, for when stderr is a file.
+///
+/// ```
+/// use aoc_common::progress::ProgressStyle;
+///
+/// assert_ne!(ProgressStyle::Bar, ProgressStyle::Log);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProgressStyle {
+    Bar,
+    Log,
+}
+
+/// Counts work done out of a known total, reporting how far along it is and
+/// the time left every so often
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use aoc_common::progress::{Progress, ProgressStyle};
+///
+/// let mut progress = Progress::new("seeds", 1000).with_style(ProgressStyle::Log);
+/// for _ in 0..250 {
+///     progress.advance(1);
+/// }
+/// assert_eq!(progress.fraction(), 0.25);
+/// assert_eq!(progress.line(Duration::from_secs(3)), "seeds [#######.......................]  25.0% 250/1000, 3s elapsed, about 9s left");
+/// progress.finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: u64,
+    style: ProgressStyle,
+    started: Instant,
+    next_report: Instant,
+    // the clock is only read every so often, not on every step
+    next_check: u64,
+    check_every: u64,
+    reported: bool,
+}
+
+/// The time left if the rest goes at the rate the work done so far went,
+/// None until anything's done.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use aoc_common::progress::estimate;
+///
+/// assert_eq!(estimate(25, 100, Duration::from_secs(10)), Some(Duration::from_secs(30)));
+/// assert_eq!(estimate(0, 100, Duration::from_secs(10)), None);
+/// ```
+pub fn estimate(done: u64, total: u64, elapsed: Duration) -> Option<Duration> {
+    if done == 0 {
+        return None
+    }
+    let left = total.saturating_sub(done) as u128;
+    Some(Duration::from_nanos((elapsed.as_nanos() * left / done as u128).min(u64::MAX as u128) as u64))
+}
+
+// Whole seconds, or minutes and seconds past a minute
+fn seconds(duration: Duration) -> String {
+    match duration.as_secs() {
+        secs if secs < 60 => format!("{}s", secs),
+        secs if secs < 3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        secs => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+impl Progress {
+    // A bar when stderr is a terminal, log lines otherwise
+    pub fn new(label: &str, total: u64) -> Progress {
+        let style = if std::io::stderr().is_terminal() { ProgressStyle::Bar } else { ProgressStyle::Log };
+        let started = Instant::now();
+        let check_every = (total / 1000).max(1);
+        Progress {
+            label: label.to_string(),
+            total,
+            done: 0,
+            style,
+            started,
+            next_report: started + FIRST_REPORT,
+            next_check: check_every,
+            check_every,
+            reported: false,
+        }
+    }
+
+    pub fn with_style(self, style: ProgressStyle) -> Progress {
+        Progress { style, ..self }
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0
+        }
+        self.done.min(self.total) as f64 / self.total as f64
+    }
+
+    pub fn advance(&mut self, steps: u64) {
+        self.done += steps;
+        if self.done < self.next_check {
+            return
+        }
+        self.next_check = self.done + self.check_every;
+        let now = Instant::now();
+        if now >= self.next_report {
+            self.report(now - self.started);
+            self.next_report = now + if self.style == ProgressStyle::Bar { BAR_INTERVAL } else { LOG_INTERVAL };
+        }
+    }
+
+    pub fn line(&self, elapsed: Duration) -> String {
+        let filled = (self.fraction() * BAR_WIDTH as f64) as usize;
+        let left = match estimate(self.done, self.total, elapsed) {
+            Some(left) => format!("about {} left", seconds(left)),
+            None => String::from("no estimate yet"),
+        };
+        format!(
+            "{} [{}{}] {:>5.1}% {}/{}, {} elapsed, {}",
+            self.label,
+            "#".repeat(filled),
+            ".".repeat(BAR_WIDTH - filled),
+            self.fraction() * 100.0,
+            self.done,
+            self.total,
+            seconds(elapsed),
+            left,
+        )
+    }
+
+    fn report(&mut self, elapsed: Duration) {
+        self.reported = true;
+        match self.style {
+            ProgressStyle::Bar => {
+                let _ = write!(std::io::stderr().lock(), "\r{}\x1b[K", self.line(elapsed));
+            }
+            ProgressStyle::Log => tracing::info!("{}", self.line(elapsed)),
+        }
+    }
+
+    // Ends the bar's line, if one was drawn, so whatever comes next starts
+    // on a line of its own
+    pub fn finish(self) {
+        if self.reported && self.style == ProgressStyle::Bar {
+            let _ = writeln!(std::io::stderr().lock(), "\r{}\x1b[K", self.line(self.started.elapsed()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+    // The messages `f` logs, at every level
+    struct Messages(Arc<Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for Messages {
+        fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+            event.record(&mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                if field.name() == "message" {
+                    self.0.lock().unwrap().push(format!("{:?}", value));
+                }
+            });
+        }
+    }
+
+    fn capture(f: impl FnOnce()) -> Vec<String> {
+        let messages = Arc::new(Mutex::new(vec![]));
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(Messages(messages.clone())), f);
+        let messages = messages.lock().unwrap().clone();
+        messages
+    }
+
+    #[test]
+    fn test_estimate() {
+        assert_eq!(estimate(100, 100, Duration::from_secs(5)), Some(Duration::ZERO));
+        assert_eq!(estimate(1, 3, Duration::from_millis(10)), Some(Duration::from_millis(20)));
+        // more done than the total still doesn't go negative
+        assert_eq!(estimate(5, 3, Duration::from_secs(1)), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(seconds(Duration::from_millis(59_900)), "59s");
+        assert_eq!(seconds(Duration::from_secs(125)), "2m05s");
+        assert_eq!(seconds(Duration::from_secs(7380)), "2h03m");
+    }
+
+    #[test]
+    fn test_line() {
+        let mut progress = Progress::new("brute force", 0).with_style(ProgressStyle::Log);
+        assert_eq!(progress.fraction(), 1.0);
+        progress = Progress::new("brute force", 3).with_style(ProgressStyle::Log);
+        assert!(progress.line(Duration::ZERO).ends_with("  0.0% 0/3, 0s elapsed, no estimate yet"));
+        progress.advance(3);
+        assert_eq!(progress.line(Duration::from_secs(61)), format!("brute force [{}] 100.0% 3/3, 1m01s elapsed, about 0s left", "#".repeat(BAR_WIDTH)));
+    }
+
+    #[test]
+    fn test_quiet_at_first() {
+        let messages = capture(|| {
+            let mut progress = Progress::new("seeds", 10).with_style(ProgressStyle::Log);
+            for _ in 0..10 {
+                progress.advance(1);
+            }
+            progress.finish();
+        });
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn test_reports() {
+        let messages = capture(|| {
+            let mut progress = Progress::new("seeds", 10).with_style(ProgressStyle::Log);
+            progress.next_report = Instant::now();
+            progress.advance(4);
+            // not again until the interval has passed
+            progress.advance(4);
+        });
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("seeds [############..................]  40.0% 4/10"), "{:?}", messages);
+    }
+}
@@ -0,0 +1,305 @@
+// The days linked into a binary, each registered by its own crate with
+// `register_solution!` so the dispatcher finds them without a list of its
+// own. Registrations are gathered by `inventory` before main runs, so a day
+// is found as long as its crate is linked in.
+
+use std::sync::OnceLock;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::locale::Locale;
+use crate::solution::{Phases, Solution};
+use crate::verify::Verifier;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// Solves a part with one of the day's variants by name: (part, variant, input).
+///
+/// ```
+/// use aoc_common::registry::SolveVariant;
+///
+/// let shout: SolveVariant = |_, variant, input| Ok(format!("{} {}", variant, input.to_uppercase()));
+/// assert_eq!(shout(1, "loud", "hi"), Ok(String::from("loud HI")));
+/// ```
+pub type SolveVariant = fn(u32, &str, &str) -> Result<String, String>;
+
+/// Solves a part reading words in the locale's language: (locale, part, input).
+///
+/// ```
+/// use aoc_common::locale::Locale;
+/// use aoc_common::registry::SolveIn;
+///
+/// let language: SolveIn = |locale, _, _| Ok(locale.code.to_string());
+/// assert_eq!(language(&Locale::english(), 1, ""), Ok(String::from("en")));
+/// ```
+pub type SolveIn = fn(&Locale, u32, &str) -> Result<String, String>;
+
+/// Writes the day's input as a Graphviz graph.
+///
+/// ```
+/// use aoc_common::registry::ToDotFn;
+///
+/// let empty: ToDotFn = |_| Ok(String::from("digraph {}"));
+/// assert_eq!(empty("AAA = (BBB, CCC)"), Ok(String::from("digraph {}")));
+/// ```
+pub type ToDotFn = fn(&str) -> Result<String, String>;
+
+/// A day's entry in the registry: which puzzle it solves, its parts, its
+/// example, how it's solved, and how to solve it, one part at a time or every
+/// part with each phase timed.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::error::AocError;
+/// use aoc_common::registry::Registration;
+/// use aoc_common::solution::Solution;
+///
+/// struct Lines;
+///
+/// impl Solution for Lines {
+///     const YEAR: u32 = 2015;
+///     const DAY: u32 = 1;
+///     const PARTS: &'static [u32] = &[1];
+///     const TAGS: &'static [&'static str] = &["counting"];
+///     const ALGORITHMS: &'static [(u32, &'static str)] = &[(1, "lines")];
+///     type Input = usize;
+///
+///     fn parse(input: &str) -> Result<usize, AocError> {
+///         Ok(input.lines().count())
+///     }
+///
+///     fn part_one(lines: &usize) -> Result<Answer, AocError> {
+///         Ok(Answer::U64(*lines as u64))
+///     }
+/// }
+///
+/// let registration = Registration::of::<Lines>();
+/// assert_eq!((registration.year, registration.day, registration.parts), (2015, 1, &[1][..]));
+/// assert_eq!((registration.solve)(1, "a\nb\n").unwrap(), Answer::U64(2));
+/// assert_eq!((registration.phases)("a\nb\n").unwrap().parts[0].answer, Ok(Answer::U64(2)));
+/// assert_eq!(registration.example, "");
+/// assert_eq!((registration.tags, registration.algorithms), (&["counting"][..], &[(1, "lines")][..]));
+/// ```
+#[derive(Debug)]
+pub struct Registration {
+    pub year: u32,
+    pub day: u32,
+    pub parts: &'static [u32],
+    pub example: &'static str,
+    pub example_answers: &'static [(u32, &'static str)],
+    pub approach: &'static str,
+    // (part, complexity)
+    pub complexity: &'static [(u32, &'static str)],
+    pub tags: &'static [&'static str],
+    // (part, algorithm)
+    pub algorithms: &'static [(u32, &'static str)],
+    pub solve: fn(u32, &str) -> Result<Answer, AocError>,
+    pub phases: fn(&str) -> Result<Phases, AocError>,
+    // what only some days have, added with the `with_` methods: other
+    // implementations to solve with by name, solving in another language,
+    // a brute force to check against, and the input as a Graphviz graph
+    pub variants: &'static [&'static str],
+    pub solve_variant: Option<SolveVariant>,
+    pub solve_in: Option<SolveIn>,
+    pub verifier: Option<&'static dyn Verifier>,
+    pub to_dot: Option<ToDotFn>,
+}
+
+impl Registration {
+    pub const fn of<S: Solution>() -> Registration {
+        Registration {
+            year: S::YEAR,
+            day: S::DAY,
+            parts: S::PARTS,
+            example: S::EXAMPLE,
+            example_answers: S::EXAMPLE_ANSWERS,
+            approach: S::APPROACH,
+            complexity: S::COMPLEXITY,
+            tags: S::TAGS,
+            algorithms: S::ALGORITHMS,
+            solve: S::solve,
+            phases: S::phases,
+            variants: &[],
+            solve_variant: None,
+            solve_in: None,
+            verifier: None,
+            to_dot: None,
+        }
+    }
+
+    // The variants the day can be solved with, each name one `solve` takes
+    pub const fn with_variants(self, variants: &'static [&'static str], solve: SolveVariant) -> Registration {
+        Registration { variants, solve_variant: Some(solve), ..self }
+    }
+
+    pub const fn with_solve_in(self, solve: SolveIn) -> Registration {
+        Registration { solve_in: Some(solve), ..self }
+    }
+
+    pub const fn with_verifier(self, verifier: &'static dyn Verifier) -> Registration {
+        Registration { verifier: Some(verifier), ..self }
+    }
+
+    pub const fn with_to_dot(self, to_dot: ToDotFn) -> Registration {
+        Registration { to_dot: Some(to_dot), ..self }
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Every registered day, by year then day. A day registered twice is a bug in
+/// the binary's days, and panics.
+///
+/// ```
+/// use aoc_common::error::AocError;
+///
+/// struct Echo;
+///
+/// impl aoc_common::solution::Solution for Echo {
+///     const YEAR: u32 = 2015;
+///     const DAY: u32 = 2;
+///     const PARTS: &'static [u32] = &[1];
+///     type Input = String;
+///
+///     fn parse(input: &str) -> Result<String, AocError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn part_one(input: &String) -> Result<aoc_common::answer::Answer, AocError> {
+///         Ok(input.as_str().into())
+///     }
+/// }
+///
+/// aoc_common::register_solution!(2015, 2, Echo);
+///
+/// let days: Vec<(u32, u32)> = aoc_common::registry::registered().iter().map(|day| (day.year, day.day)).collect();
+/// assert_eq!(days, vec![(2015, 2)]);
+/// ```
+pub fn registered() -> &'static [&'static Registration] {
+    static REGISTERED: OnceLock<Vec<&'static Registration>> = OnceLock::new();
+    REGISTERED.get_or_init(|| {
+        let mut days: Vec<&'static Registration> = inventory::iter::<Registration>.into_iter().collect();
+        days.sort_by_key(|day| (day.year, day.day));
+        if let Some(pair) = days.windows(2).find(|pair| (pair[0].year, pair[0].day) == (pair[1].year, pair[1].day)) {
+            panic!("{} day {} is registered more than once", pair[0].year, pair[0].day);
+        }
+        days
+    })
+}
+
+/// The registered day, if there is one.
+///
+/// ```
+/// assert!(aoc_common::registry::find(1999, 1).is_none());
+/// ```
+pub fn find(year: u32, day: u32) -> Option<&'static Registration> {
+    registered().iter().find(|registration| (registration.year, registration.day) == (year, day)).copied()
+}
+
+/// Registers a type implementing `Solution` as the given year and day, which
+/// have to match its own `YEAR` and `DAY`, e.g. `register_solution!(2023, 5, Day5)`.
+/// Anything else the day offers follows as calls to `Registration`'s `with_`
+/// methods, e.g. `register_solution!(2023, 8, Day8, with_to_dot(to_dot))`.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::error::AocError;
+/// use aoc_common::solution::Solution;
+///
+/// struct Count;
+///
+/// impl Solution for Count {
+///     const YEAR: u32 = 2015;
+///     const DAY: u32 = 3;
+///     const PARTS: &'static [u32] = &[2];
+///     type Input = usize;
+///
+///     fn parse(input: &str) -> Result<usize, AocError> {
+///         Ok(input.len())
+///     }
+///
+///     fn part_two(len: &usize) -> Result<Answer, AocError> {
+///         Ok(Answer::U64(*len as u64))
+///     }
+/// }
+///
+/// // the input's length in bytes or in characters
+/// aoc_common::register_solution!(2015, 3, Count, with_variants(&["bytes", "chars"], |_, variant, input| {
+///     match variant {
+///         "bytes" => Ok(input.len().to_string()),
+///         _ => Ok(input.chars().count().to_string()),
+///     }
+/// }));
+///
+/// let count = aoc_common::registry::find(2015, 3).unwrap();
+/// assert_eq!((count.solve)(2, "abc").unwrap(), Answer::U64(3));
+/// assert_eq!(count.variants, &["bytes", "chars"]);
+/// assert_eq!((count.solve_variant.unwrap())(2, "chars", "ab½"), Ok(String::from("3")));
+/// assert!(count.verifier.is_none() && count.to_dot.is_none());
+/// ```
+#[macro_export]
+macro_rules! register_solution {
+    ($year:expr, $day:expr, $solution:ty $(, $with:ident($($arg:expr),* $(,)?))* $(,)?) => {
+        const _: () = assert!(
+            <$solution as $crate::solution::Solution>::YEAR == $year && <$solution as $crate::solution::Solution>::DAY == $day,
+            "register_solution! year and day don't match the solution's YEAR and DAY",
+        );
+        $crate::registry::inventory::submit! {
+            $crate::registry::Registration::of::<$solution>()$(.$with($($arg),*))*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Twice;
+
+    impl Solution for Twice {
+        const YEAR: u32 = 2016;
+        const DAY: u32 = 1;
+        const PARTS: &'static [u32] = &[1, 2];
+        type Input = u32;
+
+        fn parse(input: &str) -> Result<u32, AocError> {
+            input.trim().parse().map_err(|_| AocError::Parse(format!("Invalid number '{}'", input.trim())))
+        }
+
+        fn part_one(n: &u32) -> Result<Answer, AocError> {
+            Ok((n * 2).into())
+        }
+
+        fn part_two(n: &u32) -> Result<Answer, AocError> {
+            Ok((n * 4).into())
+        }
+    }
+
+    struct Later;
+
+    impl Solution for Later {
+        const YEAR: u32 = 2016;
+        const DAY: u32 = 7;
+        const PARTS: &'static [u32] = &[];
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<(), AocError> {
+            Ok(())
+        }
+    }
+
+    crate::register_solution!(2016, 7, Later);
+    crate::register_solution!(2016, 1, Twice);
+
+    #[test]
+    fn test_registered() {
+        let days: Vec<(u32, u32)> = registered().iter().map(|day| (day.year, day.day)).collect();
+        assert_eq!(days, vec![(2016, 1), (2016, 7)]);
+        let twice = find(2016, 1).unwrap();
+        assert_eq!(twice.parts, &[1, 2]);
+        assert_eq!((twice.solve)(2, "5\n").unwrap(), Answer::U64(20));
+        assert_eq!((twice.solve)(1, "x").unwrap_err().to_string(), "Invalid number 'x'");
+        assert!(find(2016, 2).is_none());
+    }
+}
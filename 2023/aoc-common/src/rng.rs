@@ -0,0 +1,107 @@
+// Seeded randomness for generated inputs, property tests and anything else
+// that rolls dice. Everything starts from an explicit seed, taken from
+// `--seed <n>` or the AOC_SEED environment variable when either is given, so
+// a failure can always be rerun with the seed it printed.
+
+/// Xorshift, plenty for test inputs and reproducible from the seed alone
+///
+/// ```
+/// use aoc_common::rng::Rng;
+///
+/// let mut rng = Rng::new(1731);
+/// let rolls: Vec<u64> = (0..5).map(|_| rng.below(6)).collect();
+/// assert!(rolls.iter().all(|roll| *roll < 6));
+/// assert_eq!(rng.seed(), 1731);
+///
+/// let mut again = Rng::new(1731);
+/// assert_eq!((0..5).map(|_| again.below(6)).collect::<Vec<u64>>(), rolls);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // zero is the one state xorshift never leaves
+        Rng { seed, state: seed.max(1) }
+    }
+
+    // Seeded from the arguments or AOC_SEED, or else `default`
+    pub fn from_args(args: &[String], default: u64) -> Result<Rng, String> {
+        Ok(Rng::new(seed(args, default)?))
+    }
+
+    // What the generator started from, for failure messages
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // Uniform enough in 0..bound for bounds far below 2^64
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in 0..items.len() {
+            let other = idx + self.below((items.len() - idx) as u64) as usize;
+            items.swap(idx, other);
+        }
+    }
+}
+
+/// The value of `--seed` in the arguments, or else of AOC_SEED, or else
+/// `default`
+///
+/// ```
+/// use aoc_common::rng;
+///
+/// let args: Vec<String> = ["verify", "--seed", "42"].iter().map(|arg| arg.to_string()).collect();
+/// assert_eq!(rng::seed(&args, 1), Ok(42));
+/// assert!(rng::seed(&[String::from("--seed")], 1).is_err());
+/// assert!(rng::seed(&[String::from("--seed"), String::from("x")], 1).is_err());
+/// ```
+pub fn seed(args: &[String], default: u64) -> Result<u64, String> {
+    let value = match args.iter().position(|arg| arg == "--seed") {
+        Some(idx) => args.get(idx + 1).ok_or("--seed needs a value")?.clone(),
+        None => match std::env::var("AOC_SEED") {
+            Ok(value) if !value.is_empty() => value,
+            _ => return Ok(default),
+        },
+    };
+    value.parse().map_err(|_| format!("Invalid seed '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_seed() {
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.seed(), 0);
+        assert_ne!(rng.next_u64(), 0);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Rng::new(7);
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,330 @@
+// The shape every day's solution has: a parse of the input into the day's
+// model, then a function per part working on it. Days implement `Solution` on
+// a unit struct and get `solve(part, input)` from it, so the runner, the
+// tests and the benchmarks can call any day the same way.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::logging::is_verbose_flag;
+use crate::mem::{counted, AllocStats};
+use crate::output::timed;
+use crate::strings::normalize_input;
+
+/// Where a day's time went on an input: its size, parsing it once, then each
+/// part on the parse, with what each phase allocated when the binary counts
+/// allocations.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::error::AocError;
+/// use aoc_common::solution::{Phases, Solution};
+///
+/// struct Lines;
+///
+/// impl Solution for Lines {
+///     const YEAR: u32 = 2023;
+///     const DAY: u32 = 23;
+///     const PARTS: &'static [u32] = &[1, 2];
+///     type Input = Vec<String>;
+///
+///     fn parse(input: &str) -> Result<Vec<String>, AocError> {
+///         Ok(input.lines().map(|line| line.to_string()).collect())
+///     }
+///
+///     fn part_one(lines: &Vec<String>) -> Result<Answer, AocError> {
+///         Ok((lines.len() as u64).into())
+///     }
+/// }
+///
+/// let phases: Phases = Lines::phases("a\nb\n").unwrap();
+/// assert_eq!(phases.input_bytes, 4);
+/// let answers: Vec<_> = phases.parts.into_iter().map(|part| (part.part, part.answer)).collect();
+/// assert_eq!(answers, vec![(1, Ok(Answer::U64(2))), (2, Err(String::from("Day 23 has no solution for part 2")))]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phases {
+    pub input_bytes: usize,
+    pub parse: Duration,
+    pub parse_alloc: AllocStats,
+    // each part the day solves
+    pub parts: Vec<PartPhase>,
+}
+
+impl Phases {
+    // The time over every phase
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|part| part.duration).sum::<Duration>()
+    }
+}
+
+/// A part solved on the parsed input: its answer, how long it took and what
+/// it allocated.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::mem::AllocStats;
+/// use aoc_common::solution::PartPhase;
+/// use std::time::Duration;
+///
+/// let part = PartPhase { part: 1, answer: Ok(Answer::U64(8)), duration: Duration::from_micros(3), alloc: AllocStats::default() };
+/// assert_eq!(part.answer.unwrap().to_string(), "8");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartPhase {
+    pub part: u32,
+    pub answer: Result<Answer, String>,
+    pub duration: Duration,
+    pub alloc: AllocStats,
+}
+
+/// A day's puzzle, parsed once and solved per part. A part the day doesn't
+/// solve is left out of `PARTS` and keeps its default, which says so.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::error::AocError;
+/// use aoc_common::solution::Solution;
+///
+/// struct Sums;
+///
+/// impl Solution for Sums {
+///     const YEAR: u32 = 2023;
+///     const DAY: u32 = 25;
+///     const PARTS: &'static [u32] = &[1];
+///     type Input = Vec<u32>;
+///
+///     fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+///         input.lines()
+///             .enumerate()
+///             .map(|(idx, line)| line.parse().map_err(|_| AocError::Lex { line: idx + 1, message: format!("Invalid number '{}'", line) }))
+///             .collect()
+///     }
+///
+///     fn part_one(numbers: &Vec<u32>) -> Result<Answer, AocError> {
+///         Ok(numbers.iter().sum::<u32>().into())
+///     }
+/// }
+///
+/// assert_eq!(Sums::solve(1, "1\n2\n").unwrap(), Answer::U64(3));
+/// assert_eq!(Sums::solve(2, "1\n2\n").unwrap_err().to_string(), "Day 25 has no solution for part 2");
+/// assert_eq!(Sums::solve(1, "1\nx\n").unwrap_err().to_string(), "Line 2: Invalid number 'x'");
+/// assert_eq!(Sums::solve_part1("4\n5\n").unwrap(), Answer::U64(9));
+/// // no example bundled, so nothing to check
+/// assert!(Sums::check_example().is_ok());
+/// ```
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+    // the parts the day solves
+    const PARTS: &'static [u32];
+    // the puzzle's sample input, bundled with include_str!, and the answers
+    // the puzzle gives for it
+    const EXAMPLE: &'static str = "";
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[];
+    // how the day is solved, for `aoc info` and picking days by tag, with
+    // each part's complexity in the names the approach gives its sizes
+    const APPROACH: &'static str = "";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[];
+    const TAGS: &'static [&'static str] = &[];
+    // the algorithm each part uses, named as in the day's own output
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[];
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+
+    fn part_one(_input: &Self::Input) -> Result<Answer, AocError> {
+        Err(AocError::NoSolution { day: Self::DAY, part: 1 })
+    }
+
+    fn part_two(_input: &Self::Input) -> Result<Answer, AocError> {
+        Err(AocError::NoSolution { day: Self::DAY, part: 2 })
+    }
+
+    // Parses the input and solves the part, failing before parsing for a
+    // part the day doesn't solve
+    fn solve(part: u32, input: &str) -> Result<Answer, AocError> {
+        if !Self::PARTS.contains(&part) {
+            return Err(AocError::NoSolution { day: Self::DAY, part })
+        }
+        let input = Self::parse(input)?;
+        let _span = tracing::info_span!("solve").entered();
+        solve_parsed::<Self>(part, &input)
+    }
+
+    // Parses the input once and solves every part on it, timing each phase
+    // and counting what it allocates
+    fn phases(input: &str) -> Result<Phases, AocError> {
+        let ((parsed, parse), parse_alloc) = counted(|| timed(|| Self::parse(input)));
+        let parsed = parsed?;
+        let _span = tracing::info_span!("solve").entered();
+        let parts = Self::PARTS.iter()
+            .map(|part| {
+                let ((answer, duration), alloc) = counted(|| timed(|| solve_parsed::<Self>(*part, &parsed)));
+                PartPhase { part: *part, answer: answer.map_err(String::from), duration, alloc }
+            })
+            .collect();
+        Ok(Phases { input_bytes: input.len(), parse, parse_alloc, parts })
+    }
+
+    // Solves the example, failing on a part that doesn't give the puzzle's
+    // answer for it
+    fn check_example() -> Result<(), AocError> {
+        for (part, expected) in Self::EXAMPLE_ANSWERS {
+            let answer = Self::solve(*part, Self::EXAMPLE)?;
+            if answer != *expected {
+                return Err(AocError::WrongExample {
+                    day: Self::DAY,
+                    part: *part,
+                    answer: answer.to_string(),
+                    expected: expected.to_string(),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn solve_part1(input: &str) -> Result<Answer, AocError> {
+        Self::solve(1, input)
+    }
+
+    fn solve_part2(input: &str) -> Result<Answer, AocError> {
+        Self::solve(2, input)
+    }
+}
+
+/// What a day's binary runs on: the file its first argument names, or with
+/// `--example` the day's bundled example, normalized either way as
+/// `strings::normalize_input` does, so CRLF files read the same as any other.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::error::AocError;
+/// use aoc_common::solution::{self, Solution};
+///
+/// struct Count;
+///
+/// impl Solution for Count {
+///     const YEAR: u32 = 2023;
+///     const DAY: u32 = 21;
+///     const PARTS: &'static [u32] = &[1];
+///     const EXAMPLE: &'static str = "...\r\n\r\n";
+///     type Input = usize;
+///
+///     fn parse(input: &str) -> Result<usize, AocError> {
+///         Ok(input.trim().len())
+///     }
+/// }
+///
+/// let args = vec![String::from("--example")];
+/// assert_eq!(solution::read_input::<Count>(&args).unwrap(), "...\n");
+/// assert_eq!(solution::read_input::<Count>(&[]).unwrap_err(), "No input file provided");
+/// ```
+pub fn read_input<S: Solution>(args: &[String]) -> Result<String, String> {
+    if args.iter().any(|arg| arg == "--example") {
+        return Ok(normalize_input(S::EXAMPLE).into_owned())
+    }
+    let path = args.iter().find(|arg| !is_verbose_flag(arg)).ok_or("No input file provided")?;
+    let input = AocError::read(Path::new(path))?;
+    Ok(normalize_input(&input).into_owned())
+}
+
+fn solve_parsed<S: Solution + ?Sized>(part: u32, input: &S::Input) -> Result<Answer, AocError> {
+    match part {
+        1 => S::part_one(input),
+        2 => S::part_two(input),
+        _ => Err(AocError::NoSolution { day: S::DAY, part }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Words;
+
+    impl Solution for Words {
+        const YEAR: u32 = 2023;
+        const DAY: u32 = 24;
+        const PARTS: &'static [u32] = &[2];
+        const EXAMPLE: &'static str = "x y";
+        const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(2, "X Y")];
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Vec<String>, AocError> {
+            Ok(input.split_whitespace().map(|word| word.to_string()).collect())
+        }
+
+        fn part_two(words: &Vec<String>) -> Result<Answer, AocError> {
+            Ok(words.join(" ").to_uppercase().into())
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let message = |result: Result<Answer, AocError>| result.map_err(String::from);
+        assert_eq!(message(Words::solve(2, "a b c")), Ok(Answer::from("A B C")));
+        assert_eq!(message(Words::solve(1, "a b c")), Err(String::from("Day 24 has no solution for part 1")));
+        assert_eq!(message(Words::solve(3, "")), Err(String::from("Day 24 has no solution for part 3")));
+        assert_eq!(message(Words::solve_part2("a b")), Ok(Answer::from("A B")));
+        assert_eq!(message(Words::solve_part1("a b")), Err(String::from("Day 24 has no solution for part 1")));
+        // the default, for a part listed without an implementation
+        assert!(matches!(Words::part_one(&vec![]), Err(AocError::NoSolution { day: 24, part: 1 })));
+    }
+
+    struct Wrong;
+
+    impl Solution for Wrong {
+        const YEAR: u32 = 2023;
+        const DAY: u32 = 22;
+        const PARTS: &'static [u32] = &[1];
+        const EXAMPLE: &'static str = "1 2";
+        const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(1, "3")];
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String, AocError> {
+            Ok(input.to_string())
+        }
+
+        fn part_one(input: &String) -> Result<Answer, AocError> {
+            Ok(input.as_str().into())
+        }
+    }
+
+    #[test]
+    fn test_check_example() {
+        assert!(Words::check_example().is_ok());
+        assert_eq!(Wrong::check_example().unwrap_err().to_string(), "Day 22 part 1 gives 1 2 for the example, not 3");
+    }
+
+    #[test]
+    fn test_read_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        std::fs::write(&path, "a b  \r\nc\r\n\r\n").unwrap();
+        let args = vec![path.to_string_lossy().into_owned()];
+        assert_eq!(read_input::<Words>(&args), Ok(String::from("a b\nc\n")));
+        // -v and -vv are for the logging, not the input
+        let verbose = vec![String::from("-vv"), args[0].clone()];
+        assert_eq!(read_input::<Words>(&verbose), Ok(String::from("a b\nc\n")));
+        let missing = vec![dir.path().join("missing.txt").to_string_lossy().into_owned()];
+        assert!(read_input::<Words>(&missing).unwrap_err().starts_with("Could not read "));
+        assert_eq!(read_input::<Words>(&[String::from("--example")]), Ok(String::from("x y\n")));
+    }
+
+    #[test]
+    fn test_phases() {
+        let phases = Words::phases("a b").unwrap();
+        assert_eq!(phases.input_bytes, 3);
+        assert_eq!(phases.parts.len(), 1);
+        let part = &phases.parts[0];
+        assert_eq!((part.part, &part.answer), (2, &Ok(Answer::from("A B"))));
+        assert_eq!(phases.total(), phases.parse + part.duration);
+        // the words and the vector holding them, counted by the allocator
+        // installed for these tests
+        assert_eq!(phases.parse_alloc.count, 3);
+    }
+}
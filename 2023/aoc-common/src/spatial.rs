@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use crate::point::Point;
+
+/// A half-open rectangle of grid cells, anchored at its top-left corner
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::spatial::Region;
+///
+/// let region = Region::new(Point::new(1, 1), 3, 2);
+/// assert_eq!((region.right(), region.bottom(), region.area()), (4, 3, 6));
+/// assert!(region.contains(&Point::new(3, 2)) && !region.contains(&Point::new(4, 2)));
+/// assert!(region.intersects(&Region::point(Point::new(0, 0)).expand(1)));
+/// assert_eq!(region.distance_to(&Point::new(6, 1)), 3);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Region {
+    pub origin: Point,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Region {
+    pub fn new(origin: Point, width: i64, height: i64) -> Region {
+        Region { origin, width, height }
+    }
+
+    pub fn point(point: Point) -> Region {
+        Region::new(point, 1, 1)
+    }
+
+    pub fn left(&self) -> i64 {
+        self.origin.x
+    }
+
+    pub fn top(&self) -> i64 {
+        self.origin.y
+    }
+
+    pub fn right(&self) -> i64 {
+        self.origin.x + self.width
+    }
+
+    pub fn bottom(&self) -> i64 {
+        self.origin.y + self.height
+    }
+
+    pub fn area(&self) -> i64 {
+        self.width.max(0) * self.height.max(0)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.left() && point.x < self.right()
+            && point.y >= self.top() && point.y < self.bottom()
+    }
+
+    pub fn intersects(&self, other: &Region) -> bool {
+        self.left() < other.right() && other.left() < self.right()
+            && self.top() < other.bottom() && other.top() < self.bottom()
+    }
+
+    // Grows the region by `by` cells in every direction, so `expand(1)`
+    // covers the region and everything touching it, diagonals included
+    pub fn expand(&self, by: i64) -> Region {
+        Region {
+            origin: Point { x: self.origin.x - by, y: self.origin.y - by },
+            width: self.width + by * 2,
+            height: self.height + by * 2,
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let region = *self;
+        (region.top()..region.bottom())
+            .flat_map(move |y| (region.left()..region.right()).map(move |x| Point { x, y }))
+    }
+
+    // Manhattan distance from the point to the closest cell of the region
+    pub fn distance_to(&self, point: &Point) -> i64 {
+        let dx = (self.left() - point.x).max(point.x - (self.right() - 1)).max(0);
+        let dy = (self.top() - point.y).max(point.y - (self.bottom() - 1)).max(0);
+        dx + dy
+    }
+}
+
+/// An item of a SpatialIndex, along with the region it occupies.
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::spatial::{Region, SpatialIndex};
+///
+/// let mut index = SpatialIndex::new();
+/// let id = index.insert_region(Region::new(Point::new(0, 0), 3, 1), 467);
+/// let entry = index.get(id).unwrap();
+/// assert_eq!((*entry.value(), entry.region().area()), (467, 3));
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Entry<T> {
+    region: Region,
+    value: T,
+}
+
+impl<T> Entry<T> {
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Items occupying points or rectangular regions of a 2D grid, bucketed by
+/// cell so region queries only look at the cells they cover
+///
+/// ```
+/// use aoc_common::point::Point;
+/// use aoc_common::spatial::{Region, SpatialIndex};
+///
+/// let mut index = SpatialIndex::new();
+/// let number = index.insert_region(Region::new(Point::new(0, 0), 3, 1), "467");
+/// index.insert_at(Point::new(3, 1), "*");
+/// index.insert_at(Point::new(7, 7), "#");
+/// let touching: Vec<&str> = index.adjacent(number).iter().map(|entry| *entry.value()).collect();
+/// assert_eq!(touching, vec!["*"]);
+/// assert_eq!(index.at(&Point::new(1, 0)).len(), 1);
+/// assert_eq!(index.nearest(&Point::new(6, 6)).map(|entry| *entry.value()), Some("#"));
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpatialIndex<T> {
+    entries: Vec<Entry<T>>,
+    // derived from the entries, and keyed by points which most formats
+    // can't use as map keys
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cells: HashMap<Point, Vec<usize>>,
+}
+
+impl<T> Default for SpatialIndex<T> {
+    fn default() -> SpatialIndex<T> {
+        SpatialIndex {
+            entries: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> {
+    pub fn new() -> SpatialIndex<T> {
+        SpatialIndex::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn insert_at(&mut self, point: Point, value: T) -> usize {
+        self.insert_region(Region::point(point), value)
+    }
+
+    pub fn insert_region(&mut self, region: Region, value: T) -> usize {
+        let id = self.entries.len();
+        for point in region.points() {
+            self.cells.entry(point).or_default().push(id);
+        }
+        self.entries.push(Entry { region, value });
+        id
+    }
+
+    pub fn get(&self, id: usize) -> Option<&Entry<T>> {
+        self.entries.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry<T>> {
+        self.entries.iter()
+    }
+
+    pub fn at(&self, point: &Point) -> Vec<&Entry<T>> {
+        self.cells.get(point)
+            .map(|ids| ids.iter().map(|id| &self.entries[*id]).collect())
+            .unwrap_or_default()
+    }
+
+    // Every entry whose region intersects the given one, in insertion order
+    pub fn query(&self, region: Region) -> Vec<&Entry<T>> {
+        self.query_ids(region)
+            .into_iter()
+            .map(|id| &self.entries[id])
+            .collect()
+    }
+
+    // Entries touching the given entry, including diagonally
+    pub fn adjacent(&self, id: usize) -> Vec<&Entry<T>> {
+        let Some(entry) = self.entries.get(id) else { return vec![] };
+        self.query_ids(entry.region.expand(1))
+            .into_iter()
+            .filter(|other| *other != id)
+            .map(|other| &self.entries[other])
+            .collect()
+    }
+
+    pub fn nearest(&self, point: &Point) -> Option<&Entry<T>> {
+        self.nearest_by(point, |_| true)
+    }
+
+    // Closest entry (by Manhattan distance) accepted by the filter, ties
+    // going to the earliest inserted
+    pub fn nearest_by<F>(&self, point: &Point, filter: F) -> Option<&Entry<T>>
+    where
+        F: Fn(&T) -> bool
+    {
+        self.entries.iter()
+            .filter(|entry| filter(&entry.value))
+            .min_by_key(|entry| entry.region.distance_to(point))
+    }
+
+    fn query_ids(&self, region: Region) -> Vec<usize> {
+        let mut ids: Vec<usize> = if region.area() as usize > self.entries.len() {
+            // cheaper to check every entry than every cell of a huge region
+            self.entries.iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.region.intersects(&region))
+                .map(|(id, _)| id)
+                .collect()
+        } else {
+            region.points()
+                .filter_map(|point| self.cells.get(&point))
+                .flatten()
+                .copied()
+                .collect()
+        };
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_query() {
+        let mut index: SpatialIndex<&str> = SpatialIndex::new();
+        index.insert_region(Region::new(Point::new(0, 0), 3, 1), "467");
+        index.insert_at(Point::new(3, 1), "*");
+        index.insert_region(Region::new(Point::new(2, 2), 2, 1), "35");
+        index.insert_region(Region::new(Point::new(6, 2), 3, 1), "633");
+
+        let values: Vec<&str> = index.query(Region::new(Point::new(2, 0), 3, 3))
+            .iter()
+            .map(|e| *e.value())
+            .collect();
+        assert_eq!(values, vec!["467", "*", "35"]);
+
+        let adjacent: Vec<&str> = index.adjacent(1).iter().map(|e| *e.value()).collect();
+        assert_eq!(adjacent, vec!["467", "35"]);
+        assert!(index.adjacent(3).is_empty());
+
+        // a query larger than the number of entries scans instead
+        let everything = index.query(Region::new(Point::new(-10, -10), 100, 100));
+        assert_eq!(everything.len(), 4);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let mut index: SpatialIndex<char> = SpatialIndex::new();
+        index.insert_region(Region::new(Point::new(0, 0), 4, 1), 'a');
+        index.insert_at(Point::new(10, 10), 'b');
+
+        assert_eq!(index.nearest(&Point::new(3, 5)).map(|e| *e.value()), Some('a'));
+        assert_eq!(index.nearest(&Point::new(8, 9)).map(|e| *e.value()), Some('b'));
+        assert_eq!(index.nearest_by(&Point::new(0, 0), |v| *v == 'b').map(|e| *e.value()), Some('b'));
+        assert!(SpatialIndex::<char>::new().nearest(&Point::new(0, 0)).is_none());
+    }
+}
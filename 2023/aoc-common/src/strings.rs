@@ -0,0 +1,206 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+struct TrieNode<V> {
+    children: HashMap<char, usize>,
+    value: Option<V>,
+}
+
+impl<V> Default for TrieNode<V> {
+    fn default() -> TrieNode<V> {
+        TrieNode {
+            children: HashMap::new(),
+            value: None,
+        }
+    }
+}
+
+/// Words mapped to values, stored by shared prefix so every word starting at
+/// a position of some text can be found in a single pass over it
+///
+/// ```
+/// use aoc_common::strings::Trie;
+///
+/// let trie: Trie<u32> = [("one", 1), ("two", 2), ("twenty", 20)].into_iter().collect();
+/// assert_eq!(trie.get("two"), Some(&2));
+/// assert_eq!(trie.longest_match_at("xtwentyone", 1), Some((6, &20)));
+/// assert_eq!(trie.longest_match_at("xtwentyone", 7), Some((3, &1)));
+/// assert!(trie.contains_prefix("tw"));
+/// assert_eq!(trie.words_with_prefix("tw").len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Trie<V> {
+    // nodes[0] is the root, the empty prefix
+    nodes: Vec<TrieNode<V>>,
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Trie<V> {
+        Trie { nodes: vec![TrieNode::default()] }
+    }
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Trie<V> {
+        Trie::default()
+    }
+
+    // Returns the previous value if the word was already present
+    pub fn insert(&mut self, word: &str, value: V) -> Option<V> {
+        let mut node = 0;
+        for c in word.chars() {
+            node = match self.nodes[node].children.get(&c) {
+                Some(child) => *child,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(c, child);
+                    child
+                }
+            };
+        }
+        self.nodes[node].value.replace(value)
+    }
+
+    pub fn get(&self, word: &str) -> Option<&V> {
+        self.nodes[self.find(word)?].value.as_ref()
+    }
+
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        self.find(prefix).is_some()
+    }
+
+    // Every word starting with the prefix, in no particular order
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<(String, &V)> {
+        let Some(start) = self.find(prefix) else { return vec![] };
+        let mut words: Vec<(String, &V)> = vec![];
+        let mut stack: Vec<(usize, String)> = vec![(start, prefix.to_string())];
+        while let Some((node, word)) = stack.pop() {
+            if let Some(value) = &self.nodes[node].value {
+                words.push((word.clone(), value));
+            }
+            for (c, child) in &self.nodes[node].children {
+                let mut next = word.clone();
+                next.push(*c);
+                stack.push((*child, next));
+            }
+        }
+        words
+    }
+
+    // The longest word that `text` has starting at byte offset `position`,
+    // as its length in bytes and its value. Offsets that aren't on a char
+    // boundary never match.
+    pub fn longest_match_at(&self, text: &str, position: usize) -> Option<(usize, &V)> {
+        let rest = text.get(position..)?;
+        let mut node = 0;
+        let mut longest: Option<(usize, &V)> = None;
+        for (idx, c) in rest.char_indices() {
+            let Some(child) = self.nodes[node].children.get(&c) else { break };
+            node = *child;
+            if let Some(value) = &self.nodes[node].value {
+                longest = Some((idx + c.len_utf8(), value));
+            }
+        }
+        longest
+    }
+
+    fn find(&self, prefix: &str) -> Option<usize> {
+        let mut node = 0;
+        for c in prefix.chars() {
+            node = *self.nodes[node].children.get(&c)?;
+        }
+        Some(node)
+    }
+}
+
+impl<'a, V> FromIterator<(&'a str, V)> for Trie<V> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, V)>>(iter: I) -> Trie<V> {
+        let mut trie = Trie::new();
+        for (word, value) in iter {
+            trie.insert(word, value);
+        }
+        trie
+    }
+}
+
+/// Puzzle input the way every parser expects it: Unix newlines, no trailing
+/// whitespace on any line, no blank lines at the end and a final newline
+/// unless there's nothing there at all. Borrows when the input already is.
+///
+/// ```
+/// use aoc_common::strings::normalize_input;
+///
+/// assert_eq!(normalize_input("a b  \r\nc\r\n\r\n\n"), "a b\nc\n");
+/// assert_eq!(normalize_input("a\n\nb"), "a\n\nb\n");
+/// assert_eq!(normalize_input(" \n\n"), "");
+/// assert!(matches!(normalize_input("a\nb\n"), std::borrow::Cow::Borrowed(_)));
+/// ```
+pub fn normalize_input(input: &str) -> Cow<'_, str> {
+    let trimmed = input.trim_end();
+    let normal = if trimmed.is_empty() {
+        input.is_empty()
+    } else {
+        input.len() == trimmed.len() + 1
+            && input.ends_with('\n')
+            && !trimmed.contains('\r')
+            && trimmed.lines().all(|line| line.trim_end() == line)
+    };
+    if normal {
+        return Cow::Borrowed(input)
+    }
+    let mut out = String::with_capacity(trimmed.len() + 1);
+    for line in trimmed.lines() {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_prefixes() {
+        let mut trie: Trie<u32> = vec![("one", 1), ("two", 2), ("twelve", 12)].into_iter().collect();
+        assert_eq!(trie.get("two"), Some(&2));
+        assert_eq!(trie.get("tw"), None);
+        assert!(trie.contains_prefix("tw"));
+        assert!(!trie.contains_prefix("three"));
+        assert_eq!(trie.insert("two", 22), Some(2));
+
+        let mut words = trie.words_with_prefix("tw");
+        words.sort();
+        assert_eq!(words, vec![(String::from("twelve"), &12), (String::from("two"), &22)]);
+        assert_eq!(trie.words_with_prefix("").len(), 3);
+    }
+
+    #[test]
+    fn test_longest_match_at() {
+        let trie: Trie<&str> = vec![("a", "a"), ("ab", "ab"), ("abcd", "abcd"), ("é", "e")].into_iter().collect();
+        assert_eq!(trie.longest_match_at("xabcz", 1), Some((2, &"ab")));
+        assert_eq!(trie.longest_match_at("xabcd", 1), Some((4, &"abcd")));
+        assert_eq!(trie.longest_match_at("xabcd", 0), None);
+        assert_eq!(trie.longest_match_at("xabcd", 5), None);
+        assert_eq!(trie.longest_match_at("xabcd", 10), None);
+        // multi-byte characters, and an offset in the middle of one
+        assert_eq!(trie.longest_match_at("éa", 0), Some((2, &"e")));
+        assert_eq!(trie.longest_match_at("éa", 1), None);
+        assert_eq!(trie.longest_match_at("éa", 2), Some((1, &"a")));
+    }
+
+    #[test]
+    fn test_normalize_input() {
+        assert_eq!(normalize_input(""), "");
+        assert_eq!(normalize_input("\r\n"), "");
+        assert_eq!(normalize_input("a"), "a\n");
+        assert_eq!(normalize_input("a\r\n\r\nb\r\n"), "a\n\nb\n");
+        assert_eq!(normalize_input("a\t\nb \n"), "a\nb\n");
+        assert_eq!(normalize_input("  a\n"), "  a\n");
+        assert!(matches!(normalize_input(""), Cow::Borrowed(_)));
+        assert!(matches!(normalize_input("a\n\nb\n"), Cow::Borrowed(_)));
+        assert!(matches!(normalize_input("a\n\n"), Cow::Owned(_)));
+    }
+}
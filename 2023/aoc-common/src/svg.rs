@@ -0,0 +1,107 @@
+use std::fmt::Write;
+
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// How to draw one cell: a filled square with optional text centered on it
+///
+/// ```
+/// use aoc_common::svg::SvgCell;
+///
+/// let cell = SvgCell::with_text("gold", "*");
+/// assert_eq!((cell.fill.as_str(), cell.text.as_deref()), ("gold", Some("*")));
+/// assert_eq!(SvgCell::new("white").text, None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgCell {
+    pub fill: String,
+    pub text: Option<String>,
+}
+
+impl SvgCell {
+    pub fn new(fill: &str) -> SvgCell {
+        SvgCell { fill: fill.to_string(), text: None }
+    }
+
+    pub fn with_text(fill: &str, text: &str) -> SvgCell {
+        SvgCell { fill: fill.to_string(), text: Some(text.to_string()) }
+    }
+}
+
+/// Draws the grid as an SVG image, `cell_size` pixels per cell, asking `f`
+/// how each cell should look. Unlike text output it can be zoomed as far as
+/// needed to read a big grid.
+///
+/// ```
+/// use aoc_common::grid::Grid;
+/// use aoc_common::svg::{render_grid, SvgCell};
+///
+/// let grid: Grid<char> = ".*\n..".parse().unwrap();
+/// let svg = render_grid(&grid, 10, |_, c| match c {
+///     '*' => SvgCell::with_text("gold", "*"),
+///     _ => SvgCell::new("white"),
+/// });
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"20\""));
+/// assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"gold\"/>"));
+/// ```
+pub fn render_grid<T, F>(grid: &Grid<T>, cell_size: u32, f: F) -> String
+where
+    F: Fn(&Point, &T) -> SvgCell
+{
+    let (width, height) = (grid.width() as u32 * cell_size, grid.height() as u32 * cell_size);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height,
+    );
+    let _ = writeln!(
+        out,
+        "<g font-family=\"monospace\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">",
+        cell_size * 3 / 4,
+    );
+    for (point, value) in grid.iter() {
+        let cell = f(&point, value);
+        let (x, y) = (point.x as u32 * cell_size, point.y as u32 * cell_size);
+        let _ = writeln!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            x, y, cell_size, cell_size, escape(&cell.fill),
+        );
+        if let Some(text) = cell.text {
+            let center = cell_size / 2;
+            let _ = writeln!(out, "<text x=\"{}\" y=\"{}\">{}</text>", x + center, y + center, escape(&text));
+        }
+    }
+    out.push_str("</g>\n</svg>\n");
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_grid() {
+        let grid: Grid<char> = "a<\n".parse().unwrap();
+        let svg = render_grid(&grid, 10, |point, c| {
+            if point.x == 0 {
+                SvgCell::with_text("#fff", &c.to_string())
+            } else {
+                SvgCell::with_text("red", &c.to_string())
+            }
+        });
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\" viewBox=\"0 0 20 10\">\n"));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#fff\"/>\n<text x=\"5\" y=\"5\">a</text>\n"));
+        assert!(svg.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"red\"/>\n<text x=\"15\" y=\"5\">&lt;</text>\n"));
+        assert!(svg.ends_with("</g>\n</svg>\n"));
+        assert_eq!(render_grid(&grid, 10, |_, _| SvgCell::new("blue")).matches("<text").count(), 0);
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+
+use crate::answer::Answer;
+
+/// A day's solution paired with a slow but obviously correct one, to check
+/// the real solution against. The brute force only needs to cope with small
+/// inputs like the examples. Days register theirs, so it has to be shareable
+/// between threads.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::verify::Verifier;
+///
+/// // the sum of 1..=n for every n in the input
+/// struct Triangles;
+///
+/// impl Verifier for Triangles {
+///     fn parts(&self) -> &[u32] {
+///         &[1]
+///     }
+///
+///     fn solve(&self, _: u32, input: &str) -> Result<Answer, String> {
+///         let n: u64 = input.trim().parse().map_err(|_| "Not a number")?;
+///         Ok((n * (n + 1) / 2).into())
+///     }
+///
+///     fn brute_force(&self, _: u32, input: &str) -> Result<Answer, String> {
+///         let n: u64 = input.trim().parse().map_err(|_| "Not a number")?;
+///         Ok((1..=n).sum::<u64>().into())
+///     }
+/// }
+///
+/// let checks = Triangles.cross_check("100\n");
+/// assert!(checks.iter().all(|check| check.agrees()));
+/// assert_eq!(checks[0].answer, Ok(Answer::U64(5050)));
+/// ```
+pub trait Verifier: Sync {
+    // The parts both implementations solve
+    fn parts(&self) -> &[u32];
+
+    fn solve(&self, part: u32, input: &str) -> Result<Answer, String>;
+
+    fn brute_force(&self, part: u32, input: &str) -> Result<Answer, String>;
+
+    fn cross_check(&self, input: &str) -> Vec<CrossCheck> {
+        self.parts()
+            .iter()
+            .map(|part| CrossCheck {
+                part: *part,
+                answer: self.solve(*part, input),
+                expected: self.brute_force(*part, input),
+            })
+            .collect()
+    }
+}
+
+// Registrations are Debug, and hold the day's verifier if it has one
+impl fmt::Debug for dyn Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier").field("parts", &self.parts()).finish()
+    }
+}
+
+/// Both answers to one part of an input, the brute force's being the
+/// expected one.
+///
+/// ```
+/// use aoc_common::answer::Answer;
+/// use aoc_common::verify::CrossCheck;
+///
+/// let check = CrossCheck { part: 2, answer: Ok(Answer::U64(46)), expected: Ok(Answer::from("46")) };
+/// assert!(check.agrees());
+/// let check = CrossCheck { expected: Err(String::from("Too big to brute force")), ..check };
+/// assert!(!check.agrees());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossCheck {
+    pub part: u32,
+    pub answer: Result<Answer, String>,
+    pub expected: Result<Answer, String>,
+}
+
+impl CrossCheck {
+    // An error on either side is never agreement, even if both fail alike
+    pub fn agrees(&self) -> bool {
+        self.answer.is_ok() && self.answer == self.expected
+    }
+}
@@ -0,0 +1,38 @@
+/* The C declarations for aoc-ffi, checked against src/lib.rs by its tests. */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_NO_SOLUTION 1
+#define AOC_BUFFER_TOO_SMALL 2
+#define AOC_ERROR 3
+#define AOC_INVALID_ARGUMENT (-1)
+
+/*
+ * A caller-owned buffer. data points at capacity writable bytes; len is set
+ * to the number of bytes written, or to the number needed when the buffer is
+ * too small. The text is not NUL-terminated.
+ */
+typedef struct AocBuffer {
+    uint8_t *data;
+    size_t len;
+    size_t capacity;
+} AocBuffer;
+
+/*
+ * Solves one part of a puzzle for the given UTF-8 input. On AOC_OK the
+ * buffer holds the answer. AOC_NO_SOLUTION means there is no such day, and
+ * AOC_ERROR that the day could not solve the input or part; both leave the
+ * error message in the buffer.
+ */
+int32_t aoc_solve(uint32_t year,
+                  uint32_t day,
+                  uint32_t part,
+                  const uint8_t *input_ptr,
+                  size_t len,
+                  AocBuffer *out_buf);
+
+#endif /* AOC_H */
@@ -0,0 +1,180 @@
+use std::slice;
+
+// The C interface to aoc-solutions. The declarations in include/aoc.h are
+// kept in the repo for other languages to use without building anything, and
+// test_header fails when they fall out of step with this file.
+
+pub const AOC_OK: i32 = 0;
+// only for a day nothing's registered for; a day that can't solve the input
+// or the part gives AOC_ERROR
+pub const AOC_NO_SOLUTION: i32 = 1;
+pub const AOC_BUFFER_TOO_SMALL: i32 = 2;
+pub const AOC_ERROR: i32 = 3;
+pub const AOC_INVALID_ARGUMENT: i32 = -1;
+
+// Owned by the caller, so nothing allocated here ever crosses the boundary
+#[repr(C)]
+#[derive(Debug)]
+pub struct AocBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl AocBuffer {
+    // Copies as much as fits, and records the full length either way
+    unsafe fn write(&mut self, text: &str) -> bool {
+        self.len = text.len();
+        if text.len() > self.capacity {
+            return false
+        }
+        if !text.is_empty() {
+            std::ptr::copy_nonoverlapping(text.as_ptr(), self.data, text.len());
+        }
+        true
+    }
+}
+
+/// # Safety
+///
+/// `input_ptr` must point at `len` readable bytes, and `out_buf` at an
+/// AocBuffer whose `data` has `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer
+) -> i32 {
+    let Some(out_buf) = out_buf.as_mut() else {
+        return AOC_INVALID_ARGUMENT
+    };
+    if (input_ptr.is_null() && len > 0) || (out_buf.data.is_null() && out_buf.capacity > 0) {
+        return AOC_INVALID_ARGUMENT
+    }
+    let bytes = if len == 0 { &[][..] } else { slice::from_raw_parts(input_ptr, len) };
+    let Ok(input) = std::str::from_utf8(bytes) else {
+        return AOC_INVALID_ARGUMENT
+    };
+
+    let (status, text) = if !aoc_solutions::solutions().iter().any(|(y, d, _)| *y == year && *d == day) {
+        (AOC_NO_SOLUTION, format!("No solution for {} day {}", year, day))
+    } else {
+        // a panic must not unwind into the caller's frames
+        let result = std::panic::catch_unwind(|| aoc_solutions::solve(year, day, part, input))
+            .unwrap_or_else(|_| Err(String::from("Solution panicked")));
+        match result {
+            Ok(answer) => (AOC_OK, answer),
+            Err(err) => (AOC_ERROR, err),
+        }
+    };
+    if out_buf.write(&text) {
+        status
+    } else {
+        AOC_BUFFER_TOO_SMALL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(year: u32, day: u32, part: u32, input: &str, capacity: usize) -> (i32, String, usize) {
+        let mut data = vec![0u8; capacity];
+        let mut buffer = AocBuffer { data: data.as_mut_ptr(), len: 0, capacity };
+        let status = unsafe { aoc_solve(year, day, part, input.as_ptr(), input.len(), &mut buffer) };
+        let written = String::from_utf8_lossy(&data[..buffer.len.min(capacity)]).into_owned();
+        (status, written, buffer.len)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n";
+        assert_eq!(call(2023, 2, 2, input, 64), (AOC_OK, String::from("48"), 2));
+        let (status, message, _) = call(2023, 6, 1, input, 64);
+        assert_eq!(status, AOC_NO_SOLUTION);
+        assert_eq!(message, "No solution for 2023 day 6");
+        // the day's there, it just can't solve this
+        assert_eq!(call(2023, 5, 1, "not an almanac", 64).0, AOC_ERROR);
+        let (status, message, _) = call(2023, 2, 3, input, 64);
+        assert_eq!((status, message.as_str()), (AOC_ERROR, "Day 2 has no solution for part 3"));
+    }
+
+    #[test]
+    fn test_buffer_too_small() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n";
+        let (status, _, needed) = call(2023, 2, 2, input, 1);
+        assert_eq!((status, needed), (AOC_BUFFER_TOO_SMALL, 2));
+    }
+
+    // The C spelling of a Rust parameter or field, for the types used here
+    fn c_declaration(name: &str, ty: &str) -> String {
+        let c_ty = match ty {
+            "u32" => "uint32_t",
+            "i32" => "int32_t",
+            "usize" => "size_t",
+            "*const u8" => "const uint8_t *",
+            "*mut u8" => "uint8_t *",
+            "*mut AocBuffer" => "AocBuffer *",
+            ty => panic!("No C type for {}", ty),
+        };
+        if c_ty.ends_with('*') { format!("{}{}", c_ty, name) } else { format!("{} {}", c_ty, name) }
+    }
+
+    // `name: type` pairs, separated by commas, as in a signature or struct
+    fn declarations(list: &str) -> Vec<String> {
+        list.split(',')
+            .filter_map(|item| item.trim().trim_start_matches("pub ").split_once(": "))
+            .map(|(name, ty)| c_declaration(name, ty.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn test_header() {
+        let source = include_str!("lib.rs");
+        let header = include_str!("../include/aoc.h").split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let constants = [
+            ("AOC_OK", AOC_OK),
+            ("AOC_NO_SOLUTION", AOC_NO_SOLUTION),
+            ("AOC_BUFFER_TOO_SMALL", AOC_BUFFER_TOO_SMALL),
+            ("AOC_ERROR", AOC_ERROR),
+            ("AOC_INVALID_ARGUMENT", AOC_INVALID_ARGUMENT),
+        ];
+        for (name, value) in constants {
+            // negative ones in parentheses, so `x-AOC_INVALID_ARGUMENT` still means x+1
+            let value = if value < 0 { format!("({})", value) } else { value.to_string() };
+            assert!(header.contains(&format!("#define {} {} ", name, value)), "{} isn't {} in the header", name, value);
+        }
+        assert_eq!(header.matches("#define AOC_").count(), constants.len() + 1, "the header defines constants lib.rs doesn't");
+
+        let fields = source.split("pub struct AocBuffer {").nth(1).and_then(|rest| rest.split_once('}')).unwrap().0;
+        let expected = format!("typedef struct AocBuffer {{ {}; }} AocBuffer;", declarations(fields).join("; "));
+        assert!(header.contains(&expected), "the header doesn't declare {}", expected);
+
+        // every exported function, with its parameters and return type
+        let exported: Vec<&str> = source.split("extern \"C\" fn ").skip(1).collect();
+        assert!(!exported.is_empty());
+        for function in &exported {
+            let (name, rest) = function.split_once('(').unwrap();
+            let (params, rest) = rest.split_once(')').unwrap();
+            let returns = rest.trim_start().strip_prefix("-> ").unwrap().split_once(' ').unwrap().0;
+            let expected = format!("{}({});", c_declaration(name, returns), declarations(params).join(", "));
+            assert!(header.contains(&expected), "the header doesn't declare {}", expected);
+        }
+        assert_eq!(header.matches(" aoc_").count(), exported.len(), "the header declares functions lib.rs doesn't export");
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut buffer = AocBuffer { data: std::ptr::null_mut(), len: 0, capacity: 0 };
+        let invalid = [0xffu8, 0xfe];
+        unsafe {
+            assert_eq!(aoc_solve(2023, 2, 1, invalid.as_ptr(), invalid.len(), &mut buffer), AOC_INVALID_ARGUMENT);
+            assert_eq!(aoc_solve(2023, 2, 1, std::ptr::null(), 4, &mut buffer), AOC_INVALID_ARGUMENT);
+            assert_eq!(aoc_solve(2023, 2, 1, invalid.as_ptr(), 0, std::ptr::null_mut()), AOC_INVALID_ARGUMENT);
+        }
+    }
+}
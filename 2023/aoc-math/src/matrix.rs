@@ -0,0 +1,185 @@
+use std::ops::{Add, Mul};
+
+use crate::modint::ModInt;
+
+// Values a matrix can hold: anything with an addition and a multiplication
+// that have identities
+pub trait MatrixElement: Copy + Add<Output = Self> + Mul<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+}
+
+impl MatrixElement for u64 {
+    fn zero() -> u64 {
+        0
+    }
+
+    fn one() -> u64 {
+        1
+    }
+}
+
+impl<const M: u64> MatrixElement for ModInt<M> {
+    fn zero() -> ModInt<M> {
+        ModInt::new(0)
+    }
+
+    fn one() -> ModInt<M> {
+        ModInt::new(1)
+    }
+}
+
+// Square matrix stored row by row. Raising the transition matrix of a linear
+// recurrence to the n-th power gives its n-th step in O(log n) multiplications
+// instead of simulating every step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    size: usize,
+    cells: Vec<T>,
+}
+
+impl<T: MatrixElement> Matrix<T> {
+    pub fn new(size: usize, cells: Vec<T>) -> Option<Matrix<T>> {
+        if cells.len() != size * size {
+            return None
+        }
+        Some(Matrix { size, cells })
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Matrix<T>> {
+        let size = rows.len();
+        if rows.iter().any(|row| row.len() != size) {
+            return None
+        }
+        Matrix::new(size, rows.into_iter().flatten().collect())
+    }
+
+    pub fn zero(size: usize) -> Matrix<T> {
+        Matrix { size, cells: vec![T::zero(); size * size] }
+    }
+
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut identity = Matrix::zero(size);
+        for idx in 0..size {
+            identity.cells[idx * size + idx] = T::one();
+        }
+        identity
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<T> {
+        if row >= self.size || col >= self.size {
+            return None
+        }
+        Some(self.cells[row * self.size + col])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        assert!(row < self.size && col < self.size, "({}, {}) is outside the matrix", row, col);
+        self.cells[row * self.size + col] = value;
+    }
+
+    // Exponentiation by squaring
+    pub fn pow(&self, mut exponent: u64) -> Matrix<T> {
+        let mut base = self.clone();
+        let mut result = Matrix::identity(self.size);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            // skipping the last squaring keeps u64 matrices from overflowing
+            // on a power that's never used
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // The matrix times a column vector, i.e. one step of the recurrence
+    pub fn apply(&self, vector: &[T]) -> Vec<T> {
+        assert_eq!(vector.len(), self.size, "vector length doesn't match the matrix");
+        self.cells
+            .chunks(self.size.max(1))
+            .take(self.size)
+            .map(|row| row.iter()
+                .zip(vector)
+                .fold(T::zero(), |sum, (a, b)| sum + *a * *b))
+            .collect()
+    }
+}
+
+impl<T: MatrixElement> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(self.size, other.size, "matrices have different sizes");
+        let size = self.size;
+        let mut product = Matrix::zero(size);
+        for row in 0..size {
+            for k in 0..size {
+                let a = self.cells[row * size + k];
+                for col in 0..size {
+                    let idx = row * size + col;
+                    product.cells[idx] = product.cells[idx] + a * other.cells[k * size + col];
+                }
+            }
+        }
+        product
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci() -> Matrix<u64> {
+        Matrix::from_rows(vec![vec![1, 1], vec![1, 0]]).unwrap()
+    }
+
+    #[test]
+    fn test_construction() {
+        assert!(Matrix::<u64>::new(2, vec![1, 2, 3]).is_none());
+        assert!(Matrix::<u64>::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+        let matrix = Matrix::<u64>::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(matrix.get(1, 0), Some(3));
+        assert_eq!(matrix.get(2, 0), None);
+        assert_eq!(&matrix * &Matrix::identity(2), matrix);
+        assert_eq!(matrix.pow(0), Matrix::identity(2));
+        assert_eq!(matrix.apply(&[1, 1]), vec![3, 7]);
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        // [[1, 1], [1, 0]]^n = [[F(n+1), F(n)], [F(n), F(n-1)]]
+        assert_eq!(fibonacci().pow(10).get(0, 1), Some(55));
+        // F(93) is the largest that fits in a u64, so this is as far as it goes
+        assert_eq!(fibonacci().pow(92).get(0, 0), Some(12200160415121876738));
+
+        type Mod = ModInt<1_000_000_007>;
+        let fibonacci = Matrix::from_rows(vec![
+            vec![Mod::new(1), Mod::new(1)],
+            vec![Mod::new(1), Mod::new(0)],
+        ]).unwrap();
+        assert_eq!(fibonacci.pow(1_000_000_000_000).get(0, 1).map(|f| f.value()), Some(730695249));
+    }
+
+    #[test]
+    fn test_matches_simulation() {
+        // walks of each length on a small directed graph, stepped one at a
+        // time and compared with the matrix power
+        let mut adjacency: Matrix<u64> = Matrix::zero(4);
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 3), (3, 1)] {
+            adjacency.set(to, from, 1);
+        }
+        let mut walks = vec![1, 0, 0, 0];
+        for steps in 1..30 {
+            walks = adjacency.apply(&walks);
+            assert_eq!(adjacency.pow(steps).apply(&[1, 0, 0, 0]), walks);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+// An integer modulo M, for answers that are only asked for modulo some prime.
+// M has to fit in 32 bits for two residues to multiply without overflowing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64>(u64);
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> ModInt<M> {
+        ModInt(value % M)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, mut exponent: u64) -> ModInt<M> {
+        let mut base = *self;
+        let mut result = ModInt::new(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> ModInt<M> {
+        ModInt::new(value)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn add(self, other: ModInt<M>) -> ModInt<M> {
+        ModInt((self.0 + other.0) % M)
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn sub(self, other: ModInt<M>) -> ModInt<M> {
+        ModInt((self.0 + M - other.0) % M)
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = ModInt<M>;
+
+    fn mul(self, other: ModInt<M>) -> ModInt<M> {
+        ModInt(self.0 * other.0 % M)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Mod7 = ModInt<7>;
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(Mod7::new(12).value(), 5);
+        assert_eq!(Mod7::new(5) + Mod7::new(4), Mod7::new(2));
+        assert_eq!(Mod7::new(2) - Mod7::new(5), Mod7::new(4));
+        assert_eq!(Mod7::new(3) * Mod7::new(5), Mod7::new(1));
+        // Fermat: a^(p-1) = 1 for a prime p
+        assert_eq!(Mod7::new(3).pow(6), Mod7::new(1));
+        assert_eq!(ModInt::<1_000_000_007>::new(2).pow(40).value(), (1u64 << 40) % 1_000_000_007);
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// An exact fraction, always kept in lowest terms with a positive denominator
+// so equal values compare and hash equal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        Rational::checked_new(numerator, denominator).expect("denominator is zero")
+    }
+
+    pub fn checked_new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None
+        }
+        let divisor = gcd(numerator.abs(), denominator.abs()) * denominator.signum();
+        Some(Rational { numerator: numerator / divisor, denominator: denominator / divisor })
+    }
+
+    pub fn zero() -> Rational {
+        Rational { numerator: 0, denominator: 1 }
+    }
+
+    pub fn one() -> Rational {
+        Rational { numerator: 1, denominator: 1 }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    // The value as an integer, if it is one
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.is_integer() {
+            Some(self.numerator)
+        } else {
+            None
+        }
+    }
+
+    pub fn floor(&self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    pub fn ceil(&self) -> i128 {
+        -(-self.numerator).div_euclid(self.denominator)
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational { numerator: self.numerator.abs(), denominator: self.denominator }
+    }
+
+    pub fn recip(&self) -> Option<Rational> {
+        Rational::checked_new(self.denominator, self.numerator)
+    }
+
+    pub fn checked_div(self, other: Rational) -> Option<Rational> {
+        Some(self * other.recip()?)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Rational {
+        Rational { numerator: value as i128, denominator: 1 }
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Rational {
+        Rational { numerator: value, denominator: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        // dividing by the gcd of the denominators first keeps the products small
+        let divisor = gcd(self.denominator, other.denominator);
+        Rational::new(
+            self.numerator * (other.denominator / divisor) + other.numerator * (self.denominator / divisor),
+            self.denominator / divisor * other.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        // cross-cancelling first, for the same reason
+        let a = gcd(self.numerator.abs(), other.denominator).max(1);
+        let b = gcd(other.numerator.abs(), self.denominator).max(1);
+        Rational::new(
+            (self.numerator / a) * (other.numerator / b),
+            (self.denominator / b) * (other.denominator / a),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        self.checked_div(other).expect("division by zero")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // denominators are positive, so cross-multiplying keeps the order
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if a == 0 {
+        return b
+    }
+    gcd(b % a, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator)
+    }
+
+    #[test]
+    fn test_normalization() {
+        assert_eq!(r(2, 4), r(1, 2));
+        assert_eq!(r(3, -6), r(-1, 2));
+        assert_eq!(r(-3, -6).denominator(), 2);
+        assert_eq!(r(0, -5), Rational::zero());
+        assert_eq!(r(0, -5).denominator(), 1);
+        assert_eq!(Rational::checked_new(1, 0), None);
+        assert_eq!(r(6, 3).to_integer(), Some(2));
+        assert_eq!(r(7, 3).to_integer(), None);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(3, 4), r(-1, 4));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert_eq!(r(1, 3) * Rational::zero(), Rational::zero());
+        assert_eq!(Rational::zero().recip(), None);
+        assert_eq!(r(1, 2).checked_div(Rational::zero()), None);
+        // 0.1 + 0.2 is exactly 0.3 here
+        assert_eq!(r(1, 10) + r(2, 10), r(3, 10));
+    }
+
+    #[test]
+    fn test_ordering_and_rounding() {
+        let mut values = vec![r(1, 2), r(-7, 3), r(2, 5), Rational::from(1i64), r(-1, 3)];
+        values.sort();
+        assert_eq!(values, vec![r(-7, 3), r(-1, 3), r(2, 5), r(1, 2), Rational::one()]);
+        assert_eq!((r(-7, 3).floor(), r(-7, 3).ceil()), (-3, -2));
+        assert_eq!((r(7, 3).floor(), r(7, 3).ceil()), (2, 3));
+        assert_eq!(r(4, 2).ceil(), 2);
+        assert_eq!(r(-3, 4).to_string(), "-3/4");
+        assert_eq!(r(8, 4).to_string(), "2");
+        assert_eq!(r(1, 4).to_f64(), 0.25);
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        // where two hailstone paths cross: p1 + t * v1 = p2 + s * v2
+        let (p1, v1) = ((19, 13), (-2, 1));
+        let (p2, v2) = ((18, 19), (-1, -1));
+        let determinant = v1.0 * v2.1 - v1.1 * v2.0;
+        let t = r((p2.0 - p1.0) * v2.1 - (p2.1 - p1.1) * v2.0, determinant);
+        let x = Rational::from(p1.0) + t * Rational::from(v1.0);
+        let y = Rational::from(p1.1) + t * Rational::from(v1.1);
+        assert_eq!((x, y), (r(43, 3), r(46, 3)));
+
+        // large coordinates that lose precision as floats
+        let big = 200_000_000_000_000i128;
+        assert_eq!(r(big + 1, 3) - r(big, 3), r(1, 3));
+    }
+}
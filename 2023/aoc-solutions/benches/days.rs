@@ -0,0 +1,40 @@
+// A Criterion group per day, e.g. 2023-day5, benching parse and each part the
+// day solves on its example and, when the checkout has one, its personal
+// input, so each can be measured on its own. Run a day's group with
+// `aoc bench --day 5 --criterion` or `cargo bench --bench days -- 2023-day5`.
+
+use aoc_common::fixtures;
+use aoc_common::solution::Solution;
+use aoc_common::strings::normalize_input;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("{}-day{}", S::YEAR, S::DAY));
+    let mut inputs = vec![("example", fixtures::example(S::DAY))];
+    inputs.extend(fixtures::personal_input(S::DAY).map(|input| ("input", input)));
+    for (name, input) in inputs {
+        let input = normalize_input(&input);
+        group.bench_with_input(BenchmarkId::new("parse", name), &*input, |b, input| b.iter(|| S::parse(input)));
+        let parsed = S::parse(&input).unwrap_or_else(|err| panic!("Day {}'s {} doesn't parse: {}", S::DAY, name, err));
+        for part in S::PARTS {
+            let id = BenchmarkId::new(format!("part{}", part), name);
+            match part {
+                1 => group.bench_with_input(id, &parsed, |b, parsed| b.iter(|| S::part_one(parsed))),
+                _ => group.bench_with_input(id, &parsed, |b, parsed| b.iter(|| S::part_two(parsed))),
+            };
+        }
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<aoc_solutions::day_1::Day1>(c);
+    bench_day::<aoc_solutions::day_2::Day2>(c);
+    bench_day::<aoc_solutions::day_3::Day3>(c);
+    bench_day::<aoc_solutions::day_4::Day4>(c);
+    bench_day::<aoc_solutions::day_5::Day5>(c);
+    bench_day::<aoc_solutions::day_8::Day8>(c);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
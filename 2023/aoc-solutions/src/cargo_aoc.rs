@@ -0,0 +1,116 @@
+// The solutions split the way cargo-aoc expects them: a generator turning the
+// input into the day's parsed form, and a solver per part taking a reference
+// to it. Each is a thin wrapper around what the day's own `solve` calls, so
+// they can be registered with cargo-aoc's attributes, for example
+//
+//     #[aoc_generator(day5)]
+//     fn generator(input: &str) -> Almanac { cargo_aoc::day5_generator(input).unwrap() }
+//
+//     #[aoc(day5, part1)]
+//     fn part1(almanac: &Almanac) -> u64 { cargo_aoc::day5_part1(almanac).unwrap() }
+//
+// Parse failures are returned rather than panicked on, which cargo-aoc
+// accepts from both generators and solvers.
+
+use std::ops::Range;
+
+use aoc_common::error::AocError;
+use aoc_common::locale::Locale;
+use day_2::{Game, AVAILABLE};
+use day_3::ItemMatrix;
+use day_4::Card;
+use day_5::NumberMapper;
+use day_8::network::{Network, Step};
+
+pub type Almanac = (Vec<u64>, NumberMapper);
+pub type RangeAlmanac = (Vec<Range<u64>>, NumberMapper);
+
+// Day 1 has no parsing step, its solver takes the input as is
+pub fn day1_part2(input: &str) -> u32 {
+    day_1::get_calibration_value(input, &Locale::english())
+}
+
+pub fn day2_generator(input: &str) -> Vec<Game> {
+    day_2::parse(input)
+}
+
+pub fn day2_part1(games: &[Game]) -> u64 {
+    day_2::possible_games_sum(games, &AVAILABLE)
+}
+
+pub fn day2_part2(games: &[Game]) -> Result<u64, AocError> {
+    day_2::sum_of_powers(games)
+}
+
+pub fn day3_generator(input: &str) -> Result<ItemMatrix, AocError> {
+    day_3::parse(input)
+}
+
+pub fn day3_part1(matrix: &ItemMatrix) -> Result<u64, AocError> {
+    day_3::total(matrix.find_real_parts()?.into_iter().map(u64::from))
+}
+
+pub fn day3_part2(matrix: &ItemMatrix) -> Result<u64, AocError> {
+    day_3::total(matrix.find_gear_ratios()?)
+}
+
+pub fn day4_generator(input: &str) -> Vec<Card> {
+    day_4::parse_contents(input)
+}
+
+pub fn day4_part1(cards: &[Card]) -> u32 {
+    day_4::get_card_point_total(cards)
+}
+
+pub fn day4_part2(cards: &[Card]) -> u32 {
+    day_4::get_card_copies_total(cards)
+}
+
+pub fn day5_generator(input: &str) -> Result<Almanac, AocError> {
+    day_5::parse_contents(input)
+}
+
+// Part 2 reads the seeds as ranges, so it needs its own generator
+pub fn day5_generator_part2(input: &str) -> Result<RangeAlmanac, AocError> {
+    day_5::parse_content_ranges(input)
+}
+
+pub fn day5_part1((seeds, mapper): &Almanac) -> Result<u64, String> {
+    day_5::find_smallest_location(seeds.clone(), mapper)
+}
+
+pub fn day5_part2((seed_ranges, mapper): &RangeAlmanac) -> Result<u64, String> {
+    day_5::find_smallest_location_ranges(seed_ranges.clone(), mapper)
+}
+
+pub fn day8_generator(input: &str) -> Result<(Network, Vec<Step>), AocError> {
+    day_8::parse_network_and_steps(input)
+}
+
+pub fn day8_part2((network, steps): &(Network, Vec<Step>)) -> Result<u64, String> {
+    day_8::ghost_steps(network, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_solve() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..\n";
+        let matrix = day3_generator(input).unwrap();
+        assert_eq!(day3_part1(&matrix).unwrap().to_string(), crate::solve(2023, 3, 1, input).unwrap());
+        assert_eq!(day3_part2(&matrix).unwrap().to_string(), crate::solve(2023, 3, 2, input).unwrap());
+
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n";
+        let cards = day4_generator(input);
+        assert_eq!(day4_part1(&cards), 10);
+        assert_eq!(day4_part2(&cards), 3);
+    }
+
+    #[test]
+    fn test_generator_errors() {
+        assert!(day5_generator("").is_err());
+        assert!(day8_generator("").is_err());
+    }
+}
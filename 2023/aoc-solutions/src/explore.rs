@@ -0,0 +1,136 @@
+//! Entry points for poking at a day's parsed input interactively, for
+//! example from a Rust Jupyter kernel (evcxr):
+//!
+//! ```text
+//! :dep aoc-solutions = { path = "2023/aoc-solutions" }
+//! let (seeds, mapper) = aoc_solutions::explore::parse_day5(&std::fs::read_to_string("2023/day-5/input.txt")?).unwrap();
+//! mapper.location_for_seed(seeds[0])
+//! ```
+//!
+//! Each one is a thin wrapper over the day's own parser, so what you see here
+//! is exactly what the solutions work with. The day crates themselves are
+//! re-exported from the crate root (`aoc_solutions::day_5`) for everything
+//! else.
+
+use std::ops::Range;
+
+use aoc_common::error::AocError;
+use day_2::Game;
+use day_3::ItemMatrix;
+use day_4::Card;
+use day_5::NumberMapper;
+use day_8::network::{Network, Step};
+
+/// The games and the cubes revealed in each set.
+///
+/// ```
+/// let games = aoc_solutions::explore::parse_day2(
+///     "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+///      Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n"
+/// );
+/// assert_eq!(games.len(), 2);
+/// assert_eq!(games[0].id(), 1);
+/// assert_eq!(games[0].sets()[1].blue, 6);
+/// assert_eq!(aoc_solutions::day_2::sum_of_powers(&games).unwrap(), 48 + 12);
+/// ```
+pub fn parse_day2(input: &str) -> Vec<Game> {
+    day_2::parse(input)
+}
+
+/// The engine schematic, indexed by position.
+///
+/// ```
+/// let mut matrix = aoc_solutions::explore::parse_day3("467..114..\n...*......\n..35..633.\n").unwrap();
+/// let mut parts = matrix.find_real_parts().unwrap();
+/// parts.sort();
+/// assert_eq!(parts, vec![35, 467]);
+/// assert_eq!(matrix.find_gear_ratios().unwrap(), vec![467 * 35]);
+/// ```
+pub fn parse_day3(input: &str) -> Result<ItemMatrix, AocError> {
+    day_3::parse(input)
+}
+
+/// The scratchcards.
+///
+/// ```
+/// let cards = aoc_solutions::explore::parse_day4(
+///     "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+///      Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n"
+/// );
+/// assert_eq!(cards[0].number(), 1);
+/// assert_eq!(cards[0].matches(), 4);
+/// assert_eq!(cards[0].points(), 8);
+/// assert_eq!(cards[1].points(), 2);
+/// ```
+pub fn parse_day4(input: &str) -> Vec<Card> {
+    day_4::parse_contents(input)
+}
+
+/// The seeds, and the maps taking them all the way to locations.
+///
+/// ```
+/// let input = "seeds: 79 14 55 13
+///
+/// seed-to-soil map:
+/// 50 98 2
+/// 52 50 48
+///
+/// soil-to-fertilizer map:
+/// 0 15 37
+/// 37 52 2
+/// 39 0 15
+///
+/// fertilizer-to-water map:
+/// 49 53 8
+/// 0 11 42
+/// 42 0 7
+/// 57 7 4
+///
+/// water-to-light map:
+/// 88 18 7
+/// 18 25 70
+///
+/// light-to-temperature map:
+/// 45 77 23
+/// 81 45 19
+/// 68 64 13
+///
+/// temperature-to-humidity map:
+/// 0 69 1
+/// 1 0 69
+///
+/// humidity-to-location map:
+/// 60 56 37
+/// 56 93 4
+/// ";
+/// let (seeds, mapper) = aoc_solutions::explore::parse_day5(input).unwrap();
+/// assert_eq!(seeds, vec![79, 14, 55, 13]);
+/// assert_eq!(mapper.location_for_seed(79), Some(82));
+/// assert_eq!(aoc_solutions::day_5::find_smallest_location(seeds, &mapper), Ok(35));
+///
+/// let (ranges, mapper) = aoc_solutions::explore::parse_day5_ranges(input).unwrap();
+/// assert_eq!(ranges, vec![79..93, 55..68]);
+/// assert_eq!(aoc_solutions::day_5::find_smallest_location_ranges(ranges, &mapper), Ok(46));
+/// ```
+pub fn parse_day5(input: &str) -> Result<(Vec<u64>, NumberMapper), AocError> {
+    day_5::parse_contents(input)
+}
+
+/// Like [`parse_day5`], reading the seeds as (start, length) pairs.
+pub fn parse_day5_ranges(input: &str) -> Result<(Vec<Range<u64>>, NumberMapper), AocError> {
+    day_5::parse_content_ranges(input)
+}
+
+/// The desert network and the list of left/right steps.
+///
+/// ```
+/// let (network, steps) = aoc_solutions::explore::parse_day8(
+///     "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"
+/// ).unwrap();
+/// assert_eq!(steps.len(), 3);
+/// assert_eq!(network.nodes["AAA"], ("BBB".to_string(), "BBB".to_string()));
+/// assert_eq!(network.navigate(|n| n == "AAA", |n| n == "ZZZ", &steps), Ok(6));
+/// ```
+pub fn parse_day8(input: &str) -> Result<(Network, Vec<Step>), AocError> {
+    day_8::parse_network_and_steps(input)
+}
@@ -0,0 +1,314 @@
+// Every day's solutions behind a single entry point, taking the puzzle input
+// as a string so nothing here touches the filesystem or the environment.
+// That keeps it usable from the wasm build, where neither exists, nor a
+// clock: spans are only timed by a subscriber, and there's none in wasm.
+// `cargo check-wasm` checks it still builds for the browser, and to build it:
+//
+//     cargo build -p aoc-solutions --target wasm32-unknown-unknown --release
+//     wasm-bindgen --target web target/wasm32-unknown-unknown/release/aoc_solutions.wasm --out-dir pkg
+
+// both are written against every day of the year
+#[cfg(feature = "2023")]
+pub mod cargo_aoc;
+#[cfg(feature = "2023")]
+pub mod explore;
+pub mod testgen;
+
+use std::sync::OnceLock;
+
+use aoc_common::answer::Answer;
+use aoc_common::locale::Locale;
+use aoc_common::registry;
+use aoc_common::solution::Phases;
+use aoc_common::strings::normalize_input;
+use aoc_common::verify::Verifier;
+
+// Only the days whose features are on are built, see Cargo.toml
+#[cfg(feature = "2023-day1")]
+pub use day_1;
+#[cfg(feature = "2023-day2")]
+pub use day_2;
+#[cfg(feature = "2023-day3")]
+pub use day_3;
+#[cfg(feature = "2023-day4")]
+pub use day_4;
+#[cfg(feature = "2023-day5")]
+pub use day_5;
+#[cfg(feature = "2023-day8")]
+pub use day_8;
+
+// (year, day, parts) for every day with at least one part solved, as the
+// days register themselves with aoc_common::register_solution!
+pub fn solutions() -> &'static [(u32, u32, &'static [u32])] {
+    static SOLUTIONS: OnceLock<Vec<(u32, u32, &'static [u32])>> = OnceLock::new();
+    SOLUTIONS.get_or_init(|| {
+        registry::registered().iter()
+            .filter(|registration| !registration.parts.is_empty())
+            .map(|registration| (registration.year, registration.day, registration.parts))
+            .collect()
+    })
+}
+
+// Every entry point takes the input through normalize_input first, so days
+// never see CRLF newlines or trailing blank lines, however they were loaded
+pub fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, String> {
+    answer(year, day, part, input).map(|answer| answer.to_string())
+}
+
+// As `solve`, keeping the answer as the day gave it
+pub fn answer(year: u32, day: u32, part: u32, input: &str) -> Result<Answer, String> {
+    let _span = tracing::info_span!("day", year, day, part).entered();
+    let input = &*normalize_input(input);
+    match registry::find(year, day) {
+        Some(registration) => Ok((registration.solve)(part, input)?),
+        None => Err(format!("No solution for {} day {}", year, day)),
+    }
+}
+
+// Every part of the day solved on one parse of the input, with how long the
+// parse and each part took
+pub fn phases(year: u32, day: u32, input: &str) -> Result<Phases, String> {
+    let _span = tracing::info_span!("day", year, day).entered();
+    let input = &*normalize_input(input);
+    match registry::find(year, day) {
+        Some(registration) => Ok((registration.phases)(input)?),
+        None => Err(format!("No solution for {} day {}", year, day)),
+    }
+}
+
+// The day's bundled example, if it has one
+pub fn example(year: u32, day: u32) -> Option<&'static str> {
+    registry::find(year, day).map(|registration| registration.example).filter(|example| !example.is_empty())
+}
+
+// The answer the puzzle gives for the part on the day's example
+pub fn example_answer(year: u32, day: u32, part: u32) -> Option<&'static str> {
+    registry::find(year, day)?.example_answers.iter()
+        .find(|(example_part, _)| *example_part == part)
+        .map(|(_, answer)| *answer)
+}
+
+// As `solve`, with words in the input read in the locale's language. Only
+// days that spell anything out register a way to; every other day is solved
+// as it always is.
+pub fn solve_in(locale: &Locale, year: u32, day: u32, part: u32, input: &str) -> Result<String, String> {
+    match registry::find(year, day).and_then(|registration| registration.solve_in) {
+        Some(solve_in) if !locale.is_english() => {
+            let _span = tracing::info_span!("day", year, day, part, locale = locale.code).entered();
+            solve_in(locale, part, &normalize_input(input))
+        }
+        _ => solve(year, day, part, input),
+    }
+}
+
+// Interchangeable implementations a day can be solved with, all giving the
+// same answers. Empty for days with only one.
+pub fn variants(year: u32, day: u32) -> &'static [&'static str] {
+    registry::find(year, day).map_or(&[], |registration| registration.variants)
+}
+
+pub fn solve_variant(year: u32, day: u32, part: u32, variant: &str, input: &str) -> Result<String, String> {
+    let _span = tracing::info_span!("day", year, day, part, variant).entered();
+    let registration = registry::find(year, day);
+    let Some(solve_variant) = registration.and_then(|registration| registration.solve_variant) else {
+        return Err(format!("{} day {} has no variants", year, day))
+    };
+    if !variants(year, day).contains(&variant) {
+        return Err(format!("Unknown variant '{}' for {} day {}", variant, year, day))
+    }
+    solve_variant(part, variant, &normalize_input(input))
+}
+
+// The brute force a day's solution can be checked against, for the days that
+// have one
+pub fn verifier(year: u32, day: u32) -> Option<&'static dyn Verifier> {
+    registry::find(year, day)?.verifier
+}
+
+// The day's parsed input as a Graphviz graph, for days whose input is one
+pub fn to_dot(year: u32, day: u32, input: &str) -> Result<String, String> {
+    match registry::find(year, day).and_then(|registration| registration.to_dot) {
+        Some(to_dot) => to_dot(&normalize_input(input)),
+        None => Err(format!("{} day {} has no graph to export", year, day)),
+    }
+}
+
+// The variant each part's solution uses, named as in each day's own output
+pub fn algorithm(year: u32, day: u32, part: u32) -> Option<&'static str> {
+    registry::find(year, day)?.algorithms.iter()
+        .find(|(algorithm_part, _)| *algorithm_part == part)
+        .map(|(_, algorithm)| *algorithm)
+}
+
+// How a day is solved, for `aoc info` and picking days by tag, as the day
+// describes itself in its Solution. Complexities use the names the approach
+// gives its sizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub approach: &'static str,
+    // (part, complexity)
+    pub complexity: &'static [(u32, &'static str)],
+    pub tags: &'static [&'static str],
+}
+
+pub fn metadata(year: u32, day: u32) -> Option<Metadata> {
+    registry::find(year, day).map(|registration| Metadata {
+        approach: registration.approach,
+        complexity: registration.complexity,
+        tags: registration.tags,
+    })
+}
+
+// Registered days carrying the tag, in registry order
+pub fn days_tagged(tag: &str) -> Vec<(u32, u32)> {
+    solutions().iter()
+        .filter(|(year, day, _)| metadata(*year, *day).is_some_and(|metadata| metadata.tags.contains(&tag)))
+        .map(|(year, day, _)| (*year, *day))
+        .collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use wasm_bindgen::prelude::*;
+
+    // Errors are thrown as JS exceptions holding the message
+    #[wasm_bindgen(js_name = solve)]
+    pub fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, JsValue> {
+        super::solve(year, day, part, input).map_err(|err| JsValue::from_str(&err))
+    }
+}
+
+#[cfg(all(test, feature = "2023"))]
+mod tests {
+    use super::*;
+
+    const DAY_5: &str = include_str!("../../day-5/example.txt");
+
+    #[test]
+    fn test_solve_examples() {
+        let day_2 = include_str!("../../day-2/example.txt");
+        assert_eq!(solve(2023, 2, 1, day_2), Ok(String::from("8")));
+        assert_eq!(solve(2023, 2, 2, day_2), Ok(String::from("2286")));
+
+        let day_8 = include_str!("../../day-8/example.txt");
+        assert_eq!(solve(2023, 8, 2, day_8), Ok(String::from("6")));
+        assert_eq!(answer(2023, 8, 2, day_8), Ok(Answer::U64(6)));
+    }
+
+    #[test]
+    fn test_examples() {
+        for (year, day, parts) in solutions() {
+            let example = example(*year, *day).unwrap();
+            assert_eq!(example, aoc_common::fixtures::example(*day));
+            for part in parts.iter() {
+                let expected = example_answer(*year, *day, *part).unwrap();
+                assert_eq!(solve(*year, *day, *part, example).as_deref(), Ok(expected), "{} day {} part {}", year, day, part);
+            }
+        }
+        assert_eq!(example_answer(2023, 1, 1), None);
+        assert_eq!(example(1999, 1), None);
+    }
+
+    #[test]
+    fn test_phases() {
+        let day_5 = phases(2023, 5, DAY_5).unwrap();
+        assert_eq!(day_5.input_bytes, DAY_5.len());
+        let answers: Vec<(u32, Result<Answer, String>)> = day_5.parts.into_iter().map(|part| (part.part, part.answer)).collect();
+        assert_eq!(answers, vec![(1, Ok(Answer::U64(35))), (2, Ok(Answer::U64(46)))]);
+        assert!(phases(2023, 5, "seeds: x").is_err());
+        assert_eq!(phases(1999, 1, ""), Err(String::from("No solution for 1999 day 1")));
+    }
+
+    #[test]
+    fn test_solve_in() {
+        let german = Locale::load(&aoc_common::fixtures::workspace_root().join("locales/de.toml")).unwrap();
+        assert_eq!(solve_in(&german, 2023, 1, 2, "einsxfünf\r\nacht\n"), Ok(String::from("103")));
+        // the other days have no words to read
+        let day_2 = include_str!("../../day-2/example.txt");
+        assert_eq!(solve_in(&german, 2023, 2, 1, day_2), Ok(String::from("8")));
+        let day_1 = include_str!("../../day-1/example.txt");
+        assert_eq!(solve_in(&Locale::english(), 2023, 1, 2, day_1), Ok(String::from("281")));
+    }
+
+    #[test]
+    fn test_metadata() {
+        for (year, day, parts) in solutions().iter() {
+            let metadata = metadata(*year, *day).unwrap_or_else(|| panic!("No metadata for {} day {}", year, day));
+            let described: Vec<u32> = metadata.complexity.iter().map(|(part, _)| *part).collect();
+            assert_eq!(described, parts.to_vec(), "{} day {}", year, day);
+            assert!(!metadata.approach.is_empty() && !metadata.tags.is_empty(), "{} day {}", year, day);
+        }
+        assert_eq!(metadata(2023, 6), None);
+        assert_eq!(days_tagged("graph"), vec![(2023, 8)]);
+        assert_eq!(days_tagged("nothing"), vec![]);
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert!(solve(2023, 6, 1, "").is_err());
+        assert!(solve(2022, 1, 1, "").is_err());
+        assert!(solve(2023, 2, 3, "").is_err());
+        assert!(solve(2023, 3, 1, "").is_err());
+    }
+
+    // Every day linked in has registered itself, with the parts its Solution
+    // says it solves
+    #[test]
+    fn test_registered_parts() {
+        use aoc_common::solution::Solution;
+
+        fn parts<S: Solution>() -> (u32, u32, &'static [u32]) {
+            (S::YEAR, S::DAY, S::PARTS)
+        }
+        let days = [
+            parts::<day_1::Day1>(),
+            parts::<day_2::Day2>(),
+            parts::<day_3::Day3>(),
+            parts::<day_4::Day4>(),
+            parts::<day_5::Day5>(),
+            parts::<day_8::Day8>(),
+        ];
+        assert_eq!(days.as_slice(), solutions());
+    }
+
+    #[test]
+    fn test_every_part_has_an_algorithm() {
+        for (year, day, parts) in solutions() {
+            for part in parts.iter() {
+                assert!(algorithm(*year, *day, *part).is_some(), "{} day {} part {}", year, day, part);
+            }
+        }
+        assert_eq!(algorithm(2023, 1, 1), None);
+    }
+
+    #[test]
+    fn test_variants() {
+        assert_eq!(variants(2023, 5), &["tree", "sorted"]);
+        assert!(variants(2023, 4).is_empty());
+        for variant in variants(2023, 5) {
+            assert_eq!(solve_variant(2023, 5, 1, variant, DAY_5).as_deref(), Ok("35"));
+            assert_eq!(solve_variant(2023, 5, 2, variant, DAY_5).as_deref(), Ok("46"));
+        }
+        assert_eq!(solve_variant(2023, 5, 1, "list", DAY_5), Err(String::from("Unknown variant 'list' for 2023 day 5")));
+        assert_eq!(solve_variant(2023, 4, 1, "tree", ""), Err(String::from("2023 day 4 has no variants")));
+    }
+
+    #[test]
+    fn test_verifiers() {
+        let day_8 = include_str!("../../day-8/example.txt");
+        for (day, example) in [(5, DAY_5), (8, day_8)] {
+            let checks = verifier(2023, day).unwrap().cross_check(example);
+            assert!(!checks.is_empty());
+            assert!(checks.iter().all(|check| check.agrees()), "{:?}", checks);
+        }
+        assert!(verifier(2023, 4).is_none());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(2023, 8, "LR\n\nAAA = (BBB, ZZZ)\n").unwrap();
+        assert!(dot.starts_with("digraph \"network\" {\n"));
+        assert!(dot.contains("\"AAA\" -> \"ZZZ\" [label=\"R\"];"));
+        assert!(to_dot(2023, 5, "").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+// Large random inputs for stress testing, valid in the same ways the real
+// ones are, so every solution should get through them. `size` scales each
+// day's input roughly linearly and the same seed always gives the same input.
+
+use std::fmt::Write;
+
+use aoc_common::rng::Rng;
+
+pub fn generate(year: u32, day: u32, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    match (year, day) {
+        (2023, 1) => Some(day_1(&mut rng, size)),
+        (2023, 2) => Some(day_2(&mut rng, size)),
+        (2023, 3) => Some(day_3(&mut rng, size)),
+        (2023, 4) => Some(day_4(&mut rng, size)),
+        (2023, 5) => Some(day_5(&mut rng, size, 1 << 32)),
+        (2023, 8) => Some(day_8(&mut rng, size, DAY_8_CYCLES)),
+        _ => None,
+    }
+}
+
+// Inputs small enough for a day's brute force verifier to get through, for
+// the days that have one
+pub fn generate_small(year: u32, day: u32, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    match (year, day) {
+        (2023, 5) => Some(day_5(&mut rng, 6, 1000)),
+        (2023, 8) => {
+            let size = 1 + rng.below(7) as usize;
+            Some(day_8(&mut rng, size, &[2, 3, 5]))
+        }
+        _ => None,
+    }
+}
+
+const DIGIT_WORDS: &[&str] = &["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// `size` lines of letters, digits and spelled out digits, each with at least
+// one digit
+fn day_1(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        out.push(char::from(b'1' + rng.below(9) as u8));
+        for _ in 0..(5 + rng.below(30)) {
+            match rng.below(10) {
+                0 => out.push(char::from(b'1' + rng.below(9) as u8)),
+                1 => out.push_str(rng.pick::<&str>(DIGIT_WORDS)),
+                _ => out.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// `size` games of up to six reveals
+fn day_2(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for id in 1..=size {
+        let mut sets: Vec<String> = vec![];
+        for _ in 0..(1 + rng.below(6)) {
+            let mut colors: Vec<String> = vec![];
+            for color in ["red", "green", "blue"] {
+                if rng.below(3) != 0 || (colors.is_empty() && color == "blue") {
+                    colors.push(format!("{} {}", 1 + rng.below(20), color));
+                }
+            }
+            sets.push(colors.join(", "));
+        }
+        let _ = writeln!(out, "Game {}: {}", id, sets.join("; "));
+    }
+    out
+}
+
+// A `size` by `size` schematic, sparse like the real one
+fn day_3(rng: &mut Rng, size: usize) -> String {
+    const SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < size {
+            match rng.below(20) {
+                0..=1 => row.push(*rng.pick(SYMBOLS)),
+                2..=4 => {
+                    let number = (1 + rng.below(999)).to_string();
+                    if row.len() + number.len() < size {
+                        row.push_str(&number);
+                    }
+                    row.push('.');
+                }
+                _ => row.push('.'),
+            }
+        }
+        row.truncate(size);
+        out.push_str(&row);
+        out.push('\n');
+    }
+    out
+}
+
+// `size` cards. Fewer than one match per card on average keeps the copies in
+// part 2 from growing exponentially, as the real input does.
+fn day_4(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for number in 1..=size {
+        let mut pool: Vec<u64> = (1..100).collect();
+        rng.shuffle(&mut pool);
+        let winning = &pool[..10];
+        let matches = match rng.below(10) {
+            0..=5 => 0,
+            _ => 1 + rng.below(3) as usize,
+        };
+        let mut numbers: Vec<u64> = winning[..matches].to_vec();
+        numbers.extend_from_slice(&pool[10..(10 + 25 - matches)]);
+        let format = |numbers: &[u64]| numbers.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+        let _ = writeln!(out, "Card {:>4}: {} | {}", number, format(winning), format(&numbers));
+    }
+    out
+}
+
+// Ten seed ranges and seven maps of `size` ranges each, every map shuffling
+// pieces of 0..limit around like the real ones do with 0..2^32
+fn day_5(rng: &mut Rng, size: usize, limit: u64) -> String {
+    const KINDS: &[&str] = &["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+    let mut out = String::from("seeds:");
+    for _ in 0..10 {
+        let _ = write!(out, " {} {}", rng.below(limit), 1 + rng.below(limit / 16));
+    }
+    out.push('\n');
+
+    for kinds in KINDS.windows(2) {
+        let mut cuts: Vec<u64> = (0..size.max(1) - 1).map(|_| 1 + rng.below(limit - 1)).collect();
+        cuts.push(0);
+        cuts.push(limit);
+        cuts.sort();
+        cuts.dedup();
+        let pieces: Vec<(u64, u64)> = cuts.windows(2).map(|cut| (cut[0], cut[1] - cut[0])).collect();
+        let mut order: Vec<usize> = (0..pieces.len()).collect();
+        rng.shuffle(&mut order);
+        let _ = writeln!(out, "\n{}-to-{} map:", kinds[0], kinds[1]);
+        let mut target = 0;
+        for idx in order {
+            let (source, length) = pieces[idx];
+            let _ = writeln!(out, "{} {} {}", target, source, length);
+            target += length;
+        }
+    }
+    out
+}
+
+const DAY_8_CYCLES: &[u64] = &[43, 47, 53, 59, 61, 67];
+
+// A ghost per entry of `cycles` whose path loops back to its start's successor
+// right after reaching its Z node, with `size` instructions, like the real
+// input. Each loop is `size` times its entry of `cycles` steps long, so for a
+// prime `size` outside of those the answer is `size` times all of them. Node
+// names are three characters, so there are only enough of them for a `size`
+// up to 119 with DAY_8_CYCLES.
+fn day_8(rng: &mut Rng, size: usize, cycles: &[u64]) -> String {
+    let size = size.clamp(1, 119);
+    let steps: Vec<bool> = (0..size).map(|_| rng.below(2) == 0).collect();
+    let mut names = (0..).map(|idx: usize| {
+        const CHARS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY0123456789";
+        let char_at = |n: usize| char::from(CHARS[n % CHARS.len()]);
+        format!("{}{}{}", char_at(idx / (CHARS.len() * CHARS.len())), char_at(idx / CHARS.len()), char_at(idx))
+    });
+
+    let mut lines: Vec<String> = vec![];
+    for (ghost, cycle) in cycles.iter().enumerate() {
+        let length = size * *cycle as usize;
+        let start = format!("{}{}A", char::from(b'B' + ghost as u8), char::from(b'B' + ghost as u8));
+        let goal = format!("{}{}Z", char::from(b'B' + ghost as u8), char::from(b'B' + ghost as u8));
+        let path: Vec<String> = (1..length).map(|_| names.next().unwrap()).collect();
+
+        // the node after `from` on the path, taken with the instruction at `time`
+        let mut link = |from: &str, to: &str, time: usize| {
+            let decoy = path[rng.below(path.len() as u64) as usize].clone();
+            let (left, right) = match steps[time % size] {
+                true => (to.to_string(), decoy),
+                false => (decoy, to.to_string()),
+            };
+            lines.push(format!("{} = ({}, {})", from, left, right));
+        };
+        link(&start, &path[0], 0);
+        for time in 1..length - 1 {
+            link(&path[time - 1], &path[time], time);
+        }
+        link(&path[length - 2], &goal, length - 1);
+        // the loop starts over, so the goal leads where the start did
+        link(&goal, &path[0], length);
+    }
+    rng.shuffle(&mut lines);
+
+    let instructions: String = steps.iter().map(|left| if *left { 'L' } else { 'R' }).collect();
+    format!("{}\n\n{}\n", instructions, lines.join("\n"))
+}
+
+#[cfg(all(test, feature = "2023"))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // AOC_SEED reruns these with the seed a failure printed
+    fn test_seed(default: u64) -> u64 {
+        aoc_common::rng::seed(&[], default).unwrap()
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let seed = test_seed(1727);
+        for (year, day, parts) in crate::solutions() {
+            let input = generate(*year, *day, 50, seed).unwrap();
+            assert_eq!(generate(*year, *day, 50, seed).as_ref(), Some(&input));
+            for part in parts.iter() {
+                let answer = crate::solve(*year, *day, *part, &input);
+                assert!(answer.is_ok(), "{} day {} part {} seed {}: {:?}", year, day, part, seed, answer);
+            }
+        }
+        assert!(generate(2023, 6, 50, 1).is_none());
+    }
+
+    #[test]
+    fn test_small_inputs_cross_check() {
+        let first = test_seed(1);
+        for (year, day, _) in crate::solutions() {
+            let Some(verifier) = crate::verifier(*year, *day) else { continue };
+            for seed in first..first + 20 {
+                let input = generate_small(*year, *day, seed).unwrap();
+                for check in verifier.cross_check(&input) {
+                    assert!(check.agrees(), "{} day {} seed {}: {:?}\n{}", year, day, seed, check, input);
+                }
+            }
+        }
+        assert!(generate_small(2023, 4, 1).is_none());
+    }
+
+    #[test]
+    fn test_day_8_answer() {
+        let input = generate(2023, 8, 7, 9).unwrap();
+        let expected: u64 = 7 * DAY_8_CYCLES.iter().product::<u64>();
+        assert_eq!(crate::solve(2023, 8, 2, &input), Ok(expected.to_string()));
+    }
+
+    // Big enough inputs that anything quadratic blows the budget, which is
+    // AOC_STRESS_BUDGET_MS per part (5 seconds by default), on inputs from
+    // AOC_SEED (1727 by default). Run with
+    //     cargo test --release -p aoc-solutions stress -- --ignored
+    #[test]
+    #[ignore]
+    fn test_stress() {
+        let budget = std::env::var("AOC_STRESS_BUDGET_MS")
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(Duration::from_secs(5));
+        let seed = test_seed(1727);
+        let sizes = [(1, 200_000), (2, 100_000), (3, 2_000), (4, 200_000), (5, 2_000), (8, 97)];
+        for (day, size) in sizes {
+            let input = generate(2023, day, size, seed).unwrap();
+            for part in crate::solutions().iter().find(|(_, d, _)| *d == day).unwrap().2 {
+                let started = Instant::now();
+                let answer = crate::solve(2023, day, *part, &input);
+                let elapsed = started.elapsed();
+                assert!(answer.is_ok(), "day {} part {} seed {}: {:?}", day, part, seed, answer);
+                assert!(elapsed <= budget, "day {} part {} took {:?} on size {} seed {}", day, part, elapsed, size, seed);
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Every day's example in the shapes real inputs turn up in: saved on Windows,
+// with blank lines or stray spaces at the end, without a final newline. They
+// all have to give the example's answers, and empty input has to give an
+// answer or an error rather than a panic.
+
+use aoc_common::fixtures;
+
+fn variants(example: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("crlf", example.replace('\n', "\r\n")),
+        ("trailing blank lines", format!("{}\n\n\n", example)),
+        ("trailing whitespace", example.replace('\n', " \t\n")),
+        ("no final newline", example.trim_end().to_string()),
+    ]
+}
+
+#[test]
+fn edge_case_inputs() {
+    for (year, day, parts) in aoc_solutions::solutions() {
+        let example = fixtures::example(*day);
+        for part in parts.iter() {
+            let expected = aoc_solutions::solve(*year, *day, *part, &example);
+            assert!(expected.is_ok(), "{} day {} part {}: {:?}", year, day, part, expected);
+            for (name, input) in variants(&example) {
+                let answer = aoc_solutions::solve(*year, *day, *part, &input);
+                assert_eq!(answer, expected, "{} day {} part {} with {}", year, day, part, name);
+            }
+            for empty in ["", "\n", "\r\n\r\n", "  \n\n"] {
+                let answer = std::panic::catch_unwind(|| aoc_solutions::solve(*year, *day, *part, empty));
+                assert!(answer.is_ok(), "{} day {} part {} panicked on {:?}", year, day, part, empty);
+            }
+        }
+    }
+}
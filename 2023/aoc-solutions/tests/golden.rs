@@ -0,0 +1,37 @@
+// Checks every answer recorded in answers.toml against the solutions, for
+// the entries whose input is there. Inputs default to the day's personal
+// input, in the tree or aoc's cache, and other paths are relative to the
+// manifest, like `aoc batch` reads them.
+
+use std::fs;
+
+use aoc_common::{fixtures, manifest};
+
+#[test]
+fn golden_answers() {
+    let root = fixtures::workspace_root();
+    let text = fs::read_to_string(root.join("answers.toml")).expect("Could not read answers.toml");
+    let entries = manifest::parse(&text).expect("Could not parse answers.toml");
+    assert!(!entries.is_empty());
+
+    let mut checked = 0;
+    for entry in entries.iter() {
+        let input = match entry.input.as_ref() {
+            Some(input) => {
+                let input_path = root.join(input);
+                let input = fs::read_to_string(&input_path).ok();
+                if input.is_none() {
+                    eprintln!("skipping {} day {} part {}: no {}", entry.year, entry.day, entry.part, input_path.display());
+                }
+                input
+            }
+            None => fixtures::personal_input(entry.day),
+        };
+        let Some(input) = input else { continue };
+        let Some(expected) = entry.expected.as_deref() else { continue };
+        let answer = aoc_solutions::solve(entry.year, entry.day, entry.part, &input);
+        assert_eq!(answer.as_deref(), Ok(expected), "{} day {} part {}", entry.year, entry.day, entry.part);
+        checked += 1;
+    }
+    eprintln!("checked {} of {} answers", checked, entries.len());
+}
@@ -0,0 +1,15 @@
+fn main() {
+    // The gRPC service's code is generated from the proto file, with the
+    // protoc that comes with protoc-bin-vendored unless PROTOC names another.
+    // Nothing is generated without the grpc feature.
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/aoc.proto");
+        println!("cargo:rerun-if-env-changed=PROTOC");
+        if std::env::var_os("PROTOC").is_none() {
+            let protoc = protoc_bin_vendored::protoc_bin_path().expect("No vendored protoc for this platform; set PROTOC to one");
+            std::env::set_var("PROTOC", protoc);
+        }
+        tonic_build::compile_protos("proto/aoc.proto").expect("Could not compile proto/aoc.proto");
+    }
+}
@@ -0,0 +1,176 @@
+// `aoc verify`: every part of the year's days run on its own input and held
+// to the answer recorded in answers.toml, so a refactor that changes an
+// answer fails with what changed. A day without its input is skipped, since
+// personal inputs aren't in every checkout.
+
+use aoc_common::manifest::Entry;
+
+use crate::cache::Cache;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub expected: Option<String>,
+    // None when the day's input couldn't be read
+    pub answer: Option<Result<String, String>>,
+}
+
+impl Check {
+    fn status(&self) -> &'static str {
+        match (&self.answer, &self.expected) {
+            (None, _) => "skipped",
+            (Some(Err(_)), _) => "ERROR",
+            (Some(Ok(_)), None) => "unrecorded",
+            (Some(Ok(answer)), Some(expected)) if answer == expected => "ok",
+            (Some(Ok(_)), Some(_)) => "DIFFER",
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self.status(), "ERROR" | "DIFFER")
+    }
+}
+
+// Runs each part of the year's days on the input `read_input` gives for the
+// day, or takes its answer from the cache, next to the ledger's answer for
+// the day's own input
+pub fn check<F>(year: u32, ledger: &[Entry], read_input: F, cache: &mut Cache) -> Vec<Check>
+where
+    F: Fn(u32) -> Result<String, String>
+{
+    let mut checks = vec![];
+    for (_, day, parts) in aoc_solutions::solutions().iter().filter(|(y, _, _)| *y == year) {
+        let input = read_input(*day);
+        if let Err(err) = &input {
+            tracing::warn!("Skipping {} day {}: {}", year, day, err);
+        }
+        for part in parts.iter() {
+            let expected = ledger.iter()
+                .find(|entry| (entry.year, entry.day, entry.part) == (year, *day, *part) && entry.input.is_none())
+                .and_then(|entry| entry.expected.clone());
+            let answer = input.as_ref().ok().map(|input| {
+                tracing::info!("Verifying {} day {} part {}", year, day, part);
+                cache.get_or_solve(year, *day, *part, "solution", input, || aoc_solutions::solve(year, *day, *part, input))
+            });
+            checks.push(Check { day: *day, part: *part, expected, answer });
+        }
+    }
+    checks
+}
+
+// The lines of the expected answer and the one given, as a unified diff
+// would show them, keeping the lines they share
+pub fn diff(expected: &str, answer: &str) -> String {
+    let (expected, answer): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), answer.lines().collect());
+    // the longest common subsequence of lines, from the end
+    let mut common = vec![vec![0; answer.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..answer.len()).rev() {
+            common[i][j] = match expected[i] == answer[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < answer.len() {
+        if i < expected.len() && j < answer.len() && expected[i] == answer[j] {
+            out.push_str(&format!(" {}\n", expected[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == answer.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1]) {
+            out.push_str(&format!("-{}\n", expected[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+{}\n", answer[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+// A row per part, a diff under every answer that changed, and the counts
+pub fn report(year: u32, ledger: &str, checks: &[Check]) -> String {
+    let mut out = String::from("day part  status\n");
+    for check in checks {
+        out.push_str(&format!("{:>3} {:>4}  {}\n", check.day, check.part, check.status()));
+        match (&check.answer, &check.expected) {
+            (Some(Err(err)), _) => out.push_str(&format!("    {}\n", err)),
+            (Some(Ok(answer)), Some(expected)) if answer != expected => {
+                out.push_str(&format!("--- {} day {} part {} in {}\n+++ {} day {} part {} now\n", year, check.day, check.part, ledger, year, check.day, check.part));
+                out.push_str(&diff(expected, answer));
+            }
+            _ => (),
+        }
+    }
+    let count = |status: &str| checks.iter().filter(|check| check.status() == status).count();
+    out.push_str(&format!(
+        "{}: {} ok, {} differ, {} errors, {} unrecorded, {} skipped\n",
+        year,
+        count("ok"),
+        count("DIFFER"),
+        count("ERROR"),
+        count("unrecorded"),
+        count("skipped"),
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{fixtures, manifest};
+
+    #[test]
+    fn test_check() {
+        let ledger = manifest::parse("[[run]]\nyear = 2023\nday = 2\npart = 1\nexpected = \"8\"\n\n[[run]]\nyear = 2023\nday = 2\npart = 2\nexpected = \"1\"\n").unwrap();
+        let read = |day: u32| if day == 2 { Ok(fixtures::example(day)) } else { Err(String::from("no input")) };
+        let checks = check(2023, &ledger, read, &mut Cache::disabled());
+        let statuses: Vec<(u32, u32, &str)> = checks.iter().map(|check| (check.day, check.part, check.status())).collect();
+        assert_eq!(statuses[..3], [(1, 2, "skipped"), (2, 1, "ok"), (2, 2, "DIFFER")]);
+        assert!(checks[2].failed() && !checks[0].failed());
+        assert_eq!(checks[2].answer, Some(Ok(String::from("2286"))));
+        assert!(checks[3..].iter().all(|check| check.status() == "skipped"));
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("46", "47"), "-46\n+47\n");
+        assert_eq!(diff("#.#\n###\n..#", "#.#\n#.#\n..#"), " #.#\n-###\n+#.#\n ..#\n");
+        assert_eq!(diff("same", "same"), " same\n");
+    }
+
+    #[test]
+    fn test_report() {
+        let check = |day, part, expected: Option<&str>, answer: Option<Result<&str, &str>>| Check {
+            day,
+            part,
+            expected: expected.map(String::from),
+            answer: answer.map(|answer| answer.map(String::from).map_err(String::from)),
+        };
+        let checks = vec![
+            check(1, 2, Some("281"), Some(Ok("281"))),
+            check(3, 1, Some("520019"), Some(Ok("520018"))),
+            check(4, 1, None, Some(Ok("13"))),
+            check(5, 1, Some("35"), Some(Err("No seeds line"))),
+            check(8, 2, Some("6"), None),
+        ];
+        assert_eq!(
+            report(2023, "answers.toml", &checks),
+            "day part  status
+  1    2  ok
+  3    1  DIFFER
+--- 2023 day 3 part 1 in answers.toml
++++ 2023 day 3 part 1 now
+-520019
++520018
+  4    1  unrecorded
+  5    1  ERROR
+    No seeds line
+  8    2  skipped
+2023: 1 ok, 1 differ, 1 errors, 1 unrecorded, 1 skipped
+",
+        );
+    }
+}
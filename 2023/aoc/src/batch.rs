@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::manifest::Entry;
+
+use crate::bench;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass(Duration),
+    Fail { answer: String, duration: Duration },
+    Error(String),
+}
+
+impl Outcome {
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Pass(_) => "pass",
+            Outcome::Fail { .. } => "FAIL",
+            Outcome::Error(_) => "ERROR",
+        }
+    }
+}
+
+// Runs one entry, reading its input with `read_input`
+pub fn execute<F>(run: &Entry, read_input: F) -> Outcome
+where
+    F: Fn(&Entry) -> Result<String, String>
+{
+    if let Some(algorithm) = run.algorithm.as_deref() {
+        let available = aoc_solutions::algorithm(run.year, run.day, run.part);
+        if available != Some(algorithm) {
+            return Outcome::Error(format!("No '{}' variant of {} day {} part {}", algorithm, run.year, run.day, run.part));
+        }
+    }
+    let input = match read_input(run) {
+        Ok(input) => input,
+        Err(err) => return Outcome::Error(err),
+    };
+    match bench::time_part(run.year, run.day, run.part, &input, 1) {
+        Ok(timing) => {
+            let duration = timing.mean();
+            match run.expected.as_deref() {
+                Some(expected) if expected != timing.answer => Outcome::Fail { answer: timing.answer, duration },
+                _ => Outcome::Pass(duration),
+            }
+        }
+        Err(err) => Outcome::Error(err),
+    }
+}
+
+// Inputs in the manifest are relative to the manifest itself
+pub fn input_path(run: &Entry, manifest: &Path, default: PathBuf) -> PathBuf {
+    match run.input.as_ref() {
+        Some(input) => manifest.parent().unwrap_or(Path::new("")).join(input),
+        None => default,
+    }
+}
+
+// A row per run with its status, and the answer and expectation when they differ
+pub fn matrix(results: &[(Entry, Outcome)]) -> String {
+    let mut out = String::from("year day part algorithm     input                          status  time\n");
+    for (run, outcome) in results.iter() {
+        let input = run.input.as_ref().map(|input| input.display().to_string()).unwrap_or(String::from("-"));
+        let time = match outcome {
+            Outcome::Pass(duration) | Outcome::Fail { duration, .. } => format!("{:?}", duration),
+            Outcome::Error(_) => String::from("-"),
+        };
+        out.push_str(&format!(
+            "{:<4} {:>3} {:>4} {:<13} {:<30} {:<7} {}\n",
+            run.year,
+            run.day,
+            run.part,
+            run.algorithm.as_deref().unwrap_or("-"),
+            input,
+            outcome.status(),
+            time,
+        ));
+        match outcome {
+            Outcome::Fail { answer, .. } => out.push_str(&format!(
+                "    expected {}, got {}\n",
+                run.expected.as_deref().unwrap_or_default(),
+                answer,
+            )),
+            Outcome::Error(err) => out.push_str(&format!("    {}\n", err)),
+            Outcome::Pass(_) => (),
+        }
+    }
+    let passed = results.iter().filter(|(_, outcome)| matches!(outcome, Outcome::Pass(_))).count();
+    out.push_str(&format!("{} of {} passed\n", passed, results.len()));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::manifest;
+
+    const MANIFEST: &str = "# regression sweep
+[[run]]
+year = 2023
+day = 4
+part = 1
+input = \"stress/day #4.txt\" # a comment
+expected = \"13\"
+
+[[run]]
+year = 2023
+day = 4
+part = 2
+algorithm = \"copy-counts\"
+";
+
+    const DAY_4: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn test_execute() {
+        let runs = manifest::parse(MANIFEST).unwrap();
+        let read = |_: &Entry| Ok(String::from(DAY_4));
+        assert!(matches!(execute(&runs[0], read), Outcome::Pass(_)));
+        assert!(matches!(execute(&runs[1], read), Outcome::Pass(_)));
+
+        let wrong = Entry { expected: Some(String::from("12")), ..runs[0].clone() };
+        assert!(matches!(execute(&wrong, read), Outcome::Fail { answer, .. } if answer == "13"));
+
+        let unknown = Entry { algorithm: Some(String::from("brute-force")), ..runs[0].clone() };
+        assert_eq!(execute(&unknown, read), Outcome::Error(String::from("No 'brute-force' variant of 2023 day 4 part 1")));
+        assert_eq!(execute(&runs[0], |_| Err(String::from("missing"))), Outcome::Error(String::from("missing")));
+    }
+
+    #[test]
+    fn test_matrix() {
+        let run = manifest::parse(MANIFEST).unwrap().remove(0);
+        let results = vec![
+            (run.clone(), Outcome::Fail { answer: String::from("12"), duration: Duration::from_millis(1) }),
+            (run, Outcome::Error(String::from("missing"))),
+        ];
+        let matrix = matrix(&results);
+        assert!(matrix.contains("FAIL    1ms\n    expected 13, got 12\n"));
+        assert!(matrix.contains("ERROR   -\n    missing\n"));
+        assert!(matrix.ends_with("0 of 2 passed\n"));
+    }
+
+    #[test]
+    fn test_input_path() {
+        let run = manifest::parse(MANIFEST).unwrap().remove(0);
+        assert_eq!(input_path(&run, Path::new("sweeps/runs.toml"), PathBuf::new()), PathBuf::from("sweeps/stress/day #4.txt"));
+        let run = Entry { input: None, ..run };
+        assert_eq!(input_path(&run, Path::new("runs.toml"), PathBuf::from("day-4/input.txt")), PathBuf::from("day-4/input.txt"));
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use aoc_common::mem::{counted, AllocStats};
+use aoc_common::output::{json_string, timed};
+
+use crate::counters::{self, Counters};
+
+// How long one part took over several runs, and what one run allocated.
+// Both include parsing, since every solution parses its own input. With
+// `aoc bench --counters`, what the CPU counted too, the mean of the runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub runs: Vec<Duration>,
+    pub allocations: AllocStats,
+    pub counters: Option<Counters>,
+}
+
+impl Timing {
+    pub fn min(&self) -> Duration {
+        self.runs.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.runs.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn mean(&self) -> Duration {
+        if self.runs.is_empty() {
+            return Duration::default()
+        }
+        self.runs.iter().sum::<Duration>() / self.runs.len() as u32
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut runs = self.runs.clone();
+        runs.sort();
+        match runs.len() {
+            0 => Duration::default(),
+            len if len % 2 == 0 => (runs[len / 2 - 1] + runs[len / 2]) / 2,
+            len => runs[len / 2],
+        }
+    }
+}
+
+// Solves the part `runs` times, failing if any run doesn't find an answer
+pub fn time_part(year: u32, day: u32, part: u32, input: &str, runs: usize) -> Result<Timing, String> {
+    time_with(year, day, part, input, runs, |input| aoc_solutions::solve(year, day, part, input))
+}
+
+// Like time_part, with the part solved by `solve` rather than the registry
+pub fn time_with<F>(year: u32, day: u32, part: u32, input: &str, runs: usize, solve: F) -> Result<Timing, String>
+where
+    F: Fn(&str) -> Result<String, String>
+{
+    measure(year, day, part, input, runs, false, solve)
+}
+
+// Solves the part `runs` times, reading the hardware counters around each
+// run when `with_counters`
+fn measure<F>(year: u32, day: u32, part: u32, input: &str, runs: usize, with_counters: bool, solve: F) -> Result<Timing, String>
+where
+    F: Fn(&str) -> Result<String, String>
+{
+    let mut timing = Timing {
+        year,
+        day,
+        part,
+        answer: String::new(),
+        runs: vec![],
+        allocations: AllocStats::default(),
+        counters: None,
+    };
+    let mut counted_runs = vec![];
+    for _ in 0..runs.max(1) {
+        let run = || counted(|| timed(|| solve(input)));
+        let ((answer, duration), allocations) = if with_counters {
+            let (run, counters) = counters::measure(run)?;
+            counted_runs.push(counters);
+            run
+        } else {
+            run()
+        };
+        timing.answer = answer?;
+        timing.runs.push(duration);
+        timing.allocations = allocations;
+    }
+    if with_counters {
+        timing.counters = Some(Counters::mean(&counted_runs));
+    }
+    Ok(timing)
+}
+
+// The timing as a JSON object, the counters null when they weren't read
+pub fn to_json(timing: &Timing) -> String {
+    let counter = |count: fn(&Counters) -> u64| timing.counters.as_ref().map_or(String::from("null"), |counters| count(counters).to_string());
+    format!(
+        "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"runs\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"max_ns\":{},\"instructions\":{},\"cycles\":{},\"branch_misses\":{},\"cache_misses\":{}}}",
+        timing.year,
+        timing.day,
+        timing.part,
+        json_string(&timing.answer),
+        timing.runs.len(),
+        timing.min().as_nanos(),
+        timing.median().as_nanos(),
+        timing.mean().as_nanos(),
+        timing.max().as_nanos(),
+        counter(|counters| counters.instructions),
+        counter(|counters| counters.cycles),
+        counter(|counters| counters.branch_misses),
+        counter(|counters| counters.cache_misses),
+    )
+}
+
+// Times every solved part, or just one day's, reading inputs with `input_for`
+pub fn time_all<F>(only_day: Option<u32>, runs: usize, with_counters: bool, input_for: F) -> Result<Vec<Timing>, String>
+where
+    F: Fn(u32) -> Result<String, String>
+{
+    let mut timings = vec![];
+    for (year, day, parts) in aoc_solutions::solutions() {
+        if only_day.is_some_and(|only| only != *day) {
+            continue
+        }
+        let input = input_for(*day)?;
+        for part in parts.iter() {
+            let solve = |input: &str| aoc_solutions::solve(*year, *day, *part, input);
+            timings.push(measure(*year, *day, *part, &input, runs, with_counters, solve)?);
+        }
+    }
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let timing = Timing {
+            year: 2023,
+            day: 1,
+            part: 2,
+            answer: String::from("281"),
+            runs: [40, 10, 30, 20].iter().map(|ms| Duration::from_millis(*ms)).collect(),
+            allocations: AllocStats::default(),
+            counters: None,
+        };
+        assert_eq!(timing.min(), Duration::from_millis(10));
+        assert_eq!(timing.max(), Duration::from_millis(40));
+        assert_eq!(timing.mean(), Duration::from_millis(25));
+        assert_eq!(timing.median(), Duration::from_millis(25));
+    }
+
+    #[test]
+    fn test_time_part() {
+        let input = "two1nine\neightwothree\nabcone2threexyz\n";
+        let timing = time_part(2023, 1, 2, input, 3).unwrap();
+        assert_eq!(timing.answer, "125");
+        assert_eq!(timing.runs.len(), 3);
+        assert!(time_part(2023, 1, 1, input, 3).is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut timing = time_part(2023, 1, 2, "two1nine\n", 1).unwrap();
+        timing.runs = vec![Duration::from_nanos(1500)];
+        assert_eq!(
+            to_json(&timing),
+            "{\"year\":2023,\"day\":1,\"part\":2,\"answer\":\"29\",\"runs\":1,\"min_ns\":1500,\"median_ns\":1500,\"mean_ns\":1500,\"max_ns\":1500,\"instructions\":null,\"cycles\":null,\"branch_misses\":null,\"cache_misses\":null}",
+        );
+        timing.counters = Some(Counters { instructions: 9000, cycles: 3000, branch_misses: 12, cache_misses: 3 });
+        assert!(to_json(&timing).ends_with(",\"instructions\":9000,\"cycles\":3000,\"branch_misses\":12,\"cache_misses\":3}"));
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use aoc_common::manifest::Entry;
+
+use crate::bench;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Within(Duration),
+    Over(Duration),
+    Error(String),
+}
+
+impl Verdict {
+    fn status(&self) -> &'static str {
+        match self {
+            Verdict::Within(_) => "ok",
+            Verdict::Over(_) => "OVER",
+            Verdict::Error(_) => "ERROR",
+        }
+    }
+}
+
+// Times the entry `runs` times against its budget_ms, reading its input with
+// `read_input`. The median is what counts, so one slow run doesn't fail it.
+pub fn check<F>(run: &Entry, runs: usize, read_input: F) -> Verdict
+where
+    F: Fn(&Entry) -> Result<String, String>
+{
+    let Some(budget_ms) = run.budget_ms else {
+        return Verdict::Error(String::from("No budget_ms given"))
+    };
+    let input = match read_input(run) {
+        Ok(input) => input,
+        Err(err) => return Verdict::Error(err),
+    };
+    match bench::time_part(run.year, run.day, run.part, &input, runs) {
+        Ok(timing) if timing.median() > Duration::from_millis(budget_ms) => Verdict::Over(timing.median()),
+        Ok(timing) => Verdict::Within(timing.median()),
+        Err(err) => Verdict::Error(err),
+    }
+}
+
+// A row per entry with its budget and median time
+pub fn table(results: &[(Entry, Verdict)]) -> String {
+    let mut out = String::from("year day part budget     time         status\n");
+    for (run, verdict) in results.iter() {
+        let budget = run.budget_ms.map(|ms| format!("{}ms", ms)).unwrap_or(String::from("-"));
+        let time = match verdict {
+            Verdict::Within(duration) | Verdict::Over(duration) => format!("{:?}", duration),
+            Verdict::Error(_) => String::from("-"),
+        };
+        out.push_str(&format!("{:<4} {:>3} {:>4} {:<10} {:<12} {}\n", run.year, run.day, run.part, budget, time, verdict.status()));
+        if let Verdict::Error(err) = verdict {
+            out.push_str(&format!("    {}\n", err));
+        }
+    }
+    let within = results.iter().filter(|(_, verdict)| matches!(verdict, Verdict::Within(_))).count();
+    out.push_str(&format!("{} of {} within budget\n", within, results.len()));
+    out
+}
+
+// The runs that didn't make it, as "2023 day 5 part 2", for the error message
+pub fn failures(results: &[(Entry, Verdict)]) -> Vec<String> {
+    results.iter()
+        .filter(|(_, verdict)| !matches!(verdict, Verdict::Within(_)))
+        .map(|(run, _)| format!("{} day {} part {}", run.year, run.day, run.part))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::manifest;
+
+    const BUDGETS: &str = "[[run]]
+year = 2023
+day = 4
+part = 1
+budget_ms = 10000
+
+[[run]]
+year = 2023
+day = 4
+part = 2
+budget_ms = 0
+";
+
+    const DAY_4: &str = include_str!("../../day-4/example.txt");
+
+    #[test]
+    fn test_check() {
+        let runs = manifest::parse(BUDGETS).unwrap();
+        let read = |_: &Entry| Ok(String::from(DAY_4));
+        assert!(matches!(check(&runs[0], 3, read), Verdict::Within(_)));
+        assert!(matches!(check(&runs[1], 3, read), Verdict::Over(_)));
+
+        let unbudgeted = Entry { budget_ms: None, ..runs[0].clone() };
+        assert_eq!(check(&unbudgeted, 1, read), Verdict::Error(String::from("No budget_ms given")));
+        assert_eq!(check(&runs[0], 1, |_| Err(String::from("missing"))), Verdict::Error(String::from("missing")));
+        let unsolved = Entry { day: 6, ..runs[0].clone() };
+        assert!(matches!(check(&unsolved, 1, read), Verdict::Error(_)));
+    }
+
+    #[test]
+    fn test_table() {
+        let runs = manifest::parse(BUDGETS).unwrap();
+        let results = vec![
+            (runs[0].clone(), Verdict::Within(Duration::from_millis(3))),
+            (runs[1].clone(), Verdict::Over(Duration::from_micros(20))),
+            (Entry { budget_ms: None, ..runs[1].clone() }, Verdict::Error(String::from("No budget_ms given"))),
+        ];
+        assert_eq!(
+            table(&results),
+            "year day part budget     time         status
+2023   4    1 10000ms    3ms          ok
+2023   4    2 0ms        20µs         OVER
+2023   4    2 -          -            ERROR
+    No budget_ms given
+1 of 3 within budget
+",
+        );
+        assert_eq!(failures(&results), vec!["2023 day 4 part 2", "2023 day 4 part 2"]);
+    }
+}
@@ -0,0 +1,209 @@
+// Answers already worked out, kept between runs so `aoc all`, `aoc status`
+// and `aoc verify` only solve what changed. An answer is keyed by the input's
+// hash, the day and part and the algorithm that found it, and belongs to the
+// build of the runner that found it: a rebuilt runner may solve differently,
+// so entries from any other build are dropped when the cache is read.
+//
+// The file is answers.tsv in aoc's cache directory, and has a line per answer, tab separated:
+//
+//     build  input_hash  year  day  part  algorithm  answer
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::output::input_hash;
+
+// where the answers were kept before they moved to aoc's cache directory
+pub const OLD_PATH: &str = "target/aoc-answers.tsv";
+
+type Key = (String, u32, u32, u32, String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    // None when caching is off
+    path: Option<PathBuf>,
+    build: String,
+    answers: HashMap<Key, String>,
+    changed: bool,
+}
+
+// Which build of the runner this is, from the executable's size and when it
+// was written, which a rebuild changes
+pub fn build_id() -> String {
+    let metadata = std::env::current_exe().and_then(fs::metadata);
+    let stamp = match metadata {
+        Ok(metadata) => {
+            let modified = metadata.modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|since| since.as_nanos())
+                .unwrap_or(0);
+            format!("{}:{}", metadata.len(), modified)
+        }
+        Err(_) => String::from("unknown"),
+    };
+    input_hash(&stamp)
+}
+
+impl Cache {
+    // The answers `build` stored at `path`, none if it can't be read
+    pub fn open(path: &Path, build: &str) -> Cache {
+        let mut answers = HashMap::new();
+        let text = fs::read_to_string(path).unwrap_or_default();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [entry_build, hash, year, day, part, algorithm, answer] = fields[..] else { continue };
+            if entry_build != build {
+                continue
+            }
+            let (Ok(year), Ok(day), Ok(part)) = (year.parse(), day.parse(), part.parse()) else { continue };
+            answers.insert((hash.to_string(), year, day, part, algorithm.to_string()), answer.to_string());
+        }
+        tracing::debug!("{} cached answers in {}", answers.len(), path.display());
+        Cache { path: Some(path.to_path_buf()), build: build.to_string(), answers, changed: false }
+    }
+
+    // The answers at `path`, or at `old` while there's nothing at `path`
+    // yet. Either way they're saved to `path`.
+    pub fn open_moved(path: &Path, old: &Path, build: &str) -> Cache {
+        let mut cache = Cache::open(if path.exists() { path } else { old }, build);
+        cache.path = Some(path.to_path_buf());
+        cache
+    }
+
+    // Solves everything, for --no-cache
+    pub fn disabled() -> Cache {
+        Cache { path: None, build: String::new(), answers: HashMap::new(), changed: false }
+    }
+
+    // The cached answer, or `solve`'s, which is kept if it's one. Errors
+    // aren't kept, so they're tried again next time.
+    pub fn get_or_solve<F>(&mut self, year: u32, day: u32, part: u32, algorithm: &str, input: &str, solve: F) -> Result<String, String>
+    where
+        F: FnOnce() -> Result<String, String>
+    {
+        if self.path.is_none() {
+            return solve()
+        }
+        let key = (input_hash(input), year, day, part, algorithm.to_string());
+        if let Some(answer) = self.answers.get(&key) {
+            tracing::debug!("{} day {} part {} ({}) from the cache", year, day, part, algorithm);
+            return Ok(answer.clone())
+        }
+        let answer = solve()?;
+        // the file's a line per answer
+        if !answer.contains(['\t', '\n']) {
+            self.answers.insert(key, answer.clone());
+            self.changed = true;
+        }
+        Ok(answer)
+    }
+
+    // Writes the answers back if any were added
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else { return Ok(()) };
+        if !self.changed {
+            return Ok(())
+        }
+        let mut lines: Vec<String> = self.answers.iter()
+            .map(|((hash, year, day, part, algorithm), answer)| {
+                format!("{}\t{}\t{}\t{}\t{}\t{}\t{}\n", self.build, hash, year, day, part, algorithm, answer)
+            })
+            .collect();
+        lines.sort();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+        }
+        fs::write(path, lines.concat()).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("answers.tsv");
+        let calls = Cell::new(0);
+        let solve = || {
+            calls.set(calls.get() + 1);
+            Ok(String::from("46"))
+        };
+
+        let mut cache = Cache::open(&path, "build-1");
+        assert_eq!(cache.get_or_solve(2023, 5, 2, "range-map", "input", solve), Ok(String::from("46")));
+        assert_eq!(cache.get_or_solve(2023, 5, 2, "range-map", "input", solve), Ok(String::from("46")));
+        assert_eq!(calls.get(), 1);
+        cache.save().unwrap();
+
+        let mut cache = Cache::open(&path, "build-1");
+        assert_eq!(cache.answers.len(), 1);
+        cache.get_or_solve(2023, 5, 2, "range-map", "input", solve).unwrap();
+        assert_eq!(calls.get(), 1);
+        // another input, part or algorithm is another answer
+        cache.get_or_solve(2023, 5, 2, "range-map", "other input", solve).unwrap();
+        cache.get_or_solve(2023, 5, 1, "range-map", "input", solve).unwrap();
+        cache.get_or_solve(2023, 5, 2, "tree", "input", solve).unwrap();
+        assert_eq!(calls.get(), 4);
+
+        // another build starts over
+        assert_eq!(Cache::open(&path, "build-2").answers.len(), 0);
+    }
+
+    #[test]
+    fn test_open_moved() {
+        let dir = tempfile::tempdir().unwrap();
+        let (old, path) = (dir.path().join("old").join("answers.tsv"), dir.path().join("new").join("answers.tsv"));
+        let mut cache = Cache::open(&old, "build-1");
+        cache.get_or_solve(2023, 5, 2, "range-map", "input", || Ok(String::from("46"))).unwrap();
+        cache.save().unwrap();
+
+        // read from where they were, and saved where they go now
+        let mut cache = Cache::open_moved(&path, &old, "build-1");
+        assert_eq!(cache.answers.len(), 1);
+        cache.get_or_solve(2023, 5, 1, "range-map", "input", || Ok(String::from("35"))).unwrap();
+        cache.save().unwrap();
+        assert_eq!(Cache::open(&path, "build-1").answers.len(), 2);
+        assert_eq!(Cache::open(&old, "build-1").answers.len(), 1);
+        // once they're there, the old ones aren't read
+        fs::remove_file(&path).unwrap();
+        fs::write(&path, "").unwrap();
+        assert_eq!(Cache::open_moved(&path, &old, "build-1").answers.len(), 0);
+    }
+
+    #[test]
+    fn test_errors_not_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("answers.tsv");
+        let mut cache = Cache::open(&path, "build");
+        assert!(cache.get_or_solve(2023, 6, 1, "default", "", || Err(String::from("No solution"))).is_err());
+        assert_eq!(cache.answers.len(), 0);
+        // nothing to write
+        cache.save().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_disabled() {
+        let calls = Cell::new(0);
+        let mut cache = Cache::disabled();
+        for _ in 0..2 {
+            cache.get_or_solve(2023, 5, 1, "flatten", "input", || {
+                calls.set(calls.get() + 1);
+                Ok(String::from("35"))
+            }).unwrap();
+        }
+        assert_eq!(calls.get(), 2);
+        cache.save().unwrap();
+    }
+
+    #[test]
+    fn test_build_id() {
+        assert_eq!(build_id(), build_id());
+        assert!(build_id().starts_with("fnv1a64:"));
+    }
+}
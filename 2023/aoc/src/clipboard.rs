@@ -0,0 +1,37 @@
+// Puzzle inputs read straight from the system clipboard for `aoc run
+// --clipboard`, the way they're grabbed from the browser during a live solve.
+// Reading it takes aoc built with the clipboard feature, through arboard;
+// on Linux that's X11's clipboard, which Wayland desktops share through
+// XWayland.
+
+#[cfg(feature = "clipboard")]
+pub fn read() -> Result<String, String> {
+    let unavailable = |err: arboard::Error| format!("Could not read the clipboard: {}", err);
+    let text = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()).map_err(unavailable)?;
+    // what's copied out of a browser may come without the last newline
+    match text.trim().is_empty() {
+        true => Err(String::from("The clipboard is empty")),
+        false if text.ends_with('\n') => Ok(text),
+        false => Ok(text + "\n"),
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn read() -> Result<String, String> {
+    Err(String::from("Reading the clipboard needs aoc built with the clipboard feature"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // there's rarely a clipboard to read where the tests run, so all that's
+    // certain is that it fails with a message rather than panicking
+    #[test]
+    fn test_read() {
+        match read() {
+            Ok(text) => assert!(text.ends_with('\n')),
+            Err(err) => assert!(err.contains("clipboard"), "{}", err),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Hardware performance counters for `aoc bench --counters`, read through
+// perf_event_open on Linux with the perf feature. The kernel may still refuse
+// them, in a VM without a PMU or with kernel.perf_event_paranoid above 2, and
+// then measuring fails with the kernel's reason rather than giving zeros.
+
+// What the CPU counted for one run of a part
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Counters {
+    pub instructions: u64,
+    pub cycles: u64,
+    pub branch_misses: u64,
+    pub cache_misses: u64,
+}
+
+impl Counters {
+    // The mean of several runs' counts
+    pub fn mean(runs: &[Counters]) -> Counters {
+        let len = runs.len().max(1) as u64;
+        let sum = |count: fn(&Counters) -> u64| runs.iter().map(count).sum::<u64>() / len;
+        Counters {
+            instructions: sum(|counters| counters.instructions),
+            cycles: sum(|counters| counters.cycles),
+            branch_misses: sum(|counters| counters.branch_misses),
+            cache_misses: sum(|counters| counters.cache_misses),
+        }
+    }
+
+    // Instructions per cycle
+    pub fn ipc(&self) -> f64 {
+        if self.cycles == 0 {
+            return 0.0
+        }
+        self.instructions as f64 / self.cycles as f64
+    }
+}
+
+// Runs `f` with the counters on, this thread's user space only
+#[cfg(all(feature = "perf", target_os = "linux"))]
+pub fn measure<R, F: FnOnce() -> R>(f: F) -> Result<(R, Counters), String> {
+    use perf_event::events::Hardware;
+    use perf_event::{Builder, Group};
+
+    let unavailable = |err: std::io::Error| format!("Hardware counters aren't available: {}", err);
+    let mut group = Group::new().map_err(unavailable)?;
+    let mut counter = |kind: Hardware| Builder::new().group(&mut group).kind(kind).build().map_err(unavailable);
+    let instructions = counter(Hardware::INSTRUCTIONS)?;
+    let cycles = counter(Hardware::CPU_CYCLES)?;
+    let branch_misses = counter(Hardware::BRANCH_MISSES)?;
+    let cache_misses = counter(Hardware::CACHE_MISSES)?;
+
+    group.enable().map_err(unavailable)?;
+    let result = f();
+    group.disable().map_err(unavailable)?;
+    let counts = group.read().map_err(unavailable)?;
+    let counters = Counters {
+        instructions: counts[&instructions],
+        cycles: counts[&cycles],
+        branch_misses: counts[&branch_misses],
+        cache_misses: counts[&cache_misses],
+    };
+    Ok((result, counters))
+}
+
+#[cfg(not(all(feature = "perf", target_os = "linux")))]
+pub fn measure<R, F: FnOnce() -> R>(_f: F) -> Result<(R, Counters), String> {
+    Err(String::from("Hardware counters need aoc built with the perf feature, on Linux"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean() {
+        let run = |instructions: u64, cycles: u64| Counters { instructions, cycles, branch_misses: 4, cache_misses: cycles / 10 };
+        let mean = Counters::mean(&[run(300, 100), run(500, 300)]);
+        assert_eq!(mean, Counters { instructions: 400, cycles: 200, branch_misses: 4, cache_misses: 20 });
+        assert_eq!(mean.ipc(), 2.0);
+        assert_eq!(Counters::mean(&[]), Counters::default());
+        assert_eq!(Counters::default().ipc(), 0.0);
+    }
+
+    // Either real counts or the reason there are none; a sandbox or VM
+    // often has no counters to give
+    #[test]
+    fn test_measure() {
+        match measure(|| (0..100_000u64).sum::<u64>()) {
+            Ok((sum, counters)) => {
+                assert_eq!(sum, 4_999_950_000);
+                assert!(counters.instructions > 0);
+            }
+            Err(err) => assert!(err.starts_with("Hardware counters"), "{}", err),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::bench::Timing;
+
+pub const HEADER: &str = "date,commit,year,day,part,runs,min_ns,median_ns,mean_ns,max_ns,instructions,cycles,branch_misses,cache_misses";
+
+// One row per part, tagged with when and at which commit it was measured so
+// rows from many runs can share a file. The counters are left empty unless
+// they were read.
+pub fn row(timing: &Timing, date: &str, commit: &str) -> String {
+    let counters = match &timing.counters {
+        Some(counters) => format!("{},{},{},{}", counters.instructions, counters.cycles, counters.branch_misses, counters.cache_misses),
+        None => String::from(",,,"),
+    };
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}",
+        date,
+        commit,
+        timing.year,
+        timing.day,
+        timing.part,
+        timing.runs.len(),
+        timing.min().as_nanos(),
+        timing.median().as_nanos(),
+        timing.mean().as_nanos(),
+        timing.max().as_nanos(),
+        counters,
+    )
+}
+
+// Appends to the file, only writing the header when it's new or empty. A
+// file started with other columns is left alone rather than mixed with rows
+// it can't hold.
+pub fn append(path: &Path, timings: &[Timing]) -> Result<(), String> {
+    let needs_header = std::fs::metadata(path).map(|meta| meta.len() == 0).unwrap_or(true);
+    if !needs_header {
+        let existing = std::fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        if existing.lines().next() != Some(HEADER) {
+            return Err(format!("{} has other columns than these timings, write them to a new file", path.display()))
+        }
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| format!("Could not open {}: {}", path.display(), err))?;
+
+    let (date, commit) = (today(), git_commit());
+    let mut contents = String::new();
+    if needs_header {
+        contents.push_str(HEADER);
+        contents.push('\n');
+    }
+    for timing in timings {
+        contents.push_str(&row(timing, &date, &commit));
+        contents.push('\n');
+    }
+    file.write_all(contents.as_bytes())
+        .map_err(|err| format!("Could not write {}: {}", path.display(), err))
+}
+
+// Short hash of HEAD, with a trailing + when the tree has uncommitted changes
+pub fn git_commit() -> String {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").args(args).output().ok()?;
+        if !output.status.success() {
+            return None
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain"]) {
+            Some(status) if !status.is_empty() => format!("{}+", hash),
+            _ => hash,
+        },
+        None => String::from("unknown"),
+    }
+}
+
+// The current UTC date as YYYY-MM-DD
+pub fn today() -> String {
+    let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (year, month, day) = civil_from_days((seconds / 86400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Days since 1970-01-01 to a calendar date, after Howard Hinnant's
+// civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::mem::AllocStats;
+    use crate::counters::Counters;
+    use std::time::Duration;
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_row() {
+        let timing = Timing {
+            year: 2023,
+            day: 5,
+            part: 1,
+            answer: String::from("35"),
+            runs: vec![Duration::from_nanos(300), Duration::from_nanos(100)],
+            allocations: AllocStats::default(),
+            counters: None,
+        };
+        assert_eq!(row(&timing, "2023-12-05", "abc1234"), "2023-12-05,abc1234,2023,5,1,2,100,200,200,300,,,,");
+        assert_eq!(row(&timing, "", "").split(',').count(), HEADER.split(',').count());
+
+        let timing = Timing { counters: Some(Counters { instructions: 900, cycles: 300, branch_misses: 7, cache_misses: 2 }), ..timing };
+        assert!(row(&timing, "2023-12-05", "abc1234").ends_with(",300,900,300,7,2"));
+    }
+
+    #[test]
+    fn test_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timings.csv");
+        let timings = vec![crate::bench::time_part(2023, 1, 2, "two1nine\n", 1).unwrap()];
+        append(&path, &timings).unwrap();
+        append(&path, &timings).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 3);
+        assert_eq!(text.lines().next(), Some(HEADER));
+
+        std::fs::write(&path, "date,commit,year,day,part,runs,min_ns,median_ns,mean_ns,max_ns\n").unwrap();
+        assert!(append(&path, &timings).unwrap_err().ends_with("has other columns than these timings, write them to a new file"));
+    }
+}
@@ -0,0 +1,195 @@
+// What `aoc tui` shows, kept apart from the drawing so it works and can be
+// tested without a terminal: a row per solved day from the registry, each
+// part's answer from the ledger, and how the last run of it went.
+
+use std::time::Duration;
+
+use aoc_common::manifest::Entry;
+use aoc_common::output::timed;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartStatus {
+    pub part: u32,
+    pub enabled: bool,
+    pub expected: Option<String>,
+    pub outcome: Option<Result<(String, Duration), String>>,
+}
+
+impl PartStatus {
+    // A star is a run that gave the answer in the ledger
+    pub fn star(&self) -> bool {
+        match (&self.outcome, &self.expected) {
+            (Some(Ok((answer, _))), Some(expected)) => answer == expected,
+            _ => false,
+        }
+    }
+
+    pub fn status(&self) -> String {
+        match &self.outcome {
+            _ if !self.enabled => String::from("off"),
+            None => String::from("-"),
+            Some(Ok((answer, _))) if self.expected.as_ref().is_some_and(|expected| expected != answer) => String::from("wrong"),
+            Some(Ok((answer, _))) => answer.clone(),
+            Some(Err(_)) => String::from("error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub parts: Vec<PartStatus>,
+}
+
+impl Day {
+    pub fn time(&self) -> Duration {
+        self.parts.iter()
+            .filter_map(|part| match &part.outcome {
+                Some(Ok((_, duration))) => Some(*duration),
+                _ => None,
+            })
+            .sum()
+    }
+
+    pub fn stars(&self) -> usize {
+        self.parts.iter().filter(|part| part.star()).count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dashboard {
+    pub days: Vec<Day>,
+    pub selected: usize,
+    // What the last run printed, a line per part
+    pub output: Vec<String>,
+}
+
+impl Dashboard {
+    // Every solved day with its parts switched on, and the answers the ledger
+    // has for the default inputs
+    pub fn new(ledger: &[Entry]) -> Dashboard {
+        let days = aoc_solutions::solutions().iter()
+            .map(|(year, day, parts)| Day {
+                year: *year,
+                day: *day,
+                parts: parts.iter()
+                    .map(|part| PartStatus {
+                        part: *part,
+                        enabled: true,
+                        expected: ledger.iter()
+                            .find(|entry| (entry.year, entry.day, entry.part) == (*year, *day, *part) && entry.input.is_none())
+                            .and_then(|entry| entry.expected.clone()),
+                        outcome: None,
+                    })
+                    .collect(),
+            })
+            .collect();
+        Dashboard { days, selected: 0, output: vec![] }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.days.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn toggle_part(&mut self, part: u32) {
+        if let Some(status) = self.days.get_mut(self.selected).and_then(|day| day.parts.iter_mut().find(|status| status.part == part)) {
+            status.enabled = !status.enabled;
+        }
+    }
+
+    // Runs the selected day's enabled parts on the input `read_input` gives
+    // for it
+    pub fn run_selected<F>(&mut self, read_input: F)
+    where
+        F: Fn(u32) -> Result<String, String>
+    {
+        let Some(day) = self.days.get_mut(self.selected) else { return };
+        self.output.clear();
+        let input = match read_input(day.day) {
+            Ok(input) => input,
+            Err(err) => {
+                self.output.push(err);
+                return
+            }
+        };
+        for status in day.parts.iter_mut().filter(|status| status.enabled) {
+            let (answer, duration) = timed(|| aoc_solutions::solve(day.year, day.day, status.part, &input));
+            self.output.push(match &answer {
+                Ok(answer) => format!("part {}: {} ({:?})", status.part, answer, duration),
+                Err(err) => format!("part {}: error: {}", status.part, err),
+            });
+            status.outcome = Some(answer.map(|answer| (answer, duration)));
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(|day| day.stars()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{fixtures, manifest};
+
+    const LEDGER: &str = "[[run]]\nyear = 2023\nday = 1\npart = 2\nexpected = \"281\"\n
+[[run]]\nyear = 2023\nday = 2\npart = 1\nexpected = \"9\"\n
+[[run]]\nyear = 2023\nday = 2\npart = 2\ninput = \"other.txt\"\nexpected = \"1\"\n";
+
+    fn dashboard() -> Dashboard {
+        Dashboard::new(&manifest::parse(LEDGER).unwrap())
+    }
+
+    #[test]
+    fn test_new() {
+        let dashboard = dashboard();
+        assert_eq!(dashboard.days.len(), aoc_solutions::solutions().len());
+        assert_eq!(dashboard.days[0].parts[0].expected.as_deref(), Some("281"));
+        // only answers for the default input count
+        assert_eq!(dashboard.days[1].parts[1].expected, None);
+        assert!(dashboard.days.iter().flat_map(|day| day.parts.iter()).all(|part| part.enabled && part.outcome.is_none()));
+    }
+
+    #[test]
+    fn test_select() {
+        let mut dashboard = dashboard();
+        dashboard.select_previous();
+        assert_eq!(dashboard.selected, 0);
+        for _ in 0..100 {
+            dashboard.select_next();
+        }
+        assert_eq!(dashboard.selected, dashboard.days.len() - 1);
+    }
+
+    #[test]
+    fn test_run_selected() {
+        let mut dashboard = dashboard();
+        let example = |day: u32| Ok(fixtures::example(day));
+        dashboard.run_selected(example);
+        assert_eq!(dashboard.days[0].parts[0].status(), "281");
+        assert_eq!(dashboard.stars(), 1);
+        assert!(dashboard.output[0].starts_with("part 2: 281 ("));
+        assert!(!dashboard.days[0].time().is_zero());
+        assert!(dashboard.days[1].time().is_zero());
+
+        // day 2's part 1 is 8 on the example, not the 9 in the ledger
+        dashboard.select_next();
+        dashboard.toggle_part(2);
+        dashboard.run_selected(example);
+        assert_eq!(dashboard.days[1].parts[0].status(), "wrong");
+        assert_eq!(dashboard.days[1].parts[1].status(), "off");
+        assert_eq!(dashboard.days[1].parts[1].outcome, None);
+        assert_eq!(dashboard.output.len(), 1);
+        assert_eq!(dashboard.stars(), 1);
+
+        dashboard.run_selected(|_| Err(String::from("Could not read day-2/input.txt")));
+        assert_eq!(dashboard.output, vec!["Could not read day-2/input.txt"]);
+    }
+}
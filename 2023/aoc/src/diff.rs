@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use aoc_common::output::timed;
+
+// One variant's answer to a part, with the messages it logged on the way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantRun {
+    pub variant: String,
+    pub answer: Result<String, String>,
+    pub duration: Duration,
+    pub trace: Vec<String>,
+}
+
+// Tracing is on while capturing, so the timings include formatting every
+// traced step; both variants pay for it alike
+pub fn run_variant(year: u32, day: u32, part: u32, variant: &str, input: &str) -> VariantRun {
+    let ((answer, duration), trace) = crate::logging::capture(|| {
+        timed(|| aoc_solutions::solve_variant(year, day, part, variant, input))
+    });
+    VariantRun { variant: variant.to_string(), answer, duration, trace }
+}
+
+// The index of the first step the traces disagree on, counting a missing
+// step at the end of the shorter one
+pub fn first_divergence(a: &[String], b: &[String]) -> Option<usize> {
+    match a.iter().zip(b.iter()).position(|(a, b)| a != b) {
+        Some(idx) => Some(idx),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+fn answer_text(run: &VariantRun) -> String {
+    match &run.answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+// Both answers and timings, and where the runs went apart if the answers differ
+pub fn report(part: u32, a: &VariantRun, b: &VariantRun) -> String {
+    let width = a.variant.len().max(b.variant.len());
+    let mut out = format!("part {}\n", part);
+    for run in [a, b] {
+        out.push_str(&format!("  {:<width$}  {:>20}  {:?}\n", run.variant, answer_text(run), run.duration, width = width));
+    }
+    if a.answer == b.answer {
+        out.push_str("  answers match\n");
+        return out
+    }
+    match first_divergence(&a.trace, &b.trace) {
+        Some(idx) => {
+            out.push_str(&format!("  answers differ, first diverging step is #{}:\n", idx + 1));
+            for run in [a, b] {
+                let step = run.trace.get(idx).map(|step| step.as_str()).unwrap_or("(no more steps)");
+                out.push_str(&format!("    {:<width$}  {}\n", run.variant, step, width = width));
+            }
+        }
+        None => out.push_str(&format!("  answers differ, but all {} traced steps agree\n", a.trace.len())),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(variant: &str, answer: &str, trace: &[&str]) -> VariantRun {
+        VariantRun {
+            variant: variant.to_string(),
+            answer: Ok(answer.to_string()),
+            duration: Duration::from_millis(2),
+            trace: trace.iter().map(|step| step.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let steps = |steps: &[&str]| -> Vec<String> { steps.iter().map(|step| step.to_string()).collect() };
+        assert_eq!(first_divergence(&steps(&["a", "b"]), &steps(&["a", "b"])), None);
+        assert_eq!(first_divergence(&steps(&["a", "b"]), &steps(&["a", "c"])), Some(1));
+        assert_eq!(first_divergence(&steps(&["a"]), &steps(&["a", "b"])), Some(1));
+        assert_eq!(first_divergence(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let tree = run("tree", "35", &["seed 79 -> location 82", "seed 14 -> location 43"]);
+        let sorted = run("sorted", "35", &["seed 79 -> location 82", "seed 14 -> location 43"]);
+        assert_eq!(
+            report(1, &tree, &sorted),
+            "part 1\n  tree                      35  2ms\n  sorted                    35  2ms\n  answers match\n",
+        );
+
+        let sorted = run("sorted", "43", &["seed 79 -> location 82", "seed 14 -> location 44"]);
+        assert!(report(1, &tree, &sorted).ends_with(
+            "  answers differ, first diverging step is #2:\n    tree    seed 14 -> location 43\n    sorted  seed 14 -> location 44\n"
+        ));
+    }
+
+    #[test]
+    fn test_run_variant() {
+        let input = "seeds: 79 14\n\nseed-to-location map:\n50 98 2\n52 50 48\n";
+        let tree = run_variant(2023, 5, 1, "tree", input);
+        let sorted = run_variant(2023, 5, 1, "sorted", input);
+        assert_eq!(tree.answer.as_deref(), Ok("14"));
+        assert_eq!(tree.answer, sorted.answer);
+        assert_eq!(tree.trace, vec!["day_5: seed 79 -> location 81", "day_5: seed 14 -> location 14"]);
+        assert_eq!(first_divergence(&tree.trace, &sorted.trace), None);
+        assert!(run_variant(2023, 5, 1, "list", input).answer.is_err());
+    }
+}
@@ -0,0 +1,339 @@
+// Puzzle inputs downloaded from adventofcode.com the first time a day runs
+// without one, and kept in aoc's cache, so every later run reads the saved
+// file instead; `aoc puzzle` keeps the puzzles' pages the same way. Each
+// input is personal, so the download needs the session cookie of a logged in
+// browser: AOC_SESSION, or the contents of $XDG_CONFIG_HOME/aoc/session
+// (~/.config/aoc/session). The request goes through curl, which takes care
+// of TLS. Inputs shared from elsewhere, `aoc run --input-url <url>`, are kept
+// in the cache's urls/ by the URL's hash next to the ETag they came with, and
+// asked for again with it on every run, so they're only downloaded again once
+// they've changed.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use aoc_common::error::AocError;
+use aoc_common::output::input_hash;
+
+// Who's asking, as adventofcode.com asks automated requests to say
+const USER_AGENT: &str = "github.com/Goos/advent-of-code aoc runner";
+
+pub fn input_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{}/day/{}/input", year, day)
+}
+
+pub fn puzzle_url(year: u32, day: u32) -> String {
+    format!("https://adventofcode.com/{}/day/{}", year, day)
+}
+
+// The session token from `var`, or else from the file at `config`, either
+// as the bare token or as the cookie, session=<token>
+fn session_from(var: Option<String>, config: Option<&Path>) -> Result<Option<String>, String> {
+    let (token, source) = match (var, config) {
+        (Some(var), _) => (var, String::from("AOC_SESSION")),
+        (None, Some(path)) if path.exists() => (AocError::read(path)?, path.display().to_string()),
+        (None, _) => return Ok(None),
+    };
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    // it goes into curl's config, so nothing that could end the header
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("The session in {} isn't a session token", source))
+    }
+    Ok(Some(token.to_string()))
+}
+
+pub fn session() -> Result<Option<String>, String> {
+    session_from(env::var("AOC_SESSION").ok(), aoc_common::dirs::config_dir().map(|dir| dir.join("session")).as_deref())
+}
+
+// curl's config for the request, given on its stdin so the token never shows
+// in the process list
+fn curl_config(url: &str, session: Option<&str>) -> String {
+    let cookie = session.map(|session| format!("header = \"Cookie: session={}\"\n", session)).unwrap_or_default();
+    format!("url = \"{}\"\n{}user-agent = \"{}\"\n", url, cookie, USER_AGENT)
+}
+
+pub fn download(url: &str, session: Option<&str>) -> Result<String, String> {
+    tracing::info!("Downloading {}", url);
+    let failed = |err: String| format!("Could not download {}: {}", url, err);
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| failed(format!("could not run curl: {}", err)))?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        stdin.write_all(curl_config(url, session).as_bytes()).map_err(|err| failed(err.to_string()))?;
+    }
+    let output = curl.wait_with_output().map_err(|err| failed(err.to_string()))?;
+    if !output.status.success() {
+        // 400 for a session that's expired, 404 for a puzzle that's not out yet
+        return Err(failed(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+    String::from_utf8(output.stdout).map_err(|_| failed(String::from("it isn't UTF-8")))
+}
+
+// What a download with the last ETag gave: the body, with its own ETag if
+// the server sent one, or word that what's kept is still current
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Changed(String, Option<String>),
+    NotModified,
+}
+
+// An input URL, checked for what can go in curl's config
+pub fn check_url(url: &str) -> Result<(), String> {
+    let is_http = url.starts_with("https://") || url.starts_with("http://");
+    if !is_http || url.chars().any(|c| c.is_whitespace() || c.is_control() || c == '"' || c == '\\') {
+        return Err(format!("Invalid input URL '{}'", url))
+    }
+    Ok(())
+}
+
+// Where the input downloaded from `url` and its ETag are kept
+fn url_paths(dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+    let hash = input_hash(url);
+    let name = hash.trim_start_matches("fnv1a64:");
+    (dir.join(format!("{}.txt", name)), dir.join(format!("{}.etag", name)))
+}
+
+// curl's config for asking for `url` again, If-None-Match the ETag it last
+// had, with the status and the new ETag written after the body
+fn revalidate_config(url: &str, etag: Option<&str>) -> String {
+    let etag = etag.map(|etag| format!("header = \"If-None-Match: {}\"\n", etag.replace('\\', "\\\\").replace('"', "\\\""))).unwrap_or_default();
+    format!("{}{}write-out = \"\\n%{{http_code}} %header{{etag}}\"\n", curl_config(url, None), etag)
+}
+
+// curl's output for `revalidate_config`: the body, then a line of the status
+// and the ETag
+fn parse_revalidated(output: &str) -> Result<Fetched, String> {
+    let (body, status) = output.rsplit_once('\n').ok_or("curl wrote no status")?;
+    let (code, etag) = status.split_once(' ').unwrap_or((status, ""));
+    let etag = Some(etag.trim()).filter(|etag| !etag.is_empty() && !etag.chars().any(char::is_control));
+    match code {
+        "304" => Ok(Fetched::NotModified),
+        "200" => Ok(Fetched::Changed(body.to_string(), etag.map(str::to_string))),
+        code => Err(format!("unexpected status {}", code)),
+    }
+}
+
+pub fn download_if_changed(url: &str, etag: Option<&str>) -> Result<Fetched, String> {
+    tracing::info!("Downloading {}", url);
+    let failed = |err: String| format!("Could not download {}: {}", url, err);
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| failed(format!("could not run curl: {}", err)))?;
+    if let Some(mut stdin) = curl.stdin.take() {
+        stdin.write_all(revalidate_config(url, etag).as_bytes()).map_err(|err| failed(err.to_string()))?;
+    }
+    let output = curl.wait_with_output().map_err(|err| failed(err.to_string()))?;
+    if !output.status.success() {
+        return Err(failed(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+    let output = String::from_utf8(output.stdout).map_err(|_| failed(String::from("it isn't UTF-8")))?;
+    parse_revalidated(&output).map_err(failed)
+}
+
+// The input at `url`, kept in `dir`: downloaded again only when `fetch`,
+// given the ETag it was kept with, says it's changed, and read as it's kept
+// when the download fails
+fn url_cached_with<F>(dir: &Path, url: &str, fetch: F) -> Result<String, String>
+where
+    F: FnOnce(Option<&str>) -> Result<Fetched, String>
+{
+    let (path, etag_path) = url_paths(dir, url);
+    let kept = path.exists().then(|| AocError::read(&path)).transpose()?;
+    let etag = match &kept {
+        Some(_) if etag_path.exists() => Some(AocError::read(&etag_path)?.trim().to_string()),
+        _ => None,
+    };
+    match (fetch(etag.as_deref()), kept) {
+        (Ok(Fetched::NotModified), Some(kept)) => {
+            tracing::debug!("{} hasn't changed", url);
+            Ok(kept)
+        }
+        (Ok(Fetched::NotModified), None) => Err(format!("Could not download {}: nothing's kept for it, but it wasn't sent", url)),
+        (Ok(Fetched::Changed(input, new_etag)), _) => {
+            save(&path, &input)?;
+            match new_etag {
+                Some(new_etag) => save(&etag_path, &new_etag)?,
+                None if etag_path.exists() => fs::remove_file(&etag_path).map_err(|err| format!("Could not remove {}: {}", etag_path.display(), err))?,
+                None => (),
+            }
+            tracing::info!("Saved {}", path.display());
+            Ok(input)
+        }
+        (Err(err), Some(kept)) => {
+            tracing::warn!("{}; reading the input kept from before", err);
+            Ok(kept)
+        }
+        (Err(err), None) => Err(err),
+    }
+}
+
+pub fn url_cached(url: &str) -> Result<String, String> {
+    check_url(url)?;
+    match aoc_common::dirs::cache_dir() {
+        Some(dir) => url_cached_with(&dir.join("urls"), url, |etag| download_if_changed(url, etag)),
+        None => match download_if_changed(url, None)? {
+            Fetched::Changed(input, _) => Ok(input),
+            Fetched::NotModified => Err(format!("Could not download {}: it wasn't sent", url)),
+        },
+    }
+}
+
+// What's saved at `path`, or what `fetch` gives, which is saved there for
+// next time
+pub fn cached<F>(path: &Path, fetch: F) -> Result<String, String>
+where
+    F: FnOnce() -> Result<String, String>
+{
+    if path.exists() {
+        return Ok(AocError::read(path)?)
+    }
+    let contents = fetch()?;
+    save(path, &contents)?;
+    tracing::info!("Saved {}", path.display());
+    Ok(contents)
+}
+
+// Writes the file, and the directories it's in if they're not there yet
+pub fn save(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+    }
+    fs::write(path, contents).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn test_session_from() {
+        assert_eq!(session_from(Some(String::from("53616c74\n")), None), Ok(Some(String::from("53616c74"))));
+        assert_eq!(session_from(Some(String::from("session=53616c74")), None), Ok(Some(String::from("53616c74"))));
+        assert_eq!(session_from(None, None), Ok(None));
+        assert_eq!(
+            session_from(Some(String::from("53616c74\"\nurl = \"elsewhere")), None),
+            Err(String::from("The session in AOC_SESSION isn't a session token")),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+        assert_eq!(session_from(None, Some(&path)), Ok(None));
+        fs::write(&path, "session=abc123\n").unwrap();
+        assert_eq!(session_from(None, Some(&path)), Ok(Some(String::from("abc123"))));
+        // the variable comes first
+        assert_eq!(session_from(Some(String::from("def456")), Some(&path)), Ok(Some(String::from("def456"))));
+    }
+
+    #[test]
+    fn test_curl_config() {
+        assert_eq!(
+            curl_config(&input_url(2023, 5), Some("abc123")),
+            "url = \"https://adventofcode.com/2023/day/5/input\"
+header = \"Cookie: session=abc123\"
+user-agent = \"github.com/Goos/advent-of-code aoc runner\"
+",
+        );
+        // the puzzle's out to anyone, just without part two
+        assert_eq!(
+            curl_config(&puzzle_url(2023, 5), None),
+            "url = \"https://adventofcode.com/2023/day/5\"\nuser-agent = \"github.com/Goos/advent-of-code aoc runner\"\n",
+        );
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day-5").join("input.txt");
+        let fetches = Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok(String::from("seeds: 79 14 55 13\n"))
+        };
+        assert_eq!(cached(&path, fetch), Ok(String::from("seeds: 79 14 55 13\n")));
+        // saved, so the second run reads it instead
+        assert_eq!(cached(&path, fetch), Ok(String::from("seeds: 79 14 55 13\n")));
+        assert_eq!(fetches.get(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "seeds: 79 14 55 13\n");
+
+        // nothing's saved when the download fails
+        let missing = dir.path().join("day-6").join("input.txt");
+        assert_eq!(cached(&missing, || Err(String::from("offline"))), Err(String::from("offline")));
+        assert!(!missing.exists());
+    }
+
+    #[test]
+    fn test_check_url() {
+        assert_eq!(check_url("https://example.com/team/day-5.txt"), Ok(()));
+        assert_eq!(check_url("file:///etc/passwd"), Err(String::from("Invalid input URL 'file:///etc/passwd'")));
+        assert!(check_url("https://example.com/\"\nurl = \"elsewhere").is_err());
+        assert!(check_url("https://example.com/a b").is_err());
+    }
+
+    #[test]
+    fn test_revalidate_config() {
+        assert_eq!(
+            revalidate_config("https://example.com/5.txt", Some("W/\"5e1\"")),
+            "url = \"https://example.com/5.txt\"
+user-agent = \"github.com/Goos/advent-of-code aoc runner\"
+header = \"If-None-Match: W/\\\"5e1\\\"\"
+write-out = \"\\n%{http_code} %header{etag}\"
+",
+        );
+        assert!(!revalidate_config("https://example.com/5.txt", None).contains("If-None-Match"));
+    }
+
+    #[test]
+    fn test_parse_revalidated() {
+        assert_eq!(
+            parse_revalidated("seeds: 79 14\n\n200 \"5e1\""),
+            Ok(Fetched::Changed(String::from("seeds: 79 14\n"), Some(String::from("\"5e1\"")))),
+        );
+        assert_eq!(parse_revalidated("seeds: 79 14\n200 "), Ok(Fetched::Changed(String::from("seeds: 79 14"), None)));
+        assert_eq!(parse_revalidated("\n304 \"5e1\""), Ok(Fetched::NotModified));
+        assert_eq!(parse_revalidated("moved\n301 "), Err(String::from("unexpected status 301")));
+    }
+
+    #[test]
+    fn test_url_cached_with() {
+        let scratch = tempfile::tempdir().unwrap();
+        let dir = scratch.path().join("urls");
+        let url = "https://example.com/team/day-5.txt";
+        let asked = RefCell::new(vec![]);
+        let fetch = |reply: Result<Fetched, String>| {
+            let asked = &asked;
+            move |etag: Option<&str>| {
+                asked.borrow_mut().push(etag.map(str::to_string));
+                reply
+            }
+        };
+        let changed = |input: &str, etag: Option<&str>| Ok(Fetched::Changed(input.to_string(), etag.map(str::to_string)));
+        // nothing kept, and the download fails
+        assert_eq!(url_cached_with(&dir, url, fetch(Err(String::from("offline")))), Err(String::from("offline")));
+        assert_eq!(url_cached_with(&dir, url, fetch(changed("seeds: 79\n", Some("\"a\"")))), Ok(String::from("seeds: 79\n")));
+        // unchanged, so the kept input is read, and it's kept when offline
+        assert_eq!(url_cached_with(&dir, url, fetch(Ok(Fetched::NotModified))), Ok(String::from("seeds: 79\n")));
+        assert_eq!(url_cached_with(&dir, url, fetch(Err(String::from("offline")))), Ok(String::from("seeds: 79\n")));
+        // changed, without an ETag this time, so the next ask goes without one
+        assert_eq!(url_cached_with(&dir, url, fetch(changed("seeds: 14\n", None))), Ok(String::from("seeds: 14\n")));
+        assert_eq!(url_cached_with(&dir, url, fetch(Ok(Fetched::NotModified))), Ok(String::from("seeds: 14\n")));
+        let etag = Some(String::from("\"a\""));
+        assert_eq!(*asked.borrow(), vec![None, None, etag.clone(), etag.clone(), etag, None]);
+        // another URL's kept apart
+        let other = "https://example.com/team/day-6.txt";
+        assert!(url_cached_with(&dir, other, fetch(Ok(Fetched::NotModified))).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::output::{input_hash, timed};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("aoc");
+}
+
+use proto::solver_server::{Solver, SolverServer};
+use proto::{ListSolutionsRequest, ListSolutionsResponse, Solution, SolveRequest, SolveResponse, TraceEvent};
+
+// The gRPC counterpart of `aoc serve`, answering from the same solutions
+#[derive(Debug, Clone)]
+pub struct GrpcService {
+    solve_timeout: Duration,
+}
+
+fn solutions() -> ListSolutionsResponse {
+    ListSolutionsResponse {
+        solutions: aoc_solutions::solutions().iter()
+            .map(|(year, day, parts)| Solution { year: *year, day: *day, parts: parts.to_vec() })
+            .collect(),
+    }
+}
+
+// Solves on the blocking pool, giving up after the timeout. Like the HTTP
+// server, an abandoned solution still runs to completion in the background.
+async fn solve_blocking(request: &SolveRequest, timeout: Duration) -> Result<(String, Duration), Status> {
+    let (year, day, part) = (request.year, request.day, request.part);
+    let solved = aoc_solutions::solutions().iter()
+        .any(|(y, d, parts)| *y == year && *d == day && parts.contains(&part));
+    if !solved {
+        return Err(Status::not_found(format!("No solution for {} day {} part {}", year, day, part)))
+    }
+    let input = request.input.clone();
+    let task = tokio::task::spawn_blocking(move || timed(|| aoc_solutions::solve(year, day, part, &input)));
+    match tokio::time::timeout(timeout, task).await {
+        Ok(Ok((Ok(answer), duration))) => Ok((answer, duration)),
+        Ok(Ok((Err(err), _))) => Err(Status::invalid_argument(err)),
+        Ok(Err(_)) => Err(Status::internal("Solution failed")),
+        Err(_) => Err(Status::deadline_exceeded("Solving took too long")),
+    }
+}
+
+#[tonic::async_trait]
+impl Solver for GrpcService {
+    async fn solve(&self, request: Request<SolveRequest>) -> Result<Response<SolveResponse>, Status> {
+        let request = request.into_inner();
+        let (answer, duration) = solve_blocking(&request, self.solve_timeout).await?;
+        Ok(Response::new(SolveResponse {
+            year: request.year,
+            day: request.day,
+            part: request.part,
+            answer,
+            duration_ns: duration.as_nanos() as u64,
+            algorithm: aoc_solutions::algorithm(request.year, request.day, request.part)
+                .unwrap_or("default")
+                .to_string(),
+            input_hash: input_hash(&request.input),
+        }))
+    }
+
+    async fn list_solutions(
+        &self,
+        _request: Request<ListSolutionsRequest>
+    ) -> Result<Response<ListSolutionsResponse>, Status> {
+        Ok(Response::new(solutions()))
+    }
+
+    type TraceStream = ReceiverStream<Result<TraceEvent, Status>>;
+
+    async fn trace(&self, request: Request<SolveRequest>) -> Result<Response<Self::TraceStream>, Status> {
+        let request = request.into_inner();
+        let timeout = self.solve_timeout;
+        let (sender, receiver) = mpsc::channel(4);
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let event = |phase: &str, message: String| TraceEvent {
+                phase: phase.to_string(),
+                message,
+                elapsed_ns: started.elapsed().as_nanos() as u64,
+            };
+            let received = format!("{} bytes, {}", request.input.len(), input_hash(&request.input));
+            let _ = sender.send(Ok(event("received", received))).await;
+            let solving = format!("{} day {} part {}", request.year, request.day, request.part);
+            let _ = sender.send(Ok(event("solving", solving))).await;
+            let result = solve_blocking(&request, timeout)
+                .await
+                .map(|(answer, duration)| event("solved", format!("{} in {:?}", answer, duration)));
+            let _ = sender.send(result).await;
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+pub fn serve(port: u16, solve_timeout: Duration) -> Result<(), String> {
+    let runtime = tokio::runtime::Runtime::new().map_err(|err| format!("Could not start runtime: {}", err))?;
+    let address = ([0, 0, 0, 0], port).into();
+    tracing::info!("Serving gRPC on 0.0.0.0:{}", port);
+    runtime.block_on(
+        Server::builder()
+            .timeout(solve_timeout + Duration::from_secs(1))
+            .add_service(SolverServer::new(GrpcService { solve_timeout }))
+            .serve(address)
+    ).map_err(|err| format!("gRPC server failed: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions() {
+        let list = solutions();
+        assert_eq!(list.solutions.len(), aoc_solutions::solutions().len());
+        assert_eq!(list.solutions[0], Solution { year: 2023, day: 1, parts: vec![2] });
+    }
+
+    #[test]
+    fn test_solve() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let service = GrpcService { solve_timeout: Duration::from_secs(5) };
+        let request = SolveRequest { year: 2023, day: 1, part: 2, input: String::from("two1nine\n") };
+        let response = runtime.block_on(service.solve(Request::new(request.clone()))).unwrap().into_inner();
+        assert_eq!(response.answer, "29");
+        assert_eq!(response.algorithm, "trie");
+
+        let missing = SolveRequest { part: 1, ..request };
+        let status = runtime.block_on(service.solve(Request::new(missing))).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+}
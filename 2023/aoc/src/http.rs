@@ -0,0 +1,225 @@
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+// Just enough HTTP/1.1 for the service modes: one request per connection,
+// bodies only by Content-Length, and hard limits on size and time so a slow
+// or oversized request can't tie up a thread forever.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+    pub max_header_bytes: usize,
+    pub max_body_bytes: usize,
+    // for reading the whole request, not each individual read
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits { max_header_bytes: 8 * 1024, max_body_bytes: 1024 * 1024, timeout: Duration::from_secs(10) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    // Header names are case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: String) -> Response {
+        Response { status, content_type, body }
+    }
+
+    pub fn text(status: u16, body: &str) -> Response {
+        Response::new(status, "text/plain; charset=utf-8", format!("{}\n", body))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            503 => "Service Unavailable",
+            504 => "Gateway Timeout",
+            _ => "Unknown",
+        }
+    }
+}
+
+// Reads one request, or gives the response to send back when it's unusable
+pub fn read_request<S: Read>(stream: S, limits: &Limits, set_timeout: impl Fn(Duration)) -> Result<Request, Response> {
+    let deadline = Instant::now() + limits.timeout;
+    // each read waits at most until the overall deadline
+    let remaining = || -> Result<Duration, Response> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(Response::text(408, "Request took too long"))
+        }
+        set_timeout(remaining);
+        Ok(remaining)
+    };
+    let io_error = |err: std::io::Error| -> Response {
+        match err.kind() {
+            ErrorKind::WouldBlock | ErrorKind::TimedOut => Response::text(408, "Request took too long"),
+            _ => Response::text(400, "Could not read request"),
+        }
+    };
+
+    let mut reader = BufReader::new(stream);
+    let mut header_bytes = 0;
+    let mut lines = vec![];
+    loop {
+        remaining()?;
+        let mut line = String::new();
+        // reading through take() means one endless line can't exhaust memory
+        let read = (&mut reader)
+            .take((limits.max_header_bytes - header_bytes + 1) as u64)
+            .read_line(&mut line)
+            .map_err(io_error)?;
+        header_bytes += read;
+        if header_bytes > limits.max_header_bytes {
+            return Err(Response::text(431, "Request headers too large"))
+        }
+        if read == 0 {
+            return Err(Response::text(400, "Incomplete request"))
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break
+        }
+        lines.push(line);
+    }
+
+    let request_line = lines.first().ok_or(Response::text(400, "Empty request"))?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(Response::text(400, "Malformed request line"))
+    };
+    let headers: Vec<(String, String)> = lines[1..].iter()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let mut request = Request { method: method.to_string(), path: path.to_string(), headers, body: vec![] };
+
+    let length: usize = match request.header("Content-Length") {
+        Some(length) => length.parse().map_err(|_| Response::text(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > limits.max_body_bytes {
+        return Err(Response::text(413, &format!("Body is limited to {} bytes", limits.max_body_bytes)))
+    }
+    request.body = vec![0; length];
+    let mut filled = 0;
+    while filled < length {
+        remaining()?;
+        let read = reader.read(&mut request.body[filled..]).map_err(io_error)?;
+        if read == 0 {
+            return Err(Response::text(400, "Body shorter than Content-Length"))
+        }
+        filled += read;
+    }
+    Ok(request)
+}
+
+pub fn write_response<W: Write>(mut stream: W, response: &Response) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len(),
+        response.body,
+    )?;
+    stream.flush()
+}
+
+// Reads a request from a connection, handles it and answers, all within
+// the limits
+pub fn handle_connection<F>(stream: TcpStream, limits: &Limits, handler: F)
+where
+    F: FnOnce(Request) -> Response
+{
+    let reader = match stream.try_clone() {
+        Ok(reader) => reader,
+        Err(_) => return,
+    };
+    let timeout_stream = &stream;
+    let response = match read_request(reader, limits, |timeout| {
+        let _ = timeout_stream.set_read_timeout(Some(timeout));
+    }) {
+        Ok(request) => handler(request),
+        Err(response) => response,
+    };
+    let _ = stream.set_write_timeout(Some(limits.timeout));
+    let _ = write_response(&stream, &response);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(raw: &str, limits: &Limits) -> Result<Request, Response> {
+        read_request(raw.as_bytes(), limits, |_| {})
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /solve/2023/2/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 5\r\n\r\nhello";
+        let request = read(raw, &Limits::default()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/solve/2023/2/1");
+        assert_eq!(request.header("Content-Length"), Some("5"));
+        assert_eq!(request.body, b"hello");
+
+        let request = read("GET /metrics HTTP/1.1\r\n\r\n", &Limits::default()).unwrap();
+        assert!(request.body.is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        let limits = Limits { max_header_bytes: 64, max_body_bytes: 4, timeout: Duration::from_secs(1) };
+        let status = |raw: &str| read(raw, &limits).map(|_| 200).unwrap_or_else(|response| response.status);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello"), 413);
+        assert_eq!(status(&format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(100))), 431);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nab"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: x\r\n\r\n"), 400);
+        assert_eq!(status("GET / HTTP/1.1\r\n"), 400);
+        assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\nabcd"), 200);
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out = vec![];
+        write_response(&mut out, &Response::text(404, "Not found")).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: 10\r\nConnection: close\r\n\r\nNot found\n",
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// `aoc input stats`: the shape of an input, to check what a parser assumes
+// about it before running a solver on it. Sections are split by blank lines,
+// numbers are runs of digits with a minus sign straight before them counted
+// in, and a section of two or more lines all the same width is taken for a
+// grid.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub lines: usize,
+    // (width, height), when the section's lines make a grid
+    pub grid: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub bytes: usize,
+    pub lines: usize,
+    pub sections: Vec<Section>,
+    pub numbers: usize,
+    // None when there are no numbers, or one's too long to compare
+    pub range: Option<(i128, i128)>,
+    // the shortest and the longest line, leaving out blank ones
+    pub widths: (usize, usize),
+    // every character but whitespace, in order
+    pub chars: String,
+}
+
+fn section(lines: &[&str]) -> Section {
+    let width = lines[0].chars().count();
+    let is_grid = lines.len() > 1 && width > 1 && lines.iter().all(|line| line.chars().count() == width);
+    Section { lines: lines.len(), grid: is_grid.then_some((width, lines.len())) }
+}
+
+// The numbers in a line, each as it's written
+fn numbers(line: &str) -> Vec<&str> {
+    let mut numbers = vec![];
+    let bytes = line.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if !bytes[idx].is_ascii_digit() {
+            idx += 1;
+            continue
+        }
+        let mut start = idx;
+        // a minus sign makes it negative, unless it joins two words like "a-1"
+        if start > 0 && bytes[start - 1] == b'-' && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric()) {
+            start -= 1;
+        }
+        while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        numbers.push(&line[start..idx]);
+    }
+    numbers
+}
+
+pub fn stats(input: &str) -> Stats {
+    let lines: Vec<&str> = input.lines().collect();
+    let sections: Vec<Section> = lines.split(|line| line.trim().is_empty())
+        .filter(|section| !section.is_empty())
+        .map(section)
+        .collect();
+    let numbers: Vec<&str> = lines.iter().flat_map(|line| numbers(line)).collect();
+    let values: Option<Vec<i128>> = numbers.iter().map(|number| number.parse().ok()).collect();
+    let range = values.and_then(|values| Some((*values.iter().min()?, *values.iter().max()?)));
+    let widths = lines.iter().filter(|line| !line.trim().is_empty()).map(|line| line.chars().count());
+    let mut chars: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.sort();
+    chars.dedup();
+    Stats {
+        bytes: input.len(),
+        lines: lines.len(),
+        sections,
+        numbers: numbers.len(),
+        range,
+        widths: (widths.clone().min().unwrap_or(0), widths.max().unwrap_or(0)),
+        chars: chars.into_iter().collect(),
+    }
+}
+
+pub fn report(stats: &Stats) -> String {
+    let mut out = format!("bytes     {}\nlines     {}\n", stats.bytes, stats.lines);
+    let sizes: Vec<String> = stats.sections.iter().map(|section| section.lines.to_string()).collect();
+    match stats.sections.len() {
+        0 | 1 => out.push_str(&format!("sections  {}\n", stats.sections.len())),
+        count => out.push_str(&format!("sections  {}, of {} lines\n", count, sizes.join(", "))),
+    }
+    out.push_str(&format!("widths    {} to {}\n", stats.widths.0, stats.widths.1));
+    match stats.range {
+        Some((min, max)) => out.push_str(&format!("numbers   {}, from {} to {}\n", stats.numbers, min, max)),
+        None => out.push_str(&format!("numbers   {}\n", stats.numbers)),
+    }
+    for (idx, section) in stats.sections.iter().enumerate() {
+        let Some((width, height)) = section.grid else { continue };
+        match stats.sections.len() {
+            1 => out.push_str(&format!("grid      {} x {}\n", width, height)),
+            _ => out.push_str(&format!("grid      {} x {} in section {}\n", width, height, idx + 1)),
+        }
+    }
+    out.push_str(&format!("chars     {}\n", stats.chars));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixtures;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers("Card 1: 41 48 | 83 -86"), vec!["1", "41", "48", "83", "-86"]);
+        assert_eq!(numbers("seed-to-soil map: x-1 -2"), vec!["1", "-2"]);
+        assert_eq!(numbers("-7"), vec!["-7"]);
+        assert!(numbers("no numbers").is_empty());
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&fixtures::example(3));
+        assert_eq!(stats.lines, 10);
+        assert_eq!(stats.sections, vec![Section { lines: 10, grid: Some((10, 10)) }]);
+        assert_eq!((stats.numbers, stats.range), (10, Some((35, 755))));
+        assert_eq!(stats.chars, "#$*+.123456789");
+
+        let stats = super::stats(&fixtures::example(5));
+        assert_eq!(stats.sections.len(), 8);
+        assert_eq!(stats.sections[0], Section { lines: 1, grid: None });
+        assert_eq!(stats.range, Some((0, 98)));
+
+        let stats = super::stats("");
+        assert_eq!((stats.lines, stats.sections.len(), stats.range, stats.widths), (0, 0, None, (0, 0)));
+        assert_eq!(super::stats("1 99999999999999999999999999999999999999999").range, None);
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(&stats("#.#\n.#.\n\nseeds: 79 -14\n\n##\n##\n")),
+            "bytes     30
+lines     7
+sections  3, of 2, 1, 2 lines
+widths    2 to 13
+numbers   2, from -14 to 79
+grid      3 x 2 in section 1
+grid      2 x 2 in section 3
+chars     #-.1479:des
+",
+        );
+        assert_eq!(
+            report(&stats("#.\n.#\n")),
+            "bytes     6\nlines     2\nsections  1\nwidths    2 to 2\nnumbers   0\ngrid      2 x 2\nchars     #.\n",
+        );
+    }
+}
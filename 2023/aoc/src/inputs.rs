@@ -0,0 +1,108 @@
+// `aoc run` with --input given more than once: the day run on each of the
+// inputs, say your own and a friend's, with the answers in a table of a row
+// per input and a column per part, so a solution that only works on some
+// inputs shows which. An input that can't be read, or a part that fails, has
+// its error in the table rather than stopping the rest.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputRun {
+    pub input: String,
+    // the error reading the input when it couldn't be, else each part's answer
+    pub answers: Result<Vec<Result<String, String>>, String>,
+}
+
+impl InputRun {
+    pub fn failed(&self) -> bool {
+        self.answers.as_ref().map_or(true, |answers| answers.iter().any(Result::is_err))
+    }
+}
+
+// Reads each input with `read_input` and solves each of the parts on it
+pub fn collect<R, S>(inputs: &[&str], parts: &[u32], read_input: R, solve: S) -> Vec<InputRun>
+where
+    R: Fn(&str) -> Result<String, String>,
+    S: Fn(u32, &str) -> Result<String, String>,
+{
+    inputs.iter()
+        .map(|name| {
+            tracing::info!("Running on {}", name);
+            let answers = read_input(name).map(|input| parts.iter().map(|part| solve(*part, &input)).collect());
+            InputRun { input: name.to_string(), answers }
+        })
+        .collect()
+}
+
+// The table, its columns as wide as their widest cell. A row whose input
+// couldn't be read has the error across the parts' columns.
+pub fn table(parts: &[u32], runs: &[InputRun]) -> String {
+    let mut rows: Vec<Vec<String>> = vec![];
+    rows.push(std::iter::once(String::from("input")).chain(parts.iter().map(|part| format!("part {}", part))).collect());
+    for run in runs {
+        let cells = match &run.answers {
+            Ok(answers) => answers.iter()
+                .map(|answer| match answer {
+                    Ok(answer) => answer.clone(),
+                    Err(err) => format!("error: {}", err),
+                })
+                .collect(),
+            Err(err) => vec![format!("error: {}", err)],
+        };
+        rows.push(std::iter::once(run.input.clone()).chain(cells).collect());
+    }
+    let mut widths = vec![0; parts.len() + 1];
+    for row in &rows {
+        // the error of an input that couldn't be read doesn't widen a column
+        if row.len() == widths.len() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+    }
+    widths[0] = rows.iter().map(|row| row[0].chars().count()).max().unwrap_or(0);
+    let mut out = String::new();
+    for row in rows {
+        let line: Vec<String> = row.iter().zip(&widths).map(|(cell, width)| format!("{:<width$}", cell, width = width)).collect();
+        out.push_str(line.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixtures;
+
+    #[test]
+    fn test_collect() {
+        let read = |name: &str| match name {
+            "mine.txt" => Ok(fixtures::example(5)),
+            "broken.txt" => Ok(String::from("seeds: x\n")),
+            _ => Err(format!("Could not read {}", name)),
+        };
+        let solve = |part, input: &str| aoc_solutions::solve(2023, 5, part, input);
+        let runs = collect(&["mine.txt", "broken.txt", "missing.txt"], &[1, 2], read, solve);
+        assert_eq!(runs[0], InputRun { input: String::from("mine.txt"), answers: Ok(vec![Ok(String::from("35")), Ok(String::from("46"))]) });
+        assert!(runs[1].answers.as_ref().unwrap().iter().all(Result::is_err));
+        assert_eq!(runs[2].answers, Err(String::from("Could not read missing.txt")));
+        let failed: Vec<bool> = runs.iter().map(InputRun::failed).collect();
+        assert_eq!(failed, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_table() {
+        let runs = vec![
+            InputRun { input: String::from("mine.txt"), answers: Ok(vec![Ok(String::from("35")), Ok(String::from("46"))]) },
+            InputRun { input: String::from("friends.txt"), answers: Ok(vec![Ok(String::from("226172555")), Err(String::from("No seeds line"))]) },
+            InputRun { input: String::from("b.txt"), answers: Err(String::from("Could not read b.txt: No such file or directory")) },
+        ];
+        assert_eq!(
+            table(&[1, 2], &runs),
+            "input        part 1     part 2
+mine.txt     35         46
+friends.txt  226172555  error: No seeds line
+b.txt        error: Could not read b.txt: No such file or directory
+",
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// Diagnostics through tracing, written to stderr so piping a run only ever
+// captures answers. The level comes from `--log-level <level>`, `-v` for debug
+// or `-vv` for trace, AOC_LOG, then RUST_LOG, and is info otherwise, as the
+// runner's own progress is worth seeing. Any of the last three can also be
+// per-target directives, as in `aoc=debug,day_5=trace`; `--log-format json` or
+// AOC_LOG_FORMAT=json writes one JSON object per line instead. At debug and
+// past, each span also logs how long it took when it closes.
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::sync::{Arc, Mutex};
+
+pub use aoc_common::logging::is_verbose_flag;
+use aoc_common::logging::verbosity;
+use tracing::field::Field;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+
+// The value of `--name` in the arguments, or else of the environment variable
+fn setting(args: &[String], name: &str, variable: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(idx) => Ok(Some(args.get(idx + 1).ok_or(format!("{} needs a value", name))?.clone())),
+        None => Ok(std::env::var(variable).ok().filter(|value| !value.is_empty())),
+    }
+}
+
+// A level, or RUST_LOG style directives such as `aoc=debug,day_5=trace`
+fn parse_filter(value: &str) -> Result<EnvFilter, String> {
+    EnvFilter::builder()
+        .parse(value)
+        .map_err(|err| format!("Invalid log level '{}': {}", value, err))
+}
+
+fn filter(args: &[String]) -> Result<EnvFilter, String> {
+    if let Some(level) = verbosity(args).filter(|_| !args.iter().any(|arg| arg == "--log-level")) {
+        return Ok(EnvFilter::default().add_directive(level.into()))
+    }
+    if let Some(level) = setting(args, "--log-level", "AOC_LOG")? {
+        return parse_filter(&level)
+    }
+    Ok(EnvFilter::builder().with_default_directive(LevelFilter::INFO.into()).from_env_lossy())
+}
+
+fn json(args: &[String]) -> Result<bool, String> {
+    match setting(args, "--log-format", "AOC_LOG_FORMAT")?.as_deref() {
+        None | Some("text") => Ok(false),
+        Some("json") => Ok(true),
+        Some(format) => Err(format!("Unknown log format '{}', expected text or json", format)),
+    }
+}
+
+// Installs the subscriber for the rest of the run
+pub fn init(args: &[String]) -> Result<(), String> {
+    let filter = filter(args)?;
+    // timing spans costs a clock read each, so only when it'll be shown
+    let spans = match filter.max_level_hint() {
+        Some(level) if level >= LevelFilter::DEBUG => FmtSpan::CLOSE,
+        _ => FmtSpan::NONE,
+    };
+    let layer = if json(args)? {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_span_events(spans)
+            .with_writer(io::stderr)
+            .boxed()
+    } else {
+        tracing_subscriber::fmt::layer()
+            // no timestamps, which `without_time` would also take off span timings
+            .with_timer(())
+            .with_span_events(spans)
+            .with_ansi(io::stderr().is_terminal())
+            .with_writer(io::stderr)
+            .boxed()
+    };
+    tracing_subscriber::registry()
+        .with(layer.with_filter(filter))
+        .try_init()
+        .map_err(|err| err.to_string())
+}
+
+// Keeps each event as `target: message`, whatever its level
+struct Collect(Arc<Mutex<Vec<String>>>);
+
+impl<S: Subscriber> Layer<S> for Collect {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut |field: &Field, value: &dyn fmt::Debug| {
+            if field.name() == "message" {
+                message = format!("{:?}", value);
+            }
+        });
+        self.0.lock().unwrap().push(format!("{}: {}", event.metadata().target(), message));
+    }
+}
+
+// Runs `f` with the events it logs on this thread collected instead of
+// written, at every level, so two runs can be compared step by step
+pub fn capture<R, F: FnOnce() -> R>(f: F) -> (R, Vec<String>) {
+    let messages = Arc::new(Mutex::new(vec![]));
+    let result = tracing::subscriber::with_default(tracing_subscriber::registry().with(Collect(messages.clone())), f);
+    let messages = std::mem::take(&mut *messages.lock().unwrap());
+    (result, messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_filter() {
+        let filter = |list: &[&str]| super::filter(&args(list)).map(|filter| filter.to_string());
+        assert_eq!(filter(&["--log-level", "warn"]), Ok(String::from("warn")));
+        assert_eq!(filter(&["-v"]), Ok(String::from("debug")));
+        // --log-level wins over -v
+        assert_eq!(filter(&["-vv", "--log-level", "error"]), Ok(String::from("error")));
+        assert_eq!(filter(&["--log-level", "off"]), Ok(String::from("off")));
+        assert_eq!(filter(&["--log-level", "aoc=debug,day_5=trace"]), Ok(String::from("day_5=trace,aoc=debug")));
+        assert!(filter(&["--log-level", "aoc=loud"]).is_err());
+        assert!(filter(&["--log-level"]).is_err());
+    }
+
+    #[test]
+    fn test_capture() {
+        let (answer, messages) = capture(|| {
+            let _span = tracing::info_span!("solve").entered();
+            tracing::trace!("step {}", 1);
+            tracing::warn!("step {}", 2);
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(messages, vec!["aoc::logging::tests: step 1", "aoc::logging::tests: step 2"]);
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(json(&args(&["--log-format", "json"])), Ok(true));
+        assert_eq!(json(&args(&["--log-format", "text"])), Ok(false));
+        assert!(json(&args(&["--log-format", "xml"])).is_err());
+    }
+}
@@ -0,0 +1,1004 @@
+mod answers;
+mod batch;
+mod bench;
+mod budget;
+mod cache;
+mod clipboard;
+mod counters;
+mod csv;
+#[cfg(any(feature = "tui", test))]
+mod dashboard;
+mod diff;
+mod fetch;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod http;
+mod input_stats;
+mod inputs;
+mod logging;
+mod metrics;
+#[cfg(feature = "plugins")]
+mod plugins;
+mod puzzle;
+mod report;
+mod run_all;
+mod serve;
+mod status;
+mod today;
+#[cfg(feature = "tui")]
+mod tui;
+#[cfg(feature = "vault")]
+mod vault;
+mod verify;
+
+use aoc_common::compress;
+use aoc_common::error::AocError;
+use aoc_common::locale::Locale;
+use aoc_common::mem::{human_bytes, AllocStats};
+use aoc_common::output::{AnswerPrinter, OutputFormat};
+use aoc_common::strings::normalize_input;
+use logging::is_verbose_flag;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: aoc <command> [<flags>]
+
+aoc run [<day> | --day <n>]
+    Solves a day, today's during December (US Eastern time) when none is given
+    --year <n>              the puzzle's year
+    --part <n>              only this part
+    --input <file|->        the input, .gz or .zst too; given again, a table of inputs
+    --example               the day's bundled example, checked against its answers
+    --input-url <url>       an input downloaded into the cache, and again once it changes
+    --clipboard             the clipboard's text (with the clipboard feature)
+    --time                  how long parsing and each part took
+    --csv <file>            appends the timings to a CSV file
+    --output text|json      how the answers are printed
+    --locale <locale>       digits and messages in another language, like de, or a file
+    --dot <file|->          writes the input as a Graphviz graph instead (graph days)
+    --record <events-file>  writes the solver's events, for aoc replay
+    --mem                   what parsing and each part allocated, and peak memory
+    --plugins <dir>         days from the plugins in <dir> (with the plugins feature)
+
+aoc replay <events-file>
+    Goes through the events aoc run --record wrote
+    --kind <kind>           only events of this kind
+    --summary               how many events of each kind there were
+
+aoc all
+    Solves every part of every day on its input, reusing cached answers
+    --tag <tag>             only the days with this tag
+    --no-cache              solves everything again
+    --locale <locale>       as for aoc run
+
+aoc run-all
+    Runs every day once, in parallel and uncached, timing parsing and each part
+    --year <n>              the year
+
+aoc info <year> <day>
+    How a day is solved: its approach, each part's algorithm and complexity, its tags
+
+aoc input stats [<file|->]
+    An input's lines, sections, numbers and grids, to check a parser against
+    --day <n>               the day's input instead of a file
+    --year <n>              the day's year
+
+aoc puzzle [<day> | --day <n>]
+    Prints the day's puzzle as markdown, downloaded the first time
+    --year <n>              the puzzle's year
+    --refresh               downloads it again, say for part two
+
+aoc batch <manifest.toml>
+    Runs each part in the manifest and checks its answer
+
+aoc bench
+    Times every day's parts on their inputs
+    --day <n>               only this day
+    --runs <n>              how many runs to time (10)
+    --csv <file>            appends the timings to a CSV file
+    --output text|json      how the timings are printed
+    --counters              hardware counters too (with the perf feature, on Linux)
+    --criterion             runs the Criterion benches instead, with cargo
+
+aoc report
+    Times every day's parts and writes them up as markdown
+    --day <n>               only this day
+    --runs <n>              how many runs to time (10)
+    --out <file>            where to write it, or stdout
+
+aoc status
+    December's calendar, with how far each part has got
+    --year <n>              the year
+    --answers <file>        the answers ledger (answers.toml)
+    --no-run                doesn't run parts to check them against the ledger
+    --no-cache              solves everything again
+
+aoc diff <year> <day> --algos <a,b> [<input>]
+    Runs two variants of a day on the same input and compares their answers
+    --part <n>              only this part
+
+aoc verify
+    Checks every part against the answers ledger
+    --year <n>              the year
+    --answers <file>        the answers ledger (answers.toml)
+    --no-cache              solves everything again
+    --cross                 checks days against a brute force instead
+    --day <n>               with --cross, only this day
+    --inputs <n>            with --cross, how many inputs to generate (20)
+    --seed <n>              with --cross, the seed to generate them from
+    --budget <file>         checks runs are within their time budgets instead
+    --runs <n>              with --budget, how many runs to time (3)
+
+aoc bench-service
+    Benchmarks the days on an interval and serves the timings to Prometheus
+    --port <n>              the port (9100)
+    --interval <seconds>    how often to benchmark (300)
+    --runs <n>              how many runs to time (10)
+    --days <n,n,...>        only these days
+
+aoc serve
+    Solves inputs posted over HTTP
+    --host <address>        the address to listen on (127.0.0.1)
+    --port <n>              the port (8080)
+    --max-body <bytes>      the largest input accepted (1 MiB)
+    --timeout <seconds>     how long a solve may take (10)
+    --max-concurrent <n>    how many solves may run at once (8)
+    --max-connections <n>   how many connections may be open at once (64)
+
+aoc grpc
+    Solves inputs sent over gRPC (with the grpc feature)
+    --port <n>              the port (50051)
+    --timeout <seconds>     how long a solve may take (10)
+
+aoc plugins
+    Lists the plugins and the days they take over (with the plugins feature)
+    --plugins <dir>         where the plugins are (plugins/)
+
+aoc tui
+    A dashboard of every solved day (with the tui feature)
+    --answers <file>        the answers ledger (answers.toml)
+    --schematic             inspects day 3's schematic instead
+    --input <file>          with --schematic, the input
+
+aoc vault keygen | seal | unseal
+    Keeps inputs encrypted with age, as input.txt.age (with the vault feature)
+    --year <n>              the year to seal or unseal
+    --keep                  keeps the plain inputs when sealing
+
+Every command takes
+    -v, -vv                 debug or trace logs
+    --log-level <filter>    a level, or directives like aoc=debug,day_5=trace
+    --log-format text|json  JSON lines instead of text
+
+Answers go to stdout and everything else to stderr.
+
+Inputs are read from $XDG_CACHE_HOME/aoc/<year>/day-<n>/input.txt (~/.cache/aoc),
+or else day-<n>/input.txt or <year>/day-<n>/input.txt. One that's missing is
+downloaded into the cache, which needs curl and a session cookie. Answers found
+by all, status and verify are cached in answers.tsv until aoc is rebuilt.
+
+Environment:
+    AOC_SESSION             the adventofcode.com session cookie, or ~/.config/aoc/session
+    AOC_LOG, RUST_LOG       the log filter when --log-level isn't given
+    AOC_LOG_FORMAT          the log format when --log-format isn't given
+    AOC_LOCALE              the locale when --locale isn't given
+    AOC_SEED                the seed when --seed isn't given
+    AOC_VAULT_KEY           the vault's key file, or ~/.config/aoc/vault.key";
+
+const DEFAULT_YEAR: u32 = 2023;
+
+#[global_allocator]
+static ALLOCATOR: aoc_common::mem::CountingAllocator = aoc_common::mem::CountingAllocator;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    logging::init(args)?;
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => run_day(&args[1..]),
+        Some("all") => all(&args[1..]),
+        Some("run-all") => run_all(&args[1..]),
+        Some("info") => info(&args[1..]),
+        Some("input") => input(&args[1..]),
+        Some("puzzle") => puzzle(&args[1..]),
+        Some("batch") => batch(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("diff") => diff(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("report") => report(&args[1..]),
+        Some("status") => status(&args[1..]),
+        Some("replay") => replay(&args[1..]),
+        Some("bench-service") => bench_service(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        #[cfg(feature = "grpc")]
+        Some("grpc") => grpc(&args[1..]),
+        #[cfg(feature = "plugins")]
+        Some("plugins") => list_plugins(&args[1..]),
+        #[cfg(feature = "tui")]
+        Some("tui") => tui(&args[1..]),
+        #[cfg(feature = "vault")]
+        Some("vault") => vault(&args[1..]),
+        _ => Err(USAGE.into()),
+    }
+}
+
+// The value following `--name`, if the flag was given
+fn flag_value<'a>(args: &'a [String], name: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|arg| arg == name) {
+        Some(idx) => args.get(idx + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or(format!("{} needs a value", name)),
+        None => Ok(None),
+    }
+}
+
+// Every value given for a flag that can be repeated
+fn flag_values<'a>(args: &'a [String], name: &str) -> Result<Vec<&'a str>, String> {
+    args.iter().enumerate()
+        .filter(|(_, arg)| *arg == name)
+        .map(|(idx, _)| args.get(idx + 1).map(|value| value.as_str()).ok_or(format!("{} needs a value", name)))
+        .collect()
+}
+
+fn parsed_flag(args: &[String], name: &str) -> Result<Option<u32>, String> {
+    flag_value(args, name)?
+        .map(|value| value.parse().map_err(|_| format!("Invalid value for {}: '{}'", name, value)))
+        .transpose()
+}
+
+fn parts_for(year: u32, day: u32) -> Result<&'static [u32], String> {
+    aoc_solutions::solutions().iter()
+        .find(|(y, d, _)| *y == year && *d == day)
+        .map(|(_, _, parts)| *parts)
+        .ok_or(format!("No solution for {} day {}", year, day))
+}
+
+// "-" reads stdin, so an input can be piped in
+fn read_input(path: &Path) -> Result<String, String> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(|err| format!("Could not read stdin: {}", err))?;
+        return Ok(input)
+    }
+    Ok(AocError::read(path)?)
+}
+
+fn default_input(day: u32) -> PathBuf {
+    find_input(DEFAULT_YEAR, day)
+}
+
+fn find_input(year: u32, day: u32) -> PathBuf {
+    find_day_file(year, day, "input.txt")
+}
+
+// Where a day's file, its input or puzzle, is kept: in aoc's cache, under
+// <year>/day-<n>/, or in the tree as it used to be, in day-<n>/ when run from
+// the year's workspace or <year>/day-<n>/ from the repository's root, plain
+// or compressed. The cache's when there's none yet, for a download to go to
+// and the error to name.
+fn find_day_file(year: u32, day: u32, name: &str) -> PathBuf {
+    let cached = aoc_common::dirs::day_dir(year, day).map(|dir| dir.join(name));
+    let in_workspace = PathBuf::from(format!("day-{}", day)).join(name);
+    let in_tree = [in_workspace.clone(), PathBuf::from(year.to_string()).join(&in_workspace)];
+    cached.iter().chain(&in_tree)
+        .flat_map(|path| compress::variants(path))
+        .find(|path| is_kept(path))
+        .or(cached)
+        .unwrap_or(in_workspace)
+}
+
+// Whether the file's there, sealed in the vault or not
+fn is_kept(path: &Path) -> bool {
+    #[cfg(feature = "vault")]
+    if vault::sealed_path(path).exists() {
+        return true
+    }
+    path.exists()
+}
+
+// The day's input from where it's kept, downloaded and saved in the cache
+// first if it isn't yet and there's a session to download it with.
+// With the vault, a sealed input is read and a downloaded one sealed.
+fn day_input(year: u32, day: u32) -> Result<String, String> {
+    let path = find_input(year, day);
+    let download = || match fetch::session()? {
+        Some(session) => fetch::download(&fetch::input_url(year, day), Some(&session)),
+        None => Err(format!("{}; set AOC_SESSION to download it", read_input(&path).unwrap_err())),
+    };
+    #[cfg(feature = "vault")]
+    return vault::cached(&path, download);
+    #[cfg(not(feature = "vault"))]
+    fetch::cached(&path, download)
+}
+
+// Writes to the file, or to stdout for "-"
+fn write_output(path: &str, contents: &str) -> Result<(), Box<dyn Error>> {
+    if path == "-" {
+        print!("{}", contents);
+    } else {
+        fs::write(path, contents).map_err(|err| format!("Could not write {}: {}", path, err))?;
+    }
+    Ok(())
+}
+
+// The year and day the arguments ask for: the day as <day> or --day <n>, or
+// without one today's puzzle during December
+fn puzzle_day(args: &[String]) -> Result<(u32, u32), Box<dyn Error>> {
+    let positional = args.iter().find(|arg| !is_verbose_flag(arg)).filter(|arg| !arg.starts_with("--")).map(|day| day.as_str());
+    let day = match (positional, flag_value(args, "--day")?) {
+        (Some(positional), Some(flag)) if positional != flag => return Err("Give the day once, as <day> or --day <n>".into()),
+        (positional, flag) => positional.or(flag),
+    };
+    let (year, day) = match day {
+        Some(day) => {
+            let day: u32 = day.parse().map_err(|_| format!("Invalid day '{}'", day))?;
+            (parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR), day)
+        }
+        None => {
+            let (year, day) = today::puzzle().ok_or("No day given, and there's no puzzle out today to default to")?;
+            (parsed_flag(args, "--year")?.unwrap_or(year), day)
+        }
+    };
+    Ok((year, day))
+}
+
+fn run_day(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (year, day) = puzzle_day(args)?;
+    let locale = locale(args)?;
+    let (day_parts, solve) = day_solver(args, year, day, locale.clone())?;
+    let parts = match parsed_flag(args, "--part")? {
+        Some(part) => vec![part],
+        None => day_parts,
+    };
+    let inputs = flag_values(args, "--input")?;
+    if inputs.len() > 1 {
+        return run_inputs(args, &inputs, &parts, solve)
+    }
+    let example = args.iter().any(|arg| arg == "--example");
+    let from_clipboard = args.iter().any(|arg| arg == "--clipboard");
+    let url = flag_value(args, "--input-url")?;
+    if from_clipboard && (example || !inputs.is_empty()) {
+        return Err("--clipboard is the input, so it can't be given with --example or --input".into())
+    }
+    if url.is_some() && (example || from_clipboard || !inputs.is_empty()) {
+        return Err("--input-url is the input, so it can't be given with --example, --clipboard or --input".into())
+    }
+    let input = match example {
+        true => aoc_solutions::example(year, day).ok_or(format!("{} day {} has no example", year, day))?.to_string(),
+        false if from_clipboard => clipboard::read()?,
+        false => match (url, flag_value(args, "--input")?) {
+            (Some(url), _) => fetch::url_cached(url)?,
+            (None, Some(path)) => read_input(Path::new(path))?,
+            (None, None) => day_input(year, day)?,
+        },
+    };
+    if let Some(path) = flag_value(args, "--dot")? {
+        let dot = aoc_solutions::to_dot(year, day, &input)?;
+        return write_output(path, &dot)
+    }
+    let format = OutputFormat::from_args(args)?;
+    let show_time = args.iter().any(|arg| arg == "--time");
+
+    if args.iter().any(|arg| arg == "--mem") {
+        return run_mem(args, year, day, &parts, &input, &locale)
+    }
+    let printer = AnswerPrinter::new(format, year, day, &input);
+    let run_parts = || -> Result<Vec<bench::Timing>, Box<dyn Error>> {
+        let mut timings = vec![];
+        for part in parts {
+            aoc_common::event!("part", part = part);
+            let timing = bench::time_with(year, day, part, &input, 1, |input| solve(part, input))?;
+            let algorithm = aoc_solutions::algorithm(year, day, part).unwrap_or("default");
+            let record = printer.record(part, algorithm, &timing.answer, timing.mean());
+            printer.print(&locale.message("part", &[("part", &part.to_string())]), &record);
+            if show_time && format == OutputFormat::Text {
+                eprintln!("  {}", locale.message("took", &[("time", &format!("{:?}", timing.mean()))]));
+            }
+            timings.push(timing);
+        }
+        Ok(timings)
+    };
+    let timings = match flag_value(args, "--record")? {
+        Some(path) => {
+            let (timings, recording) = aoc_common::events::record(run_parts);
+            recording.write(Path::new(path))?;
+            tracing::info!("Recorded {} events to {}", recording.events.len(), path);
+            timings?
+        }
+        None => run_parts()?,
+    };
+    if let Some(path) = flag_value(args, "--csv")? {
+        csv::append(Path::new(path), &timings)?;
+    }
+    if example {
+        check_example(&timings)?;
+    }
+    Ok(())
+}
+
+// The day run on each of several inputs, with their answers in a table
+fn run_inputs(args: &[String], inputs: &[&str], parts: &[u32], solve: DaySolver) -> Result<(), Box<dyn Error>> {
+    if let Some(flag) = ["--example", "--clipboard", "--input-url", "--dot", "--mem", "--record", "--csv"].iter().find(|flag| args.iter().any(|arg| arg == *flag)) {
+        return Err(format!("{} takes a single input", flag).into())
+    }
+    if OutputFormat::from_args(args)? != OutputFormat::Text {
+        return Err("Several inputs are only written as text".into())
+    }
+    let runs = inputs::collect(inputs, parts, |path| read_input(Path::new(path)), solve);
+    print!("{}", inputs::table(parts, &runs));
+    match runs.iter().filter(|run| run.failed()).count() {
+        0 => Ok(()),
+        failed => Err(format!("{} of {} inputs failed", failed, runs.len()).into()),
+    }
+}
+
+// The example's answers against the ones the puzzle gives for it
+fn check_example(timings: &[bench::Timing]) -> Result<(), String> {
+    for timing in timings {
+        match aoc_solutions::example_answer(timing.year, timing.day, timing.part) {
+            Some(expected) if timing.answer != expected => {
+                return Err(format!("Part {} gives {} for the example, not {}", timing.part, timing.answer, expected))
+            }
+            Some(_) => tracing::info!("Part {} gives the example's answer", timing.part),
+            None => tracing::warn!("No answer to check part {} of the example against", timing.part),
+        }
+    }
+    Ok(())
+}
+
+// The locale from --locale or AOC_LOCALE: `en`, the name of one in locales/,
+// or the path of a locale file. English when neither is given.
+fn locale(args: &[String]) -> Result<Locale, Box<dyn Error>> {
+    let name = match flag_value(args, "--locale")? {
+        Some(name) => name.to_string(),
+        None => env::var("AOC_LOCALE").unwrap_or_default(),
+    };
+    if name.is_empty() || name == "en" {
+        return Ok(Locale::english())
+    }
+    let path = Path::new(&name);
+    let path = if path.extension().is_some() { path.to_path_buf() } else { Path::new("locales").join(format!("{}.toml", name)) };
+    Ok(Locale::load(&path)?)
+}
+
+type DaySolver = Box<dyn Fn(u32, &str) -> Result<String, String>>;
+
+// The day's parts and what solves them: a plugin for the day from --plugins
+// <dir> (plugins/ by default) when there is one, else the linked in solution
+#[cfg_attr(not(feature = "plugins"), allow(unused_variables))]
+fn day_solver(args: &[String], year: u32, day: u32, locale: Locale) -> Result<(Vec<u32>, DaySolver), Box<dyn Error>> {
+    #[cfg(feature = "plugins")]
+    {
+        let dir = Path::new(flag_value(args, "--plugins")?.unwrap_or("plugins"));
+        if let Some(plugin) = plugins::discover(dir)?.into_iter().find(|plugin| (plugin.year, plugin.day) == (year, day)) {
+            tracing::info!("Solving {} day {} with {}", year, day, plugin.path.display());
+            let parts = plugin.parts.clone();
+            return Ok((parts, Box::new(move |part, input| plugin.solve(part, input))))
+        }
+    }
+    Ok((parts_for(year, day)?.to_vec(), Box::new(move |part, input| aoc_solutions::solve_in(&locale, year, day, part, input))))
+}
+
+// The plugins in --plugins <dir> (plugins/ by default) and the days they
+// take over
+#[cfg(feature = "plugins")]
+fn list_plugins(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let dir = Path::new(flag_value(args, "--plugins")?.unwrap_or("plugins"));
+    let plugins = plugins::discover(dir)?;
+    if plugins.is_empty() {
+        return Err(format!("No plugins in {}", dir.display()).into())
+    }
+    for plugin in plugins {
+        let parts: Vec<String> = plugin.parts.iter().map(|part| part.to_string()).collect();
+        println!("{} day {} part {}: {}", plugin.year, plugin.day, parts.join(","), plugin.path.display());
+    }
+    Ok(())
+}
+
+// Makes the vault's key, or seals or unseals the inputs of the year's days
+#[cfg(feature = "vault")]
+fn vault(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let key_path = vault::key_path().ok_or("No AOC_VAULT_KEY, and no home directory for the key to go in")?;
+    if args.first().map(|arg| arg.as_str()) == Some("keygen") {
+        if key_path.exists() {
+            return Err(format!("{} already exists", key_path.display()).into())
+        }
+        let vault = vault::Vault::generate();
+        vault.save_key(&key_path)?;
+        println!("{}", vault.recipient());
+        return Ok(())
+    }
+    let vault = vault::Vault::open()?.ok_or(format!("No key at {}; make one with aoc vault keygen", key_path.display()))?;
+    let year = parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR);
+    let keep = args.iter().any(|arg| arg == "--keep");
+    for (_, day, _) in aoc_solutions::solutions().iter().filter(|(y, _, _)| *y == year) {
+        let path = find_input(year, *day);
+        match args.first().map(|arg| arg.as_str()) {
+            Some("seal") if vault::seal_file(&vault, &path, keep)? => println!("Sealed {}", vault::sealed_path(&path).display()),
+            Some("unseal") if vault::unseal_file(&vault, &path)? => println!("Unsealed {}", path.display()),
+            Some("seal" | "unseal") => (),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    Ok(())
+}
+
+// The answers cache, or one that keeps nothing with --no-cache
+fn open_cache(args: &[String]) -> cache::Cache {
+    if args.iter().any(|arg| arg == "--no-cache") {
+        cache::Cache::disabled()
+    } else {
+        match aoc_common::dirs::cache_dir() {
+            Some(dir) => cache::Cache::open_moved(&dir.join("answers.tsv"), Path::new(cache::OLD_PATH), &cache::build_id()),
+            None => cache::Cache::open(Path::new(cache::OLD_PATH), &cache::build_id()),
+        }
+    }
+}
+
+// Every part of every solved day on its default input, or of the days with
+// the tag given with --tag
+fn all(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let days: Vec<(u32, u32)> = match flag_value(args, "--tag")? {
+        Some(tag) => {
+            let days = aoc_solutions::days_tagged(tag);
+            if days.is_empty() {
+                return Err(format!("No days tagged '{}'", tag).into())
+            }
+            days
+        }
+        None => aoc_solutions::solutions().iter().map(|(year, day, _)| (*year, *day)).collect(),
+    };
+    let locale = locale(args)?;
+    // another locale can read the same input differently
+    let algorithm = if locale.is_english() { String::from("solution") } else { format!("solution:{}", locale.code) };
+    let mut cache = open_cache(args);
+    for (year, day) in days {
+        let input = day_input(year, day)?;
+        for part in parts_for(year, day)? {
+            let answer = cache.get_or_solve(year, day, *part, &algorithm, &input, || aoc_solutions::solve_in(&locale, year, day, *part, &input))?;
+            let args = [("year", year.to_string()), ("day", day.to_string()), ("part", part.to_string()), ("answer", answer)];
+            let args: Vec<(&str, &str)> = args.iter().map(|(name, value)| (*name, value.as_str())).collect();
+            println!("{}", locale.message("answer", &args));
+        }
+    }
+    cache.save()?;
+    Ok(())
+}
+
+fn run_all(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let year = parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR);
+    if !aoc_solutions::solutions().iter().any(|(y, _, _)| *y == year) {
+        return Err(format!("No solutions for {}", year).into());
+    }
+    print!("{}", run_all::header());
+    let (runs, wall) = aoc_common::output::timed(|| {
+        run_all::collect(year, |day| day_input(year, day), |run| print!("{}", run_all::rows(run)))
+    });
+    print!("{}", run_all::summary(year, &runs, wall));
+    Ok(())
+}
+
+// How a day is solved: the approach, each part's algorithm and complexity,
+// and its tags
+fn info(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [year, day] = [args.first(), args.get(1)].map(|arg| arg.and_then(|arg| arg.parse::<u32>().ok()));
+    let (Some(year), Some(day)) = (year, day) else { return Err(USAGE.into()) };
+    let metadata = aoc_solutions::metadata(year, day).ok_or(format!("No solution for {} day {}", year, day))?;
+    println!("{} day {}", year, day);
+    println!("{}", metadata.approach);
+    for (part, complexity) in metadata.complexity {
+        println!("part {}: {}, {}", part, aoc_solutions::algorithm(year, day, *part).unwrap_or("default"), complexity);
+    }
+    println!("tags: {}", metadata.tags.join(", "));
+    Ok(())
+}
+
+// What an input looks like: its lines, sections, numbers and grids. The
+// file given, or else the day's input.
+fn input(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.first().map(|arg| arg.as_str()) != Some("stats") {
+        return Err(USAGE.into())
+    }
+    let input = match positional_args(&args[1..], &["--day", "--year"]).first() {
+        Some(path) => read_input(Path::new(path))?,
+        None => {
+            let day = parsed_flag(args, "--day")?.ok_or(USAGE)?;
+            day_input(parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR), day)?
+        }
+    };
+    print!("{}", input_stats::report(&input_stats::stats(&normalize_input(&input))));
+    Ok(())
+}
+
+// Every part of the year against the answers recorded for it
+fn verify_answers(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let year = parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR);
+    let ledger = flag_value(args, "--answers")?.unwrap_or("answers.toml");
+    let entries = aoc_common::manifest::parse(&read_input(Path::new(ledger))?).map_err(|err| format!("{}: {}", ledger, err))?;
+    let mut cache = open_cache(args);
+    let checks = answers::check(year, &entries, |day| day_input(year, day), &mut cache);
+    cache.save()?;
+    if checks.is_empty() {
+        return Err(format!("No solutions for {}", year).into())
+    }
+    print!("{}", answers::report(year, ledger, &checks));
+    let failed = checks.iter().filter(|check| check.failed()).count();
+    if failed > 0 {
+        return Err(format!("{} part(s) don't give the answer in {}", failed, ledger).into())
+    }
+    Ok(())
+}
+
+fn batch(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let manifest = Path::new(args.first().ok_or(USAGE)?);
+    let runs = aoc_common::manifest::parse(&read_input(manifest)?)
+        .map_err(|err| format!("{}: {}", manifest.display(), err))?;
+
+    let mut results = vec![];
+    for run in runs {
+        tracing::info!("Running {} day {} part {}", run.year, run.day, run.part);
+        let outcome = batch::execute(&run, |run| read_input(&batch::input_path(run, manifest, default_input(run.day))));
+        results.push((run, outcome));
+    }
+    print!("{}", batch::matrix(&results));
+    let failed = results.iter().filter(|(_, outcome)| !matches!(outcome, batch::Outcome::Pass(_))).count();
+    if failed > 0 {
+        return Err(format!("{} of {} runs did not pass", failed, results.len()).into())
+    }
+    Ok(())
+}
+
+fn bench(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let runs = parsed_flag(args, "--runs")?.unwrap_or(10) as usize;
+    let only_day = parsed_flag(args, "--day")?;
+    if args.iter().any(|arg| arg == "--criterion") {
+        return criterion(only_day);
+    }
+
+    let with_counters = args.iter().any(|arg| arg == "--counters");
+    let format = OutputFormat::from_args(args)?;
+
+    let timings = bench::time_all(only_day, runs, with_counters, |day| day_input(DEFAULT_YEAR, day))?;
+    for timing in timings.iter() {
+        if format == OutputFormat::Json {
+            println!("{}", bench::to_json(timing));
+            continue
+        }
+        print!(
+            "{} day {:>2} part {}: median {:>12?}  min {:>12?}  max {:>12?}",
+            timing.year, timing.day, timing.part, timing.median(), timing.min(), timing.max()
+        );
+        match &timing.counters {
+            Some(counters) => println!(
+                "  {} instructions  {} cycles  {:.2} IPC  {} branch misses  {} cache misses",
+                counters.instructions, counters.cycles, counters.ipc(), counters.branch_misses, counters.cache_misses
+            ),
+            None => println!(),
+        }
+    }
+    if let Some(path) = flag_value(args, "--csv")? {
+        csv::append(Path::new(path), &timings)?;
+        tracing::info!("Wrote {} rows to {}", timings.len(), path);
+    }
+    Ok(())
+}
+
+// Runs two variants of a day on the same input, failing if their answers differ
+fn diff(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let positional = positional_args(args, &["--algos", "--part", "--log-level", "--log-format"]);
+    let (year, day) = match positional[..] {
+        [year, day, ..] => (
+            year.parse::<u32>().map_err(|_| format!("Invalid year '{}'", year))?,
+            day.parse::<u32>().map_err(|_| format!("Invalid day '{}'", day))?,
+        ),
+        _ => return Err(USAGE.into()),
+    };
+    let algos: Vec<&str> = flag_value(args, "--algos")?.ok_or("--algos needs two variants, e.g. tree,sorted")?
+        .split(',')
+        .map(|algo| algo.trim())
+        .collect();
+    let [a, b] = algos[..] else {
+        return Err("--algos needs exactly two variants".into())
+    };
+    let known = aoc_solutions::variants(year, day);
+    if known.is_empty() {
+        return Err(format!("{} day {} has no variants", year, day).into())
+    }
+    for algo in [a, b] {
+        if !known.contains(&algo) {
+            return Err(format!("Unknown variant '{}' for {} day {}, expected one of: {}", algo, year, day, known.join(", ")).into());
+        }
+    }
+    let parts = match parsed_flag(args, "--part")? {
+        Some(part) => vec![part],
+        None => parts_for(year, day)?.to_vec(),
+    };
+    let input = match positional.get(2) {
+        Some(path) => read_input(Path::new(path))?,
+        None => day_input(year, day)?,
+    };
+
+    let mut differing = 0;
+    for part in parts {
+        let run_a = diff::run_variant(year, day, part, a, &input);
+        let run_b = diff::run_variant(year, day, part, b, &input);
+        print!("{}", diff::report(part, &run_a, &run_b));
+        if run_a.answer != run_b.answer {
+            differing += 1;
+        }
+    }
+    if differing > 0 {
+        return Err(format!("{} and {} disagree on {} part(s)", a, b, differing).into())
+    }
+    Ok(())
+}
+
+// Every day of the year on a calendar, marked with how far each part has got.
+// Parts with a ledger answer are run on the day's input to check they still
+// give it, unless --no-run.
+fn status(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let year = parsed_flag(args, "--year")?.unwrap_or(DEFAULT_YEAR);
+    let ledger = Path::new(flag_value(args, "--answers")?.unwrap_or("answers.toml"));
+    let mut entries = vec![];
+    if ledger.exists() {
+        entries = aoc_common::manifest::parse(&read_input(ledger)?).map_err(|err| format!("{}: {}", ledger.display(), err))?;
+    }
+    let run = !args.iter().any(|arg| arg == "--no-run");
+    let mut cache = open_cache(args);
+    let states = status::collect(year, &entries, run, |day| day_input(year, day), &mut cache);
+    cache.save()?;
+    print!("{}{}", status::calendar(year, &states), status::summary(year, &states));
+    Ok(())
+}
+
+// `aoc run --mem`: the day's registered solution parsing the input once and
+// solving the parts on it, with what parsing and each part allocated, then
+// the most memory the process held
+fn run_mem(args: &[String], year: u32, day: u32, parts: &[u32], input: &str, locale: &Locale) -> Result<(), Box<dyn Error>> {
+    let format = OutputFormat::from_args(args)?;
+    let text = format == OutputFormat::Text;
+    let show_time = args.iter().any(|arg| arg == "--time");
+    let printer = AnswerPrinter::new(format, year, day, input);
+    let phases = aoc_solutions::phases(year, day, input)?;
+    let report = |alloc: AllocStats, duration: Duration| {
+        if !text {
+            return
+        }
+        let (bytes, count, peak) = (human_bytes(alloc.bytes), alloc.count.to_string(), human_bytes(alloc.peak));
+        eprintln!("  {}", locale.message("allocated", &[("bytes", &bytes), ("count", &count), ("peak", &peak)]));
+        if show_time {
+            eprintln!("  {}", locale.message("took", &[("time", &format!("{:?}", duration))]));
+        }
+    };
+    if text {
+        eprintln!("{}:", locale.message("parse", &[]));
+    }
+    report(phases.parse_alloc, phases.parse);
+    for part in parts {
+        let phase = phases.parts.iter()
+            .find(|phase| phase.part == *part)
+            .ok_or(format!("Day {} has no solution for part {}", day, part))?;
+        let answer = phase.answer.clone()?.to_string();
+        let algorithm = aoc_solutions::algorithm(year, day, *part).unwrap_or("default");
+        let record = printer.record(*part, algorithm, &answer, phase.duration);
+        printer.print(&locale.message("part", &[("part", &part.to_string())]), &record);
+        report(phase.alloc, phase.duration);
+    }
+    if let (true, Some(rss)) = (text, aoc_common::mem::rss()) {
+        eprintln!("{}", locale.message("peak_rss", &[("rss", &human_bytes(rss.peak))]));
+    }
+    Ok(())
+}
+
+// Criterion's benches, parse and each part on their own, run by cargo in the
+// workspace aoc was built from
+fn criterion(day: Option<u32>) -> Result<(), Box<dyn Error>> {
+    let mut command = Command::new(env::var("CARGO").unwrap_or(String::from("cargo")));
+    command.args(["bench", "-p", "aoc-solutions", "--bench", "days"]).current_dir(aoc_common::fixtures::workspace_root());
+    if let Some(day) = day {
+        command.args(["--", &format!("{}-day{}/", DEFAULT_YEAR, day)]);
+    }
+    let status = command.status().map_err(|err| format!("Could not run cargo bench: {}", err))?;
+    if !status.success() {
+        return Err(format!("cargo bench failed: {}", status).into());
+    }
+    Ok(())
+}
+
+// The day's puzzle as markdown, downloaded the first time and kept in the
+// cache after that
+fn puzzle(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (year, day) = puzzle_day(args)?;
+    let path = find_day_file(year, day, "puzzle.md");
+    // part two only shows once part one's solved, so it's worth asking again
+    if args.iter().any(|arg| arg == "--refresh") && path.exists() {
+        fs::remove_file(&path).map_err(|err| format!("Could not remove {}: {}", path.display(), err))?;
+    }
+    let markdown = fetch::cached(&path, || {
+        let html = fetch::download(&fetch::puzzle_url(year, day), fetch::session()?.as_deref())?;
+        puzzle::to_markdown(&html).ok_or(format!("{} day {} isn't out yet", year, day))
+    })?;
+    print!("{}", markdown);
+    Ok(())
+}
+
+// A recording from `aoc run --record` gone through again: every event, those
+// of one kind with --kind, or how many of each kind there were with --summary
+fn replay(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let path = positional_args(args, &["--kind"]).first().copied().ok_or(USAGE)?;
+    let mut recording = aoc_common::events::Recording::read(Path::new(path))?;
+    if let Some(kind) = flag_value(args, "--kind")? {
+        recording.events.retain(|event| event.kind == kind);
+    }
+    if args.iter().any(|arg| arg == "--summary") {
+        print!("{}", recording.summary());
+    } else {
+        print!("{}", recording.replay());
+    }
+    Ok(())
+}
+
+// Checks the days that have a brute force against it, on their examples and
+// on small generated inputs, failing if any answer differs
+fn verify(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = flag_value(args, "--budget")? {
+        return verify_budget(Path::new(path), parsed_flag(args, "--runs")?.unwrap_or(3) as usize)
+    }
+    if !args.iter().any(|arg| arg == "--cross") {
+        return verify_answers(args)
+    }
+    let only_day = parsed_flag(args, "--day")?;
+    let inputs = parsed_flag(args, "--inputs")?.unwrap_or(20);
+    let first_seed = aoc_common::rng::seed(args, 1)?;
+    let year = DEFAULT_YEAR;
+    if let Some(day) = only_day {
+        aoc_solutions::verifier(year, day).ok_or(format!("{} day {} has no brute force to check against", year, day))?;
+    }
+
+    let mut cache = open_cache(args);
+    let mut cases = vec![];
+    for (_, day, _) in aoc_solutions::solutions().iter().filter(|(y, _, _)| *y == year) {
+        let Some(verifier) = aoc_solutions::verifier(year, *day) else { continue };
+        if only_day.is_some_and(|only| only != *day) {
+            continue
+        }
+        // the brute forces are called directly, not through aoc_solutions::solve
+        let example = aoc_solutions::example(year, *day).map(|text| normalize_input(text).into_owned());
+        if example.is_none() {
+            tracing::warn!("No example for {} day {}, checking generated inputs only", year, day);
+        }
+        tracing::info!("Checking {} day {}", year, day);
+        cases.extend(verify::cross(year, *day, verifier, example.as_deref(), first_seed, inputs as u64, &mut cache));
+    }
+    cache.save()?;
+    print!("{}", verify::table(&cases));
+    let differing = cases.iter().flat_map(|case| case.checks.iter()).filter(|check| !check.agrees()).count();
+    if differing > 0 {
+        return Err(format!("{} check(s) differ from the brute force", differing).into())
+    }
+    Ok(())
+}
+
+// Times every run in the budgets manifest, a coarse check for performance
+// regressions, failing with the runs that went over their budget_ms
+fn verify_budget(manifest: &Path, runs: usize) -> Result<(), Box<dyn Error>> {
+    let entries = aoc_common::manifest::parse(&read_input(manifest)?)
+        .map_err(|err| format!("{}: {}", manifest.display(), err))?;
+
+    let mut results = vec![];
+    for run in entries {
+        tracing::info!("Timing {} day {} part {}", run.year, run.day, run.part);
+        let verdict = budget::check(&run, runs, |run| read_input(&batch::input_path(run, manifest, default_input(run.day))));
+        results.push((run, verdict));
+    }
+    print!("{}", budget::table(&results));
+    let failures = budget::failures(&results);
+    if !failures.is_empty() {
+        return Err(format!("Not within budget: {}", failures.join(", ")).into())
+    }
+    Ok(())
+}
+
+// The arguments that are neither flags nor the values of `flags_with_values`
+fn positional_args<'a>(args: &'a [String], flags_with_values: &[&str]) -> Vec<&'a str> {
+    let mut positional = vec![];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if flags_with_values.contains(&arg.as_str()) {
+            iter.next();
+        } else if !arg.starts_with("--") && !is_verbose_flag(arg) {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
+}
+
+fn report(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let runs = parsed_flag(args, "--runs")?.unwrap_or(10) as usize;
+    let only_day = parsed_flag(args, "--day")?;
+
+    let timings = bench::time_all(only_day, runs, false, |day| day_input(DEFAULT_YEAR, day))?;
+    let markdown = report::markdown(&timings, &csv::today(), &csv::git_commit());
+    write_output(flag_value(args, "--out")?.unwrap_or("-"), &markdown)
+}
+
+fn bench_service(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let port = parsed_flag(args, "--port")?.unwrap_or(9100);
+    let config = metrics::ServiceConfig {
+        port: u16::try_from(port).map_err(|_| format!("Invalid port {}", port))?,
+        interval: Duration::from_secs(parsed_flag(args, "--interval")?.unwrap_or(300) as u64),
+        runs: parsed_flag(args, "--runs")?.unwrap_or(10) as usize,
+        days: match flag_value(args, "--days")? {
+            Some(days) => days.split(',')
+                .map(|day| day.trim().parse().map_err(|_| format!("Invalid day '{}'", day)))
+                .collect::<Result<Vec<u32>, String>>()?,
+            None => vec![],
+        },
+    };
+    for day in config.days.iter() {
+        parts_for(DEFAULT_YEAR, *day)?;
+    }
+    metrics::serve(config, |day| day_input(DEFAULT_YEAR, day))?;
+    Ok(())
+}
+
+fn serve(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let port = parsed_flag(args, "--port")?.unwrap_or(8080);
+    let timeout = Duration::from_secs(parsed_flag(args, "--timeout")?.unwrap_or(10) as u64);
+    let config = serve::ServerConfig {
+        host: match flag_value(args, "--host")? {
+            Some(host) => host.parse().map_err(|_| format!("Invalid address '{}'", host))?,
+            None => IpAddr::from([127, 0, 0, 1]),
+        },
+        port: u16::try_from(port).map_err(|_| format!("Invalid port {}", port))?,
+        limits: http::Limits {
+            max_body_bytes: parsed_flag(args, "--max-body")?.unwrap_or(1024 * 1024) as usize,
+            timeout,
+            ..http::Limits::default()
+        },
+        solve_timeout: timeout,
+        max_concurrent: parsed_flag(args, "--max-concurrent")?.unwrap_or(8) as usize,
+        max_connections: parsed_flag(args, "--max-connections")?.unwrap_or(64) as usize,
+    };
+    serve::serve(config)?;
+    Ok(())
+}
+
+#[cfg(feature = "grpc")]
+fn grpc(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let port = parsed_flag(args, "--port")?.unwrap_or(50051);
+    let port = u16::try_from(port).map_err(|_| format!("Invalid port {}", port))?;
+    let timeout = Duration::from_secs(parsed_flag(args, "--timeout")?.unwrap_or(10) as u64);
+    grpc::serve(port, timeout)?;
+    Ok(())
+}
+
+// A dashboard of every solved day, checked against the answers ledger
+#[cfg(feature = "tui")]
+fn tui(args: &[String]) -> Result<(), Box<dyn Error>> {
+    if args.iter().any(|arg| arg == "--schematic") {
+        let input = match flag_value(args, "--input")? {
+            Some(path) => read_input(Path::new(path))?,
+            None => day_input(DEFAULT_YEAR, 3)?,
+        };
+        return tui::inspect(aoc_solutions::day_3::inspector::Inspector::new(&input)?)
+    }
+    let ledger = Path::new(flag_value(args, "--answers")?.unwrap_or("answers.toml"));
+    let mut entries = vec![];
+    if ledger.exists() {
+        entries = aoc_common::manifest::parse(&read_input(ledger)?).map_err(|err| format!("{}: {}", ledger.display(), err))?;
+    }
+    tui::run(dashboard::Dashboard::new(&entries), |day| day_input(DEFAULT_YEAR, day))
+}
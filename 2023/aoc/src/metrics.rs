@@ -0,0 +1,168 @@
+use std::fmt::Write;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::bench::{self, Timing};
+use crate::http::{self, Limits, Response};
+
+// What the benchmarking thread has measured so far, read by every scrape
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    pub timings: Vec<Timing>,
+    pub rounds: u64,
+    pub errors: u64,
+    pub last_round: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceConfig {
+    pub port: u16,
+    pub interval: Duration,
+    pub runs: usize,
+    // every solved day when empty
+    pub days: Vec<u32>,
+}
+
+// Prometheus' text exposition format
+pub fn render(metrics: &Metrics) -> String {
+    let mut out = String::new();
+    let labels = |timing: &Timing| {
+        format!("year=\"{}\",day=\"{}\",part=\"{}\"", timing.year, timing.day, timing.part)
+    };
+
+    let _ = writeln!(out, "# HELP aoc_solve_duration_seconds Time to solve a part, over the runs of the last round.");
+    let _ = writeln!(out, "# TYPE aoc_solve_duration_seconds gauge");
+    for timing in metrics.timings.iter() {
+        for (stat, duration) in [("min", timing.min()), ("median", timing.median()), ("max", timing.max())] {
+            let _ = writeln!(
+                out,
+                "aoc_solve_duration_seconds{{{},stat=\"{}\"}} {}",
+                labels(timing),
+                stat,
+                duration.as_secs_f64(),
+            );
+        }
+    }
+
+    let _ = writeln!(out, "# HELP aoc_solve_allocations Heap allocations made by one run of a part.");
+    let _ = writeln!(out, "# TYPE aoc_solve_allocations gauge");
+    for timing in metrics.timings.iter() {
+        let _ = writeln!(out, "aoc_solve_allocations{{{}}} {}", labels(timing), timing.allocations.count);
+    }
+    let _ = writeln!(out, "# HELP aoc_solve_allocated_bytes Bytes allocated by one run of a part.");
+    let _ = writeln!(out, "# TYPE aoc_solve_allocated_bytes gauge");
+    for timing in metrics.timings.iter() {
+        let _ = writeln!(out, "aoc_solve_allocated_bytes{{{}}} {}", labels(timing), timing.allocations.bytes);
+    }
+
+    let _ = writeln!(out, "# HELP aoc_bench_rounds_total Benchmark rounds completed.");
+    let _ = writeln!(out, "# TYPE aoc_bench_rounds_total counter");
+    let _ = writeln!(out, "aoc_bench_rounds_total {}", metrics.rounds);
+    let _ = writeln!(out, "# HELP aoc_bench_errors_total Benchmark rounds that failed.");
+    let _ = writeln!(out, "# TYPE aoc_bench_errors_total counter");
+    let _ = writeln!(out, "aoc_bench_errors_total {}", metrics.errors);
+    if let Some(last_round) = metrics.last_round {
+        let seconds = last_round.duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0);
+        let _ = writeln!(out, "# HELP aoc_bench_last_round_timestamp_seconds When the last round finished.");
+        let _ = writeln!(out, "# TYPE aoc_bench_last_round_timestamp_seconds gauge");
+        let _ = writeln!(out, "aoc_bench_last_round_timestamp_seconds {}", seconds);
+    }
+    out
+}
+
+// Benchmarks in the background every `interval`, serving the latest results
+// on /metrics until the process is stopped
+pub fn serve<F>(config: ServiceConfig, input_for: F) -> Result<(), String>
+where
+    F: Fn(u32) -> Result<String, String> + Send + 'static
+{
+    let listener = TcpListener::bind(("0.0.0.0", config.port))
+        .map_err(|err| format!("Could not listen on port {}: {}", config.port, err))?;
+    let metrics = Arc::new(Mutex::new(Metrics::default()));
+
+    let bench_metrics = Arc::clone(&metrics);
+    let bench_config = config.clone();
+    thread::spawn(move || loop {
+        let result = if bench_config.days.is_empty() {
+            bench::time_all(None, bench_config.runs, false, &input_for)
+        } else {
+            bench_config.days.iter()
+                .map(|day| bench::time_all(Some(*day), bench_config.runs, false, &input_for))
+                .collect::<Result<Vec<Vec<Timing>>, String>>()
+                .map(|timings| timings.concat())
+        };
+        {
+            let mut metrics = bench_metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match result {
+                Ok(timings) => {
+                    metrics.timings = timings;
+                    metrics.rounds += 1;
+                    metrics.last_round = Some(SystemTime::now());
+                }
+                Err(err) => {
+                    tracing::error!("Benchmark round failed: {}", err);
+                    metrics.errors += 1;
+                }
+            }
+        }
+        thread::sleep(bench_config.interval);
+    });
+
+    tracing::info!("Serving metrics on http://0.0.0.0:{}/metrics", config.port);
+    let limits = Limits { max_body_bytes: 0, timeout: Duration::from_secs(5), ..Limits::default() };
+    for stream in listener.incoming().flatten() {
+        let metrics = Arc::clone(&metrics);
+        thread::spawn(move || {
+            http::handle_connection(stream, &limits, |request| {
+                match (request.method.as_str(), request.path.as_str()) {
+                    ("GET", "/metrics") => {
+                        let metrics = metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                        Response::new(200, "text/plain; version=0.0.4", render(&metrics))
+                    }
+                    (_, "/metrics") => Response::text(405, "Only GET is supported"),
+                    _ => Response::text(404, "Not found"),
+                }
+            });
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::mem::AllocStats;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics {
+            timings: vec![Timing {
+                year: 2023,
+                day: 8,
+                part: 2,
+                answer: String::from("6"),
+                runs: vec![Duration::from_millis(2), Duration::from_millis(4), Duration::from_millis(3)],
+                allocations: AllocStats { count: 12, bytes: 4096, peak: 2048 },
+                counters: None,
+            }],
+            rounds: 3,
+            errors: 1,
+            last_round: Some(UNIX_EPOCH + Duration::from_secs(1700000000)),
+        };
+        let text = render(&metrics);
+        assert!(text.contains("aoc_solve_duration_seconds{year=\"2023\",day=\"8\",part=\"2\",stat=\"median\"} 0.003\n"));
+        assert!(text.contains("aoc_solve_duration_seconds{year=\"2023\",day=\"8\",part=\"2\",stat=\"max\"} 0.004\n"));
+        assert!(text.contains("aoc_solve_allocations{year=\"2023\",day=\"8\",part=\"2\"} 12\n"));
+        assert!(text.contains("aoc_solve_allocated_bytes{year=\"2023\",day=\"8\",part=\"2\"} 4096\n"));
+        assert!(text.contains("aoc_bench_rounds_total 3\n"));
+        assert!(text.contains("aoc_bench_errors_total 1\n"));
+        assert!(text.contains("aoc_bench_last_round_timestamp_seconds 1700000000\n"));
+        // every sample line belongs to a declared metric
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(text.contains(&format!("# TYPE {} ", name)), "{}", line);
+        }
+    }
+}
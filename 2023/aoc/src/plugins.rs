@@ -0,0 +1,121 @@
+// Days loaded at run time from shared libraries built with their `plugin`
+// feature (see aoc_common::plugin), so iterating on one day only rebuilds
+// that day. A plugin for a day takes over from the solution linked into the
+// runner.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::plugin::{self, PluginBuffer, PLUGIN_ABI, PLUGIN_BUFFER_TOO_SMALL, PLUGIN_OK};
+use aoc_common::strings::normalize_input;
+use libloading::{Library, Symbol};
+
+type Solve = unsafe extern "C" fn(u32, *const u8, usize, *mut PluginBuffer) -> i32;
+
+pub struct Plugin {
+    pub path: PathBuf,
+    pub year: u32,
+    pub day: u32,
+    pub parts: Vec<u32>,
+    // kept loaded for as long as the plugin can be called
+    library: Library,
+}
+
+fn symbol<'a, T>(library: &'a Library, path: &Path, name: &str) -> Result<Symbol<'a, T>, String> {
+    unsafe { library.get(name.as_bytes()) }.map_err(|_| format!("{} has no {}, is it a day plugin?", path.display(), name))
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Plugin, String> {
+        // running a library's initialisers is as safe as the library; these
+        // are our own days
+        let library = unsafe { Library::new(path) }.map_err(|err| format!("Could not load {}: {}", path.display(), err))?;
+        let info = |name: &str| -> Result<u32, String> { Ok(symbol::<extern "C" fn() -> u32>(&library, path, name)?()) };
+        let abi = info("aoc_plugin_abi")?;
+        if abi != PLUGIN_ABI {
+            return Err(format!("{} was built for plugin ABI {}, this runner needs {}", path.display(), abi, PLUGIN_ABI))
+        }
+        let (year, day, parts) = (info("aoc_plugin_year")?, info("aoc_plugin_day")?, info("aoc_plugin_parts")?);
+        symbol::<Solve>(&library, path, "aoc_plugin_solve")?;
+        Ok(Plugin { path: path.to_path_buf(), year, day, parts: plugin::parts_from_mask(parts), library })
+    }
+
+    pub fn solve(&self, part: u32, input: &str) -> Result<String, String> {
+        let input = normalize_input(input);
+        let solve = symbol::<Solve>(&self.library, &self.path, "aoc_plugin_solve")?;
+        let mut data = vec![0u8; 64];
+        loop {
+            let mut buffer = PluginBuffer { data: data.as_mut_ptr(), len: 0, capacity: data.len() };
+            let status = unsafe { solve(part, input.as_ptr(), input.len(), &mut buffer) };
+            if status == PLUGIN_BUFFER_TOO_SMALL {
+                data.resize(buffer.len, 0);
+                continue
+            }
+            let text = String::from_utf8_lossy(&data[..buffer.len.min(data.len())]).into_owned();
+            return match status {
+                PLUGIN_OK => Ok(text),
+                _ if text.is_empty() => Err(format!("{} failed with status {}", self.path.display(), status)),
+                _ => Err(text),
+            }
+        }
+    }
+}
+
+// Every plugin in the directory, in file name order; none when it doesn't
+// exist. Anything without the platform's shared library extension is
+// skipped.
+pub fn discover(dir: &Path) -> Result<Vec<Plugin>, String> {
+    let Ok(entries) = fs::read_dir(dir) else { return Ok(vec![]) };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| Plugin::load(path)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixtures;
+    use std::process::Command;
+
+    // Builds day 2 as a plugin, into a target directory of its own so it
+    // doesn't fight the running build over the workspace's. It's removed
+    // when the directory handed back is dropped.
+    fn build_day_2() -> (tempfile::TempDir, PathBuf) {
+        let target = tempfile::tempdir().unwrap();
+        let status = Command::new(env!("CARGO"))
+            .args(["rustc", "-p", "day-2", "--lib", "--features", "plugin", "--crate-type", "cdylib", "--offline", "--quiet"])
+            .arg("--target-dir")
+            .arg(target.path())
+            .current_dir(fixtures::workspace_root())
+            .status()
+            .expect("Could not run cargo");
+        assert!(status.success());
+        let path = target.path().join("debug")
+            .join(format!("{}day_2.{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_EXTENSION));
+        (target, path)
+    }
+
+    #[test]
+    fn test_plugin() {
+        let (_target, path) = build_day_2();
+        let plugin = Plugin::load(&path).unwrap();
+        assert_eq!((plugin.year, plugin.day, plugin.parts.clone()), (2023, 2, vec![1, 2]));
+        let example = fixtures::example(2);
+        assert_eq!(plugin.solve(1, &example), Ok(String::from("8")));
+        assert_eq!(plugin.solve(2, &example.replace('\n', "\r\n")), Ok(String::from("2286")));
+        assert_eq!(plugin.solve(3, &example), Err(String::from("Day 2 has no solution for part 3")));
+
+        let plugins = discover(path.parent().unwrap()).unwrap();
+        assert_eq!(plugins.len(), 1);
+        assert!(discover(Path::new("no/such/plugins")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_not_a_plugin() {
+        let err = Plugin::load(Path::new("no/such/libday.so")).err().unwrap();
+        assert!(err.starts_with("Could not load no/such/libday.so"), "{}", err);
+    }
+}
@@ -0,0 +1,172 @@
+// `aoc puzzle`: a day's puzzle from its page on adventofcode.com, as markdown
+// to read in the terminal. Only the puzzle's articles are kept, part two's
+// too once it's unlocked; the page around them, answers included, is dropped.
+// Just the handful of tags puzzles are written with are understood, and any
+// other is left out with its text kept.
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        // last, so an escaped entity stays one
+        .replace("&amp;", "&")
+}
+
+// Whitespace collapsed to single spaces, the way a browser shows it
+fn collapse(text: &str, out: &mut String) {
+    let mut after_space = out.is_empty() || out.ends_with([' ', '\n']);
+    for c in text.chars() {
+        match c.is_whitespace() {
+            true if after_space => (),
+            true => {
+                out.push(' ');
+                after_space = true;
+            }
+            false => {
+                out.push(c);
+                after_space = false;
+            }
+        }
+    }
+}
+
+// Ends what's been written so far with exactly one blank line
+fn end_block(out: &mut String) {
+    while out.ends_with([' ', '\n']) {
+        out.pop();
+    }
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+// The puzzle's articles in `html` as markdown; None without any, as on the
+// page of a puzzle that isn't out yet
+pub fn to_markdown(html: &str) -> Option<String> {
+    let mut out = String::new();
+    let (mut in_article, mut in_pre, mut in_code) = (false, false, false);
+    let mut articles = 0;
+    let mut rest = html;
+    while !rest.is_empty() {
+        // the text up to the next tag, and the tag; all that's left and no
+        // tag at the end
+        let (text, tag) = match rest.find('<').and_then(|start| Some((start, start + rest[start..].find('>')?))) {
+            Some((start, end)) => {
+                let (text, tag) = (&rest[..start], &rest[start + 1..end]);
+                rest = &rest[end + 1..];
+                (text, Some(tag))
+            }
+            None => (std::mem::take(&mut rest), None),
+        };
+        if in_article {
+            let text = decode_entities(text);
+            match in_pre {
+                true => out.push_str(&text),
+                false => collapse(&text, &mut out),
+            }
+        }
+        let Some(tag) = tag else { break };
+        let name = tag.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+        match name.as_str() {
+            "article" => in_article = true,
+            "/article" => {
+                in_article = false;
+                articles += 1;
+                end_block(&mut out);
+            }
+            _ if !in_article => (),
+            "h2" => out.push_str("## "),
+            "/h2" | "p" | "/p" | "ul" | "/ul" => end_block(&mut out),
+            "li" => {
+                while out.ends_with(' ') {
+                    out.pop();
+                }
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("- ");
+            }
+            "pre" => {
+                end_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            "/pre" => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                in_pre = false;
+                end_block(&mut out);
+            }
+            // highlights in code would only show as asterisks
+            "code" | "/code" if !in_pre => {
+                out.push('`');
+                in_code = name == "code";
+            }
+            "em" | "/em" if !in_pre && !in_code => out.push('*'),
+            _ => (),
+        }
+    }
+    if articles == 0 {
+        return None
+    }
+    end_block(&mut out);
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp;lt; &quot;c&quot;"), "a <b> &lt; \"c\"");
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let html = r#"<html><body><header>Advent of Code</header><main>
+<article class="day-desc"><h2>--- Day 9: Mirage Maintenance ---</h2><p>You ride the <em>camel</em>
+  through the   sandstorm.</p>
+<p>For example:</p>
+<pre><code>0 3 6 9 &lt;12
+1 <em>3</em> 6
+</code></pre>
+<ul><li>Keep the <code>x &amp; y</code> values.</li><li>Then <a href="/2023/day/8">go</a> on.</li></ul>
+<p>What is the sum of <em class="star">these</em>?</p>
+</article>
+<p>Your puzzle answer was <code>1842168671</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <code><em>backwards</em></code>.</p></article>
+</main></body></html>"#;
+        assert_eq!(
+            to_markdown(html).unwrap(),
+            "## --- Day 9: Mirage Maintenance ---
+
+You ride the *camel* through the sandstorm.
+
+For example:
+
+```
+0 3 6 9 <12
+1 3 6
+```
+
+- Keep the `x & y` values.
+- Then go on.
+
+What is the sum of *these*?
+
+## --- Part Two ---
+
+Now `backwards`.
+",
+        );
+        assert_eq!(to_markdown("<html><p>Please don't repeatedly request this endpoint before it unlocks!</p></html>"), None);
+    }
+}
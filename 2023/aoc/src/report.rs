@@ -0,0 +1,110 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::bench::Timing;
+
+// A Markdown summary of a run, meant to be pasted into a results log as is
+pub fn markdown(timings: &[Timing], date: &str, commit: &str) -> String {
+    let mut out = String::new();
+    let runs = timings.iter().map(|timing| timing.runs.len()).max().unwrap_or(0);
+    let _ = writeln!(out, "# Advent of Code results\n");
+    let _ = writeln!(out, "Run on {} at commit `{}`, timings over {} runs per part.\n", date, commit, runs);
+
+    let _ = writeln!(out, "## Answers\n");
+    let _ = writeln!(out, "| Year | Day | Part | Answer |");
+    let _ = writeln!(out, "|-----:|----:|-----:|-------:|");
+    for timing in timings {
+        let _ = writeln!(out, "| {} | {} | {} | {} |", timing.year, timing.day, timing.part, escape(&timing.answer));
+    }
+
+    let _ = writeln!(out, "\n## Timings\n");
+    let _ = writeln!(out, "| Year | Day | Part | Median | Min | Max |");
+    let _ = writeln!(out, "|-----:|----:|-----:|-------:|----:|----:|");
+    for timing in timings {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            timing.year,
+            timing.day,
+            timing.part,
+            format_duration(timing.median()),
+            format_duration(timing.min()),
+            format_duration(timing.max()),
+        );
+    }
+    let total: Duration = timings.iter().map(|timing| timing.median()).sum();
+    let _ = writeln!(out, "\nTotal of medians: {}", format_duration(total));
+
+    let _ = writeln!(out, "\n## Algorithms\n");
+    let _ = writeln!(out, "| Year | Day | Part | Algorithm |");
+    let _ = writeln!(out, "|-----:|----:|-----:|-----------|");
+    for timing in timings {
+        let algorithm = aoc_solutions::algorithm(timing.year, timing.day, timing.part).unwrap_or("default");
+        let _ = writeln!(out, "| {} | {} | {} | {} |", timing.year, timing.day, timing.part, algorithm);
+    }
+    out
+}
+
+// Three significant-ish digits in whichever unit keeps the number readable
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{} ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.1} µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2} ms", nanos / 1e6)
+    } else {
+        format!("{:.2} s", nanos / 1e9)
+    }
+}
+
+// Answers are free text, so pipes would break the table
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::mem::AllocStats;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(640)), "640 ns");
+        assert_eq!(format_duration(Duration::from_nanos(84_712)), "84.7 µs");
+        assert_eq!(format_duration(Duration::from_micros(1_521)), "1.52 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_500)), "2.50 s");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let timings = vec![
+            Timing {
+                year: 2023,
+                day: 2,
+                part: 1,
+                answer: String::from("8"),
+                runs: vec![Duration::from_micros(80), Duration::from_micros(90), Duration::from_micros(100)],
+                allocations: AllocStats::default(),
+                counters: None,
+            },
+            Timing {
+                year: 2023,
+                day: 5,
+                part: 2,
+                answer: String::from("a|b"),
+                runs: vec![Duration::from_millis(2)],
+                allocations: AllocStats::default(),
+                counters: None,
+            },
+        ];
+        let report = markdown(&timings, "2023-12-05", "abc1234");
+        assert!(report.contains("Run on 2023-12-05 at commit `abc1234`, timings over 3 runs per part."));
+        assert!(report.contains("| 2023 | 2 | 1 | 8 |"));
+        assert!(report.contains("| 2023 | 5 | 2 | a\\|b |"));
+        assert!(report.contains("| 2023 | 2 | 1 | 90.0 µs | 80.0 µs | 100.0 µs |"));
+        assert!(report.contains("| 2023 | 5 | 2 | range-map |"));
+        assert!(report.contains("Total of medians: 2.09 ms"));
+    }
+}
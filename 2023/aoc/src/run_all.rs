@@ -0,0 +1,160 @@
+// `aoc run-all`: every registered day of a year run once on its own input,
+// with the input's size, how long parsing it and each part took, each day's
+// runtime and the year's in one table. Nothing comes from the cache, since the runtimes are the point.
+// Days run side by side on rayon's pool, and each day's rows are printed as
+// soon as it's done, so a slow day doesn't hold up the rest.
+
+use std::time::Duration;
+
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: u32,
+    pub answer: Result<String, String>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub day: u32,
+    // the input couldn't be read or parsed, so no part ran
+    pub error: Option<String>,
+    pub input_bytes: usize,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    fn failed(day: u32, err: String) -> DayRun {
+        DayRun { day, error: Some(err), input_bytes: 0, parse: Duration::ZERO, parts: vec![] }
+    }
+
+    fn duration(&self) -> Duration {
+        self.parse + self.parts.iter().map(|part| part.duration).sum::<Duration>()
+    }
+}
+
+// Parses the input `read_input` gives for each of the year's days and runs
+// each part on it, the days in parallel, handing each day to `done` as it
+// finishes. A day whose input can't be read or parsed, or a part that fails,
+// is kept in the runs rather than stopping the rest. The runs come back by
+// day.
+pub fn collect<F, D>(year: u32, read_input: F, done: D) -> Vec<DayRun>
+where
+    F: Fn(u32) -> Result<String, String> + Sync,
+    D: Fn(&DayRun) + Sync,
+{
+    let days: Vec<u32> = aoc_solutions::solutions().iter()
+        .filter(|(y, _, _)| *y == year)
+        .map(|(_, day, _)| *day)
+        .collect();
+    let mut runs: Vec<DayRun> = days.into_par_iter()
+        .map(|day| {
+            tracing::info!("Running {} day {}", year, day);
+            let run = match read_input(day).and_then(|input| aoc_solutions::phases(year, day, &input)) {
+                Err(err) => DayRun::failed(day, err),
+                Ok(phases) => {
+                    let parts = phases.parts.into_iter()
+                        .map(|part| PartRun { part: part.part, answer: part.answer.map(|answer| answer.to_string()), duration: part.duration })
+                        .collect();
+                    DayRun { day, error: None, input_bytes: phases.input_bytes, parse: phases.parse, parts }
+                }
+            };
+            done(&run);
+            run
+        })
+        .collect();
+    runs.sort_by_key(|run| run.day);
+    runs
+}
+
+pub fn header() -> String {
+    format!("{:>3}  {:>5}  {:<20}  {:>12}\n", "day", "part", "answer", "time")
+}
+
+// A day's rows: parsing, with the input's size, one per part, then the day's
+// total
+pub fn rows(run: &DayRun) -> String {
+    if let Some(err) = &run.error {
+        return format!("{:>3}  {:>5}  error: {}\n", run.day, "-", err);
+    }
+    let mut out = format!("{:>3}  {:>5}  {:<20}  {:>12?}\n", run.day, "parse", format!("{} bytes", run.input_bytes), run.parse);
+    for part in &run.parts {
+        let answer = match &part.answer {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("error: {}", err),
+        };
+        out.push_str(&format!("{:>3}  {:>5}  {:<20}  {:>12?}\n", run.day, part.part, answer, part.duration));
+    }
+    out.push_str(&format!("{:>3}  {:>5}  {:<20}  {:>12?}\n", run.day, "", "total", run.duration()));
+    out
+}
+
+// The year's total: the time spent parsing and in the parts, and `wall`, how long the
+// whole run took with the days in parallel
+pub fn summary(year: u32, runs: &[DayRun], wall: Duration) -> String {
+    let parts = runs.iter().map(|run| run.parts.len()).sum::<usize>();
+    let failed = runs.iter().filter(|run| run.error.is_some()).count()
+        + runs.iter().flat_map(|run| &run.parts).filter(|part| part.answer.is_err()).count();
+    let total: Duration = runs.iter().map(DayRun::duration).sum();
+    format!("{}: {} days, {} parts in {:?} ({:?} wall), {} failed\n", year, runs.len(), parts, total, wall, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixtures;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_collect() {
+        let finished = Mutex::new(vec![]);
+        let read = |day| match day {
+            3 => Err(String::from("no input")),
+            5 => Ok(String::from("seeds: x\n")),
+            _ => Ok(fixtures::example(day)),
+        };
+        let runs = collect(2023, read, |run| finished.lock().unwrap().push(run.day));
+        let days: Vec<u32> = runs.iter().map(|run| run.day).collect();
+        assert_eq!(days, vec![1, 2, 3, 4, 5, 8]);
+        // every day was handed over as it finished, in whatever order that was
+        let mut finished = finished.into_inner().unwrap();
+        finished.sort();
+        assert_eq!(finished, days);
+        let answers: Vec<Result<String, String>> = runs[1].parts.iter().map(|part| part.answer.clone()).collect();
+        assert_eq!(answers, vec![Ok(String::from("8")), Ok(String::from("2286"))]);
+        assert_eq!(runs[1].input_bytes, fixtures::example(2).len());
+        assert_eq!(runs[2], DayRun::failed(3, String::from("no input")));
+        assert!(runs[4].error.is_some());
+        assert!(collect(1999, |_| Ok(String::new()), |_| {}).is_empty());
+    }
+
+    #[test]
+    fn test_rows() {
+        let runs = vec![
+            DayRun {
+                day: 1,
+                error: None,
+                input_bytes: 120,
+                parse: Duration::from_micros(500),
+                parts: vec![
+                    PartRun { part: 1, answer: Ok(String::from("142")), duration: Duration::from_millis(2) },
+                    PartRun { part: 2, answer: Err(String::from("bad line")), duration: Duration::from_millis(1) },
+                ],
+            },
+            DayRun::failed(2, String::from("Could not read day-2/input.txt")),
+        ];
+        assert_eq!(
+            header() + &rows(&runs[0]) + &rows(&runs[1]) + &summary(2023, &runs, Duration::from_millis(2)),
+            "day   part  answer                        time
+  1  parse  120 bytes                    500µs
+  1      1  142                            2ms
+  1      2  error: bad line                1ms
+  1         total                        3.5ms
+  2      -  error: Could not read day-2/input.txt
+2023: 2 days, 2 parts in 3.5ms (2ms wall), 2 failed
+",
+        );
+    }
+}
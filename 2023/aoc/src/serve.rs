@@ -0,0 +1,236 @@
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::output::{json_string, timed, AnswerRecord};
+
+use crate::http::{self, Limits, Request, Response};
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub host: IpAddr,
+    pub port: u16,
+    pub limits: Limits,
+    // how long a solution may run before the request gives up on it
+    pub solve_timeout: Duration,
+    // solves beyond this many at once are turned away with a 503, counting
+    // ones that outlived their request
+    pub max_concurrent: usize,
+    // as are connections beyond this many open at once, each of which has a
+    // thread of its own
+    pub max_connections: usize,
+}
+
+// One of a limited number of things in flight: a connection until its
+// thread answers it, or a solve until its thread finishes, which may be long
+// after the request stopped waiting for it
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(in_use: &Arc<AtomicUsize>, max: usize) -> Option<Slot> {
+        if in_use.fetch_add(1, Ordering::SeqCst) >= max {
+            in_use.fetch_sub(1, Ordering::SeqCst);
+            return None
+        }
+        Some(Slot(Arc::clone(in_use)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn json_error(status: u16, message: &str) -> Response {
+    Response::new(status, "application/json", format!("{{\"error\":{}}}\n", json_string(message)))
+}
+
+// "/solve/2023/5/1" into (2023, 5, 1)
+fn parse_solve_path(path: &str) -> Option<(u32, u32, u32)> {
+    let rest = path.strip_prefix("/solve/")?;
+    let mut parts = rest.trim_end_matches('/').split('/').map(|part| part.parse::<u32>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(year)), Some(Ok(day)), Some(Ok(part)), None) => Some((year, day, part)),
+        _ => None,
+    }
+}
+
+pub fn handle(request: Request, config: &ServerConfig, solving: &Arc<AtomicUsize>) -> Response {
+    if !request.path.starts_with("/solve/") {
+        return json_error(404, "Not found")
+    }
+    if request.method != "POST" {
+        return json_error(405, "Use POST with the puzzle input as the body")
+    }
+    let Some((year, day, part)) = parse_solve_path(&request.path) else {
+        return json_error(404, "Expected /solve/{year}/{day}/{part}")
+    };
+    let solved = aoc_solutions::solutions().iter()
+        .any(|(y, d, parts)| *y == year && *d == day && parts.contains(&part));
+    if !solved {
+        return json_error(404, &format!("No solution for {} day {} part {}", year, day, part))
+    }
+    let Ok(input) = String::from_utf8(request.body) else {
+        return json_error(400, "Input must be UTF-8")
+    };
+
+    let Some(slot) = Slot::take(solving, config.max_concurrent) else {
+        return json_error(503, "Too many solves running, try again later")
+    };
+    // solved on its own thread so a slow solution can be abandoned. It
+    // can't be stopped, so it still runs to completion in the background,
+    // holding its slot until it does.
+    let (sender, receiver) = mpsc::channel();
+    let solve_input = input.clone();
+    thread::spawn(move || {
+        let solved = timed(|| aoc_solutions::solve(year, day, part, &solve_input));
+        drop(slot);
+        let _ = sender.send(solved);
+    });
+    match receiver.recv_timeout(config.solve_timeout) {
+        Ok((Ok(answer), duration)) => {
+            let record = AnswerRecord {
+                year,
+                day,
+                part,
+                answer,
+                duration,
+                algorithm: aoc_solutions::algorithm(year, day, part).unwrap_or("default").to_string(),
+                input_hash: aoc_common::output::input_hash(&input),
+            };
+            Response::new(200, "application/json", format!("{}\n", record.to_json()))
+        }
+        Ok((Err(err), _)) => json_error(422, &err),
+        Err(mpsc::RecvTimeoutError::Timeout) => json_error(504, "Solving took too long"),
+        // the solving thread panicked
+        Err(mpsc::RecvTimeoutError::Disconnected) => json_error(500, "Solution failed"),
+    }
+}
+
+pub fn serve(config: ServerConfig) -> Result<(), String> {
+    let listener = TcpListener::bind((config.host, config.port))
+        .map_err(|err| format!("Could not listen on {}:{}: {}", config.host, config.port, err))?;
+    let address = listener.local_addr().map_err(|err| err.to_string())?;
+    tracing::info!("Listening on http://{}/solve/{{year}}/{{day}}/{{part}}", address);
+    serve_on(listener, config);
+    Ok(())
+}
+
+fn serve_on(listener: TcpListener, config: ServerConfig) {
+    let config = Arc::new(config);
+    let connections = Arc::new(AtomicUsize::new(0));
+    let solving = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming().flatten() {
+        let Some(slot) = Slot::take(&connections, config.max_connections) else {
+            turn_away(stream, &config);
+            continue
+        };
+        let config = Arc::clone(&config);
+        let solving = Arc::clone(&solving);
+        thread::spawn(move || {
+            http::handle_connection(stream, &config.limits, |request| handle(request, &config, &solving));
+            drop(slot);
+        });
+    }
+}
+
+// Answered straight away, without reading the request, so a flood of
+// connections costs the listener a short write each rather than a thread each
+fn turn_away(stream: TcpStream, config: &ServerConfig) {
+    tracing::warn!("Turning away a connection, {} are open already", config.max_connections);
+    let _ = stream.set_write_timeout(Some(Duration::from_secs(1)));
+    let _ = http::write_response(&stream, &json_error(503, "Too many connections, try again later"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    fn config() -> ServerConfig {
+        ServerConfig {
+            host: IpAddr::from([127, 0, 0, 1]),
+            port: 0,
+            limits: Limits::default(),
+            solve_timeout: Duration::from_secs(5),
+            max_concurrent: 4,
+            max_connections: 8,
+        }
+    }
+
+    fn post(path: &str, body: &str) -> Response {
+        let request = Request {
+            method: String::from("POST"),
+            path: path.to_string(),
+            headers: vec![],
+            body: body.as_bytes().to_vec(),
+        };
+        handle(request, &config(), &Arc::new(AtomicUsize::new(0)))
+    }
+
+    #[test]
+    fn test_parse_solve_path() {
+        assert_eq!(parse_solve_path("/solve/2023/5/1"), Some((2023, 5, 1)));
+        assert_eq!(parse_solve_path("/solve/2023/5/1/"), Some((2023, 5, 1)));
+        assert_eq!(parse_solve_path("/solve/2023/5"), None);
+        assert_eq!(parse_solve_path("/solve/2023/5/1/2"), None);
+        assert_eq!(parse_solve_path("/solve/2023/five/1"), None);
+    }
+
+    #[test]
+    fn test_handle() {
+        let response = post("/solve/2023/1/2", "two1nine\neightwothree\n");
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("{\"year\":2023,\"day\":1,\"part\":2,\"answer\":\"112\","));
+        assert!(response.body.contains("\"algorithm\":\"trie\""));
+
+        assert_eq!(post("/solve/2023/9/1", "").status, 404);
+        assert_eq!(post("/solve/2023/1/1", "").status, 404);
+        assert_eq!(post("/solve/2023/5/1", "not an almanac").status, 422);
+        assert_eq!(post("/other", "").status, 404);
+        let request = Request { method: String::from("GET"), path: String::from("/solve/2023/1/2"), headers: vec![], body: vec![] };
+        assert_eq!(handle(request, &config(), &Arc::new(AtomicUsize::new(0))).status, 405);
+    }
+
+    #[test]
+    fn test_max_concurrent() {
+        let solving = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<Slot> = (0..4).map_while(|_| Slot::take(&solving, 4)).collect();
+        assert_eq!(slots.len(), 4);
+        let request = Request {
+            method: String::from("POST"),
+            path: String::from("/solve/2023/1/2"),
+            headers: vec![],
+            body: b"two1nine\n".to_vec(),
+        };
+        assert_eq!(handle(request.clone(), &config(), &solving).status, 503);
+        // a solve that finished, even one its request gave up on, frees its slot
+        drop(slots);
+        assert_eq!(handle(request, &config(), &solving).status, 200);
+        assert_eq!(solving.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_max_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve_on(listener, ServerConfig { max_connections: 1, ..config() }));
+
+        // connections are accepted in turn, so the first holds the only slot
+        // while it's yet to send its request
+        let mut first = TcpStream::connect(address).unwrap();
+        let mut second = TcpStream::connect(address).unwrap();
+        let mut response = String::new();
+        second.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"), "{}", response);
+        assert!(response.ends_with("{\"error\":\"Too many connections, try again later\"}\n"), "{}", response);
+
+        first.write_all(b"POST /solve/2023/1/2 HTTP/1.1\r\nContent-Length: 9\r\n\r\ntwo1nine\n").unwrap();
+        let mut response = String::new();
+        first.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    }
+}
@@ -0,0 +1,161 @@
+// `aoc status`: where every part of every day stands, from the solution
+// registry and the answers ledger, drawn as December's calendar with a mark
+// per part.
+
+use aoc_common::manifest::Entry;
+
+use crate::cache::Cache;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    // no solution registered
+    Missing,
+    // solved in code, with no answer in the ledger
+    Implemented,
+    // with an answer in the ledger, not checked against a run
+    Solved,
+    // a run gave the ledger's answer
+    Verified,
+    // a run gave another answer, or failed
+    Wrong,
+}
+
+impl State {
+    fn mark(&self) -> char {
+        match self {
+            State::Missing => '.',
+            State::Implemented => 'o',
+            State::Solved => '+',
+            State::Verified => '*',
+            State::Wrong => '!',
+        }
+    }
+}
+
+// The two parts of each of the 25 days
+pub type Year = [[State; 2]; 25];
+
+// Where each part of the year's days stands. With `run`, each part with an
+// answer in the ledger is run on the input `read_input` gives for its day to
+// check it still gives it, or has its answer taken from the cache; a day
+// whose input can't be read stays Solved.
+pub fn collect<F>(year: u32, ledger: &[Entry], run: bool, read_input: F, cache: &mut Cache) -> Year
+where
+    F: Fn(u32) -> Result<String, String>
+{
+    let mut states = [[State::Missing; 2]; 25];
+    for (_, day, parts) in aoc_solutions::solutions().iter().filter(|(y, _, _)| *y == year) {
+        let input = if run { read_input(*day).ok() } else { None };
+        for part in parts.iter() {
+            let expected = ledger.iter()
+                .find(|entry| (entry.year, entry.day, entry.part) == (year, *day, *part) && entry.input.is_none())
+                .and_then(|entry| entry.expected.as_deref());
+            let state = match (expected, &input) {
+                (None, _) => State::Implemented,
+                (Some(_), None) => State::Solved,
+                (Some(expected), Some(input)) => {
+                    tracing::info!("Checking {} day {} part {}", year, day, part);
+                    match cache.get_or_solve(year, *day, *part, "solution", input, || aoc_solutions::solve(year, *day, *part, input)) {
+                        Ok(answer) if answer == expected => State::Verified,
+                        _ => State::Wrong,
+                    }
+                }
+            };
+            if let Some(slot) = states.get_mut(*day as usize - 1).and_then(|day| day.get_mut(*part as usize - 1)) {
+                *slot = state;
+            }
+        }
+    }
+    states
+}
+
+// 0 for Monday, by Sakamoto's method
+fn weekday(year: u32, month: u32, day: u32) -> u32 {
+    const OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let year = if month < 3 { year - 1 } else { year };
+    let sunday_first = (year + year / 4 - year / 100 + year / 400 + OFFSETS[month as usize - 1] + day) % 7;
+    (sunday_first + 6) % 7
+}
+
+pub fn calendar(year: u32, states: &Year) -> String {
+    let mut out = format!("{:^47}", format!("December {}", year)).trim_end().to_string();
+    out.push('\n');
+    out.push_str("Mon    Tue    Wed    Thu    Fri    Sat    Sun\n");
+    let mut line = "       ".repeat(weekday(year, 12, 1) as usize);
+    for (idx, parts) in states.iter().enumerate() {
+        let day = idx as u32 + 1;
+        line.push_str(&format!("{:>2} {}{}  ", day, parts[0].mark(), parts[1].mark()));
+        if weekday(year, 12, day) == 6 || day == 25 {
+            out.push_str(line.trim_end());
+            out.push('\n');
+            line.clear();
+        }
+    }
+    out.push_str("* verified  + solved  o implemented  ! wrong  . missing\n");
+    out
+}
+
+pub fn summary(year: u32, states: &Year) -> String {
+    let count = |wanted: &[State]| states.iter().flatten().filter(|state| wanted.contains(state)).count();
+    format!(
+        "{}: {} of 50 parts implemented, {} solved, {} verified, {} wrong, {} missing\n",
+        year,
+        count(&[State::Implemented, State::Solved, State::Verified, State::Wrong]),
+        count(&[State::Solved, State::Verified, State::Wrong]),
+        count(&[State::Verified]),
+        count(&[State::Wrong]),
+        count(&[State::Missing]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{fixtures, manifest};
+
+    fn ledger() -> Vec<Entry> {
+        manifest::parse("[[run]]\nyear = 2023\nday = 2\npart = 1\nexpected = \"8\"\n\n[[run]]\nyear = 2023\nday = 2\npart = 2\nexpected = \"1\"\n").unwrap()
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(2023, 12, 1), 4);
+        assert_eq!(weekday(2015, 12, 1), 1);
+        assert_eq!(weekday(2024, 12, 25), 2);
+    }
+
+    #[test]
+    fn test_collect() {
+        let read = |day: u32| Ok(fixtures::example(day));
+        let states = collect(2023, &ledger(), true, read, &mut Cache::disabled());
+        // part 1 matches the example, part 2 doesn't
+        assert_eq!(states[1], [State::Verified, State::Wrong]);
+        assert_eq!(states[0], [State::Missing, State::Implemented]);
+        assert_eq!(states[5], [State::Missing, State::Missing]);
+
+        let states = collect(2023, &ledger(), false, read, &mut Cache::disabled());
+        assert_eq!(states[1], [State::Solved, State::Solved]);
+        let states = collect(2023, &ledger(), true, |_| Err(String::from("no input")), &mut Cache::disabled());
+        assert_eq!(states[1], [State::Solved, State::Solved]);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let mut states = [[State::Missing; 2]; 25];
+        states[0] = [State::Verified, State::Solved];
+        states[2] = [State::Implemented, State::Wrong];
+        assert_eq!(
+            calendar(2023, &states),
+            "                 December 2023
+Mon    Tue    Wed    Thu    Fri    Sat    Sun
+                             1 *+   2 ..   3 o!
+ 4 ..   5 ..   6 ..   7 ..   8 ..   9 ..  10 ..
+11 ..  12 ..  13 ..  14 ..  15 ..  16 ..  17 ..
+18 ..  19 ..  20 ..  21 ..  22 ..  23 ..  24 ..
+25 ..
+* verified  + solved  o implemented  ! wrong  . missing
+",
+        );
+        assert_eq!(summary(2023, &states), "2023: 4 of 50 parts implemented, 3 solved, 1 verified, 1 wrong, 46 missing\n");
+    }
+}
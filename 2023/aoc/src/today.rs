@@ -0,0 +1,66 @@
+// Which puzzle is today's, for `aoc run` without a day. Puzzles unlock at
+// midnight US Eastern time, UTC-5 in December, so the date is taken there
+// rather than locally.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const UNLOCK_OFFSET: Duration = Duration::from_secs(5 * 3600);
+
+// (year, month, day) of a count of days since 1970-01-01, Howard Hinnant's
+// civil_from_days
+fn civil(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// The year and day of the puzzle out at `now`, None outside December 1st to
+// 25th
+pub fn puzzle_at(now: SystemTime) -> Option<(u32, u32)> {
+    let since_epoch = now.duration_since(UNIX_EPOCH).ok()?.checked_sub(UNLOCK_OFFSET)?;
+    let (year, month, day) = civil((since_epoch.as_secs() / 86_400) as i64);
+    if month == 12 && day <= 25 {
+        Some((year as u32, day))
+    } else {
+        None
+    }
+}
+
+pub fn puzzle() -> Option<(u32, u32)> {
+    puzzle_at(SystemTime::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_civil() {
+        assert_eq!(civil(0), (1970, 1, 1));
+        assert_eq!(civil(19_697), (2023, 12, 6));
+        assert_eq!(civil(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn test_puzzle_at() {
+        // 2023-12-05 05:00 UTC, midnight in New York
+        assert_eq!(puzzle_at(at(1_701_752_400)), Some((2023, 5)));
+        // a second earlier day 4 is still the latest
+        assert_eq!(puzzle_at(at(1_701_752_399)), Some((2023, 4)));
+        // 2023-12-26 and 2023-11-30 in New York
+        assert_eq!(puzzle_at(at(1_703_566_800)), None);
+        assert_eq!(puzzle_at(at(1_701_406_800 - 1)), None);
+        assert_eq!(puzzle_at(UNIX_EPOCH), None);
+    }
+}
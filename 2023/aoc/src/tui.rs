@@ -0,0 +1,173 @@
+// `aoc tui`, drawing the dashboard with ratatui: up and down (or k and j)
+// pick a day, enter or r runs it, 1 and 2 switch its parts on and off and q
+// or escape quits. `aoc tui --schematic` instead shows day 3's schematic with
+// a cursor the arrow keys (or hjkl) move, and what's under it beside it.
+
+use std::error::Error;
+use std::io::{self, Stdout};
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+
+use aoc_solutions::day_3::inspector::Inspector;
+use aoc_solutions::day_3::Highlight;
+
+use crate::dashboard::Dashboard;
+
+type Backend = Terminal<CrosstermBackend<Stdout>>;
+
+fn with_terminal<F>(f: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnOnce(&mut Backend) -> Result<(), Box<dyn Error>>
+{
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let result = Terminal::new(CrosstermBackend::new(io::stdout()))
+        .map_err(|err| err.into())
+        .and_then(|mut terminal| f(&mut terminal));
+    // put the terminal back even when drawing failed
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    result
+}
+
+pub fn run<F>(mut dashboard: Dashboard, read_input: F) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(u32) -> Result<String, String>
+{
+    with_terminal(|terminal| event_loop(terminal, &mut dashboard, &read_input))
+}
+
+pub fn inspect(mut inspector: Inspector) -> Result<(), Box<dyn Error>> {
+    with_terminal(|terminal| loop {
+        terminal.draw(|frame| draw_schematic(frame, &inspector))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue
+        }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Left | KeyCode::Char('h') => inspector.move_by(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => inspector.move_by(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => inspector.move_by(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => inspector.move_by(0, 1),
+            _ => (),
+        }
+    })
+}
+
+fn event_loop<F>(terminal: &mut Backend, dashboard: &mut Dashboard, read_input: &F) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(u32) -> Result<String, String>
+{
+    loop {
+        terminal.draw(|frame| draw(frame, dashboard))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue
+        }
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Down | KeyCode::Char('j') => dashboard.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => dashboard.select_previous(),
+            KeyCode::Enter | KeyCode::Char('r') => dashboard.run_selected(read_input),
+            KeyCode::Char(c @ '1'..='2') => dashboard.toggle_part(c as u32 - '0' as u32),
+            _ => (),
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, dashboard: &Dashboard) {
+    let areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(6), Constraint::Length(1)])
+        .split(frame.size());
+
+    let part_cell = |day: &crate::dashboard::Day, part: u32| {
+        Cell::from(day.parts.iter().find(|status| status.part == part).map(|status| status.status()).unwrap_or_default())
+    };
+    let rows: Vec<Row> = dashboard.days.iter()
+        .map(|day| Row::new(vec![
+            Cell::from(day.year.to_string()),
+            Cell::from(day.day.to_string()),
+            part_cell(day, 1),
+            part_cell(day, 2),
+            Cell::from(if day.time().is_zero() { String::from("-") } else { format!("{:?}", day.time()) }),
+            Cell::from("*".repeat(day.stars())),
+        ]))
+        .collect();
+    let widths = [
+        Constraint::Length(6),
+        Constraint::Length(5),
+        Constraint::Length(18),
+        Constraint::Length(18),
+        Constraint::Length(14),
+        Constraint::Length(5),
+    ];
+    let table = Table::new(rows, widths)
+        .header(Row::new(vec!["year", "day", "part 1", "part 2", "time", "stars"]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::default().borders(Borders::ALL).title(format!(" Advent of Code, {} stars ", dashboard.stars())))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(Some(dashboard.selected));
+    frame.render_stateful_widget(table, areas[0], &mut state);
+
+    let output = Paragraph::new(dashboard.output.join("\n")).block(Block::default().borders(Borders::ALL).title(" output "));
+    frame.render_widget(output, areas[1]);
+    frame.render_widget(Paragraph::new("j/k select  enter run  1/2 toggle part  q quit"), areas[2]);
+}
+
+// The schematic coloured as the SVG is, scrolled so the cursor stays in view
+fn draw_schematic(frame: &mut Frame, inspector: &Inspector) {
+    let areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(1)])
+        .split(frame.size());
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(40)])
+        .split(areas[0]);
+
+    let lines: Vec<Line> = inspector.schematic.rows()
+        .enumerate()
+        .map(|(y, row)| Line::from(row.iter()
+            .enumerate()
+            .map(|(x, c)| {
+                let point = aoc_common::point::Point::new(x as i64, y as i64);
+                let style = match inspector.highlights.get(&point) {
+                    Some(Highlight::Part) => Style::default().fg(Color::Green),
+                    Some(Highlight::NotPart) => Style::default().fg(Color::DarkGray),
+                    Some(Highlight::Gear) => Style::default().fg(Color::Yellow),
+                    Some(Highlight::Symbol) => Style::default().fg(Color::Red),
+                    Some(Highlight::Empty) | None => Style::default(),
+                };
+                let style = if point == inspector.cursor { style.add_modifier(Modifier::REVERSED) } else { style };
+                Span::styled(c.to_string(), style)
+            })
+            .collect::<Vec<Span>>()))
+        .collect();
+    // two borders, then keep the cursor's row and column on screen
+    let scroll_y = (inspector.cursor.y + 3).saturating_sub(panes[0].height as i64).max(0) as u16;
+    let scroll_x = (inspector.cursor.x + 3).saturating_sub(panes[0].width as i64).max(0) as u16;
+    let schematic = Paragraph::new(lines)
+        .scroll((scroll_y, scroll_x))
+        .block(Block::default().borders(Borders::ALL).title(" day 3 schematic "));
+    frame.render_widget(schematic, panes[0]);
+
+    let panel = Paragraph::new(inspector.panel()).block(Block::default().borders(Borders::ALL).title(" under the cursor "));
+    frame.render_widget(panel, panes[1]);
+    frame.render_widget(Paragraph::new("arrows or hjkl move  q quit"), areas[1]);
+}
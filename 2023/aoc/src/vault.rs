@@ -0,0 +1,249 @@
+// Inputs kept encrypted, in the tree or aoc's cache, since Advent of Code asks
+// that inputs aren't published: a day's input is sealed into input.txt.age
+// next to where input.txt would be, encrypted with age to the public half of
+// an X25519 key kept out of the repository, in AOC_VAULT_KEY's file or else
+// $XDG_CONFIG_HOME/aoc/vault.key (~/.config/aoc/vault.key), in age-keygen's
+// format. Where the key is, a sealed input is decrypted as a day's run reads
+// it and a downloaded one is sealed before it's saved; a plain input.txt is
+// still read as it is.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use age::secrecy::ExposeSecret;
+use age::x25519::Identity;
+use aoc_common::error::AocError;
+
+use crate::fetch;
+
+// Where the sealed input for `input` goes, input.txt.age for input.txt
+pub fn sealed_path(input: &Path) -> PathBuf {
+    let mut name = input.file_name().unwrap_or_default().to_os_string();
+    name.push(".age");
+    input.with_file_name(name)
+}
+
+pub fn key_path() -> Option<PathBuf> {
+    env::var_os("AOC_VAULT_KEY")
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| aoc_common::dirs::config_dir().map(|dir| dir.join("vault.key")))
+}
+
+pub struct Vault {
+    identity: Identity,
+}
+
+impl Vault {
+    pub fn generate() -> Vault {
+        Vault { identity: Identity::generate() }
+    }
+
+    // The key in a key file as age-keygen writes it, comments and all
+    pub fn parse(key_file: &str) -> Result<Vault, String> {
+        let key = key_file.lines()
+            .map(str::trim)
+            .find(|line| line.starts_with("AGE-SECRET-KEY-"))
+            .ok_or("No AGE-SECRET-KEY- line in the key file")?;
+        let identity = key.parse().map_err(|err| format!("Invalid key: {}", err))?;
+        Ok(Vault { identity })
+    }
+
+    // The key at `key_path`, None if there's no key file there
+    pub fn open() -> Result<Option<Vault>, String> {
+        let Some(path) = key_path().filter(|path| path.exists()) else { return Ok(None) };
+        let vault = Vault::parse(&AocError::read(&path)?).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(Some(vault))
+    }
+
+    pub fn recipient(&self) -> String {
+        self.identity.to_public().to_string()
+    }
+
+    pub fn key_file(&self) -> String {
+        format!("# public key: {}\n{}\n", self.recipient(), self.identity.to_string().expose_secret())
+    }
+
+    // Writes a new key file, readable only by its owner from the moment it's
+    // created where that's up to the file's mode. A key already there is left
+    // alone rather than overwritten.
+    pub fn save_key(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|err| format!("Could not create {}: {}", dir.display(), err))?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path).map_err(|err| format!("Could not create {}: {}", path.display(), err))?;
+        file.write_all(self.key_file().as_bytes()).map_err(|err| format!("Could not write {}: {}", path.display(), err))
+    }
+
+    // The input encrypted and ASCII armored, so it's text like the rest of
+    // the tree
+    pub fn seal(&self, input: &str) -> Result<String, String> {
+        age::encrypt_and_armor(&self.identity.to_public(), input.as_bytes()).map_err(|err| format!("Could not encrypt: {}", err))
+    }
+
+    pub fn unseal(&self, sealed: &str) -> Result<String, String> {
+        let input = age::decrypt(&self.identity, sealed.as_bytes()).map_err(|err| format!("Could not decrypt: {}", err))?;
+        String::from_utf8(input).map_err(|_| String::from("Could not decrypt: the input isn't UTF-8"))
+    }
+}
+
+// The input for `path`: the plain file if there is one, or else the sealed
+// one decrypted, or else what `fetch` gives, saved for next time, sealed if
+// there's a vault
+fn cached_with<F>(path: &Path, vault: Option<&Vault>, fetch: F) -> Result<String, String>
+where
+    F: FnOnce() -> Result<String, String>
+{
+    let sealed = sealed_path(path);
+    if path.exists() || (!sealed.exists() && vault.is_none()) {
+        return fetch::cached(path, fetch)
+    }
+    let Some(vault) = vault else {
+        let key = key_path().map(|path| path.display().to_string()).unwrap_or(String::from("AOC_VAULT_KEY"));
+        return Err(format!("{} is sealed, and there's no key at {} to open it with", sealed.display(), key))
+    };
+    if sealed.exists() {
+        return vault.unseal(&AocError::read(&sealed)?).map_err(|err| format!("{}: {}", sealed.display(), err))
+    }
+    let input = fetch()?;
+    fetch::save(&sealed, &vault.seal(&input)?)?;
+    tracing::info!("Saved {}", sealed.display());
+    Ok(input)
+}
+
+pub fn cached<F>(path: &Path, fetch: F) -> Result<String, String>
+where
+    F: FnOnce() -> Result<String, String>
+{
+    cached_with(path, Vault::open()?.as_ref(), fetch)
+}
+
+// Seals the plain input at `path`, removing it unless it's to be kept;
+// false when there's no plain input there
+pub fn seal_file(vault: &Vault, path: &Path, keep: bool) -> Result<bool, String> {
+    if !path.exists() {
+        return Ok(false)
+    }
+    fetch::save(&sealed_path(path), &vault.seal(&AocError::read(path)?)?)?;
+    if !keep {
+        fs::remove_file(path).map_err(|err| format!("Could not remove {}: {}", path.display(), err))?;
+    }
+    Ok(true)
+}
+
+// Writes the sealed input for `path` back out in plain; false when there's
+// no sealed input for it
+pub fn unseal_file(vault: &Vault, path: &Path) -> Result<bool, String> {
+    let sealed = sealed_path(path);
+    if !sealed.exists() {
+        return Ok(false)
+    }
+    let input = vault.unseal(&AocError::read(&sealed)?).map_err(|err| format!("{}: {}", sealed.display(), err))?;
+    fetch::save(path, &input)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fixtures;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_sealed_path() {
+        assert_eq!(sealed_path(Path::new("day-5/input.txt")), PathBuf::from("day-5/input.txt.age"));
+    }
+
+    #[test]
+    fn test_key_file() {
+        let vault = Vault::generate();
+        let key_file = vault.key_file();
+        assert!(key_file.starts_with("# public key: age1"), "{}", key_file);
+        let parsed = Vault::parse(&key_file).unwrap();
+        assert_eq!(parsed.recipient(), vault.recipient());
+        assert_eq!(Vault::parse("# nothing here\n").err(), Some(String::from("No AGE-SECRET-KEY- line in the key file")));
+        assert!(Vault::parse("AGE-SECRET-KEY-1NOTAKEY").is_err());
+    }
+
+    #[test]
+    fn test_save_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aoc").join("vault.key");
+        let vault = Vault::generate();
+        vault.save_key(&path).unwrap();
+        assert_eq!(Vault::parse(&fs::read_to_string(&path).unwrap()).unwrap().recipient(), vault.recipient());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        // the first key stays
+        assert!(Vault::generate().save_key(&path).unwrap_err().starts_with("Could not create "));
+        assert_eq!(Vault::parse(&fs::read_to_string(&path).unwrap()).unwrap().recipient(), vault.recipient());
+    }
+
+    #[test]
+    fn test_seal() {
+        let vault = Vault::generate();
+        let input = fixtures::example(5);
+        let sealed = vault.seal(&input).unwrap();
+        assert!(sealed.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"), "{}", sealed);
+        assert!(!sealed.contains("seeds"));
+        assert_eq!(vault.unseal(&sealed), Ok(input));
+        // only the key it was sealed with opens it
+        assert!(Vault::generate().unseal(&sealed).unwrap_err().starts_with("Could not decrypt: "));
+    }
+
+    #[test]
+    fn test_cached_with() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Vault::generate();
+        let path = dir.path().join("day-2").join("input.txt");
+        let fetches = Cell::new(0);
+        let fetch = || {
+            fetches.set(fetches.get() + 1);
+            Ok(fixtures::example(2))
+        };
+        // downloaded and sealed, then read back from the sealed file
+        assert_eq!(cached_with(&path, Some(&vault), fetch), Ok(fixtures::example(2)));
+        assert!(!path.exists() && sealed_path(&path).exists());
+        assert_eq!(cached_with(&path, Some(&vault), fetch), Ok(fixtures::example(2)));
+        assert_eq!(fetches.get(), 1);
+        // without the key it can't be read, or downloaded over
+        let err = cached_with(&path, None, fetch).unwrap_err();
+        assert!(err.ends_with("to open it with"), "{}", err);
+        assert_eq!(fetches.get(), 1);
+
+        // plain inputs are read as they are, and saved that way without a vault
+        assert!(unseal_file(&vault, &path).unwrap());
+        assert_eq!(cached_with(&path, None, fetch), Ok(fixtures::example(2)));
+        let plain = dir.path().join("day-4").join("input.txt");
+        assert_eq!(cached_with(&plain, None, || Ok(fixtures::example(4))), Ok(fixtures::example(4)));
+        assert!(plain.exists() && !sealed_path(&plain).exists());
+    }
+
+    #[test]
+    fn test_seal_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Vault::generate();
+        let path = dir.path().join("input.txt");
+        assert_eq!(seal_file(&vault, &path, false), Ok(false));
+        fs::write(&path, fixtures::example(8)).unwrap();
+        assert_eq!(seal_file(&vault, &path, true), Ok(true));
+        assert!(path.exists());
+        assert_eq!(seal_file(&vault, &path, false), Ok(true));
+        assert!(!path.exists());
+        assert_eq!(unseal_file(&vault, &path), Ok(true));
+        assert_eq!(fs::read_to_string(&path).unwrap(), fixtures::example(8));
+        assert_eq!(unseal_file(&vault, &dir.path().join("other.txt")), Ok(false));
+    }
+}
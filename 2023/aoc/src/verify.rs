@@ -0,0 +1,121 @@
+use aoc_common::answer::Answer;
+use aoc_common::verify::{CrossCheck, Verifier};
+
+use crate::cache::Cache;
+
+// One input a day was checked on, with how each part went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub year: u32,
+    pub day: u32,
+    pub input: String,
+    pub checks: Vec<CrossCheck>,
+}
+
+// Verifier::cross_check, taking both answers from the cache when it has them.
+// The cache keeps answers as text, which compares the same as the answer.
+fn cross_check(year: u32, day: u32, verifier: &dyn Verifier, input: &str, cache: &mut Cache) -> Vec<CrossCheck> {
+    let mut cached = |part: u32, algorithm: &str, solve: &dyn Fn() -> Result<Answer, String>| {
+        cache.get_or_solve(year, day, part, algorithm, input, || solve().map(|answer| answer.to_string())).map(Answer::from)
+    };
+    verifier.parts()
+        .iter()
+        .map(|part| CrossCheck {
+            part: *part,
+            answer: cached(*part, "solution", &|| verifier.solve(*part, input)),
+            expected: cached(*part, "brute-force", &|| verifier.brute_force(*part, input)),
+        })
+        .collect()
+}
+
+// Checks the day on its example, when there is one, and on `generated` small
+// inputs made from seeds `first_seed` and up
+pub fn cross(year: u32, day: u32, verifier: &dyn Verifier, example: Option<&str>, first_seed: u64, generated: u64, cache: &mut Cache) -> Vec<Case> {
+    let mut cases = vec![];
+    if let Some(example) = example {
+        cases.push(Case { year, day, input: String::from("example"), checks: cross_check(year, day, verifier, example, cache) });
+    }
+    for seed in first_seed..first_seed + generated {
+        let Some(input) = aoc_solutions::testgen::generate_small(year, day, seed) else { break };
+        cases.push(Case { year, day, input: format!("seed {}", seed), checks: cross_check(year, day, verifier, &input, cache) });
+    }
+    cases
+}
+
+fn answer_text(answer: &Result<Answer, String>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("error: {}", err),
+    }
+}
+
+// A row per part of every case, and both answers where they differ
+pub fn table(cases: &[Case]) -> String {
+    let mut out = String::from("year day part input      status\n");
+    let mut total = 0;
+    let mut agreeing = 0;
+    for case in cases.iter() {
+        for check in case.checks.iter() {
+            total += 1;
+            let status = if check.agrees() { "agree" } else { "DIFFER" };
+            out.push_str(&format!("{:<4} {:>3} {:>4} {:<10} {}\n", case.year, case.day, check.part, case.input, status));
+            if check.agrees() {
+                agreeing += 1;
+            } else {
+                out.push_str(&format!(
+                    "    solution {}, brute force {}\n",
+                    answer_text(&check.answer),
+                    answer_text(&check.expected),
+                ));
+            }
+        }
+    }
+    out.push_str(&format!("{} of {} checks agree\n", agreeing, total));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_5: &str = include_str!("../../day-5/example.txt");
+
+    #[test]
+    fn test_cross() {
+        let verifier = aoc_solutions::verifier(2023, 5).unwrap();
+        let cases = cross(2023, 5, verifier, Some(DAY_5), 1, 3, &mut Cache::disabled());
+        let inputs: Vec<&str> = cases.iter().map(|case| case.input.as_str()).collect();
+        assert_eq!(inputs, vec!["example", "seed 1", "seed 2", "seed 3"]);
+        assert!(cases.iter().all(|case| case.checks.len() == 2));
+        assert_eq!(cases[0].checks[1].answer, Ok(Answer::U64(46)));
+        assert_eq!(cross(2023, 5, verifier, None, 1, 0, &mut Cache::disabled()), vec![]);
+
+        let inputs: Vec<String> = cross(2023, 5, verifier, None, 40, 2, &mut Cache::disabled()).into_iter().map(|case| case.input).collect();
+        assert_eq!(inputs, vec!["seed 40", "seed 41"]);
+    }
+
+    #[test]
+    fn test_table() {
+        let check = |answer: u64, expected: Result<Answer, String>| CrossCheck { part: 1, answer: Ok(Answer::U64(answer)), expected };
+        let cases = vec![
+            Case { year: 2023, day: 5, input: String::from("example"), checks: vec![check(35, Ok(Answer::from("35")))] },
+            Case {
+                year: 2023,
+                day: 5,
+                input: String::from("seed 1"),
+                checks: vec![check(35, Ok(Answer::U64(34))), check(35, Err(String::from("No seeds line")))],
+            },
+        ];
+        assert_eq!(
+            table(&cases),
+            "year day part input      status
+2023   5    1 example    agree
+2023   5    1 seed 1     DIFFER
+    solution 35, brute force 34
+2023   5    1 seed 1     DIFFER
+    solution 35, brute force error: No seeds line
+1 of 3 checks agree
+",
+        );
+    }
+}
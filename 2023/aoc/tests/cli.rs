@@ -0,0 +1,502 @@
+// Runs the aoc binary the way it's used from a shell, on the puzzle examples,
+// checking what it prints and how it exits
+
+use std::fs;
+
+use aoc_common::fixtures;
+use assert_cmd::assert::Assert;
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn example(day: u32) -> String {
+    fixtures::path(format!("day-{}/example.txt", day)).to_str().unwrap().to_string()
+}
+
+// Runs from the workspace root, where the day directories are, with a cache
+// of its own in target/
+fn aoc() -> Command {
+    let mut command = Command::cargo_bin("aoc").unwrap();
+    command
+        .current_dir(fixtures::workspace_root())
+        .env_remove("AOC_LOG")
+        .env_remove("AOC_LOG_FORMAT")
+        .env_remove("RUST_LOG")
+        .env_remove("AOC_SEED")
+        .env_remove("AOC_LOCALE")
+        .env_remove("AOC_SESSION")
+        .env("XDG_CACHE_HOME", fixtures::path("target/cli-cache"));
+    command
+}
+
+// What a run printed, for checks that go through it line by line
+fn stdout(assert: &Assert) -> String {
+    String::from_utf8_lossy(&assert.get_output().stdout).into_owned()
+}
+
+#[test]
+fn test_run_all_parts() {
+    aoc().args(["run", "2", "--input", &example(2)]).assert().success().stdout("part 1: 8\npart 2: 2286\n");
+}
+
+#[test]
+fn test_run_by_flags() {
+    let input = example(5);
+    aoc().args(["run", "--year", "2023", "--day", "5", "--part", "2", "--input", &input])
+        .assert()
+        .success()
+        .stdout("part 2: 46\n");
+
+    aoc().args(["run", "5", "--day", "4", "--input", &input])
+        .assert()
+        .code(1)
+        .stderr("Give the day once, as <day> or --day <n>\n");
+}
+
+// From the repository's root, inputs are found under the year's directory
+#[test]
+fn test_run_from_repository_root() {
+    if fixtures::personal_input(2).is_none() {
+        return
+    }
+    Command::cargo_bin("aoc").unwrap()
+        .args(["run", "--day", "2", "--part", "1"])
+        .current_dir(fixtures::workspace_root().parent().unwrap())
+        .assert()
+        .success()
+        .stdout(format!("part 1: {}\n", fixtures::expected(2023, 2, 1).unwrap()));
+}
+
+#[test]
+fn test_run_one_part() {
+    aoc().args(["run", "5", "--part", "2", "--input", &example(5)]).assert().success().stdout("part 2: 46\n");
+}
+
+#[test]
+fn test_run_json() {
+    let assert = aoc().args(["run", "4", "--part", "1", "--input", &example(4), "--output", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{\"year\":2023,\"day\":4,\"part\":1,\"answer\":\"13\",\"duration_ns\":"))
+        .stdout(predicate::str::contains("\"algorithm\":\"winning-set\",\"input_hash\":\"fnv1a64:"));
+    assert_eq!(stdout(&assert).lines().count(), 1);
+}
+
+#[test]
+fn test_run_stdin() {
+    aoc().args(["run", "8", "--input", "-"])
+        .write_stdin(fixtures::example(8))
+        .assert()
+        .success()
+        .stdout("part 2: 6\n");
+}
+
+#[test]
+fn test_run_dot() {
+    aoc().args(["run", "8", "--input", &example(8), "--dot", "-"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph \"network\" {\n"));
+}
+
+#[test]
+fn test_record_and_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let events = dir.path().join("events.txt");
+    let events = events.to_str().unwrap();
+    let input = example(5);
+    aoc().args(["run", "5", "--part", "1", "--input", &input, "--record", events])
+        .assert()
+        .success()
+        .stdout("part 1: 35\n");
+
+    let assert = aoc().args(["replay", events, "--kind", "seed"]).assert().success();
+    let out = stdout(&assert);
+    let seeds: Vec<&str> = out.lines().map(|line| line.split_once("ms ").unwrap().1).collect();
+    assert_eq!(seeds, vec!["seed seed=79 location=82", "seed seed=14 location=43", "seed seed=55 location=86", "seed seed=13 location=35"]);
+
+    aoc().args(["replay", events, "--summary"])
+        .assert()
+        .stdout(predicate::str::starts_with("part                1  last at "))
+        .stdout(predicate::str::ends_with("\n14 events\n"));
+
+    aoc().args(["replay", &input]).assert().code(1).stderr(predicate::str::contains("Not an event recording"));
+
+}
+
+#[test]
+fn test_locale() {
+    aoc().args(["run", "1", "--input", "-", "--locale", "de"])
+        .write_stdin("zwei1\nneunundacht\n")
+        .assert()
+        .success()
+        .stdout("Teil 2: 119\n");
+
+    let dir = tempfile::tempdir().unwrap();
+    let locale = dir.path().join("pirate.toml");
+    fs::write(&locale, "[digits]\nyo = 1\nho = 2\n\n[messages]\npart = \"booty {part}\"\n").unwrap();
+    aoc().args(["run", "1", "--input", "-", "--locale", locale.to_str().unwrap()])
+        .write_stdin("yoho\n")
+        .assert()
+        .stdout("booty 2: 12\n");
+
+    aoc().args(["run", "1", "--input", "-", "--locale", "xx"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("Could not read locales/xx.toml"));
+}
+
+#[test]
+fn test_run_inputs() {
+    let (two, five) = (example(2), example(5));
+    let assert = aoc().args(["run", "5", "--input", &five, "--input", "-"])
+        .write_stdin("seeds: 79 14 55 13\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with("input"))
+        .stderr(predicate::str::ends_with("1 of 2 inputs failed\n"));
+    let out = stdout(&assert);
+    let rows: Vec<Vec<&str>> = out.lines().map(|line| line.split_whitespace().collect()).collect();
+    assert_eq!(rows[1], vec![five.as_str(), "35", "46"]);
+    assert!(rows[2][1].starts_with("error:"), "{}", out);
+
+    let assert = aoc().args(["run", "2", "--input", &two, "--input", &two, "--part", "1"]).assert().success();
+    assert_eq!(stdout(&assert).lines().nth(1).map(|line| line.ends_with("  8")), Some(true));
+    aoc().args(["run", "2", "--input", &two, "--input", "-", "--mem"]).assert().stderr("--mem takes a single input\n");
+}
+
+#[test]
+fn test_input_stats() {
+    aoc().args(["input", "stats", "-"])
+        .write_stdin("seeds: 79 14\n\n#.\n.#\n")
+        .assert()
+        .success()
+        .stdout("bytes     20\nlines     4\nsections  2, of 1, 2 lines\nwidths    2 to 12\nnumbers   2, from 14 to 79\ngrid      2 x 2 in section 2\nchars     #.1479:des\n");
+    aoc().args(["input", "stats", "--day", "7"])
+        .assert()
+        .stderr(predicate::str::starts_with("Could not read "))
+        .stderr(predicate::str::contains("cli-cache/aoc/2023/day-7/input.txt: "));
+}
+
+// A compressed input is found where a plain one would be, and decompressed
+#[test]
+fn test_run_compressed() {
+    let dir = tempfile::tempdir().unwrap();
+    let cached = dir.path().join("aoc").join("2023").join("day-3");
+    fs::create_dir_all(&cached).unwrap();
+    // a Zstandard frame with the example in a single raw block
+    let text = fixtures::example(3);
+    let block = (1 | (text.len() << 3)) as u32;
+    let frame = [&[0x28, 0xb5, 0x2f, 0xfd, 0x20, text.len() as u8][..], &block.to_le_bytes()[..3], text.as_bytes()].concat();
+    fs::write(cached.join("input.txt.zst"), frame).unwrap();
+    Command::cargo_bin("aoc").unwrap()
+        .args(["run", "3"])
+        .current_dir(dir.path())
+        .env("XDG_CACHE_HOME", dir.path())
+        .assert()
+        .success()
+        .stdout("part 1: 4361\npart 2: 467835\n");
+
+    let broken = dir.path().join("input.txt.gz");
+    fs::write(&broken, fixtures::example(3)).unwrap();
+    aoc().args(["run", "3", "--input", broken.to_str().unwrap()])
+        .assert()
+        .stderr(predicate::str::starts_with(format!("Could not read {}: ", broken.display())));
+}
+
+#[cfg(not(feature = "clipboard"))]
+#[test]
+fn test_run_clipboard() {
+    aoc().args(["run", "2", "--clipboard"])
+        .assert()
+        .failure()
+        .stderr("Reading the clipboard needs aoc built with the clipboard feature\n");
+}
+
+// From a directory without the inputs, with no session to download them
+// with and then with one that's not a token, so nothing's downloaded
+#[test]
+fn test_missing_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let run = || {
+        let mut command = Command::cargo_bin("aoc").unwrap();
+        command.args(["run", "2"]).current_dir(dir.path()).env_remove("AOC_SESSION").env("XDG_CONFIG_HOME", dir.path()).env("XDG_CACHE_HOME", dir.path());
+        command
+    };
+    let cached = dir.path().join("aoc").join("2023").join("day-2").join("input.txt");
+    run().assert()
+        .failure()
+        .stderr(predicate::str::starts_with(format!("Could not read {}: ", cached.display())))
+        .stderr(predicate::str::ends_with("; set AOC_SESSION to download it\n"));
+    run().env("AOC_SESSION", "not a token")
+        .assert()
+        .failure()
+        .stderr("The session in AOC_SESSION isn't a session token\n");
+    assert!(!dir.path().join("aoc").exists());
+}
+
+// A puzzle that's been downloaded is read from the cache, or from the tree
+// where puzzles used to be kept
+#[test]
+fn test_puzzle() {
+    let dir = tempfile::tempdir().unwrap();
+    let cached = dir.path().join("cache").join("aoc").join("2023").join("day-9");
+    fs::create_dir_all(&cached).unwrap();
+    fs::write(cached.join("puzzle.md"), "## --- Day 9: Mirage Maintenance ---\n").unwrap();
+    fs::create_dir_all(dir.path().join("day-10")).unwrap();
+    fs::write(dir.path().join("day-10").join("puzzle.md"), "## --- Day 10: Pipe Maze ---\n").unwrap();
+    for (day, expected) in [("9", "## --- Day 9: Mirage Maintenance ---\n"), ("10", "## --- Day 10: Pipe Maze ---\n")] {
+        Command::cargo_bin("aoc").unwrap()
+            .args(["puzzle", "--day", day])
+            .current_dir(dir.path())
+            .env("XDG_CACHE_HOME", dir.path().join("cache"))
+            .assert()
+            .success()
+            .stdout(expected);
+    }
+}
+
+#[test]
+fn test_errors() {
+    let cases: &[(&[&str], &str)] = &[
+        (&[], "Usage:"),
+        (&["run", "6"], "No solution for 2023 day 6"),
+        (&["puzzle", "--day", "x"], "Invalid day 'x'"),
+        (&["run", "two"], "Invalid day 'two'"),
+        (&["run", "2", "--year", "2022"], "No solution for 2022 day 2"),
+        (&["run", "2", "--part", "x"], "Invalid value for --part: 'x'"),
+        (&["run", "2", "--input", "no/such/input.txt"], "Could not read no/such/input.txt"),
+        (&["run", "2", "--input", "-", "--output", "yaml"], "Unknown output format 'yaml'"),
+        (&["run", "2", "--example", "--clipboard"], "--clipboard is the input, so it can't be given with --example or --input"),
+        (&["run", "2", "--input", "a.txt", "--input", "b.txt", "--clipboard"], "--clipboard takes a single input"),
+        (&["run", "2", "--input-url", "ftp://example.com/2.txt"], "Invalid input URL 'ftp://example.com/2.txt'"),
+        (&["run", "2", "--input-url", "https://example.com/2.txt", "--example"], "--input-url is the input, so it can't be given with --example, --clipboard or --input"),
+        (&["diff", "2023", "4", "--algos", "tree,sorted"], "2023 day 4 has no variants"),
+        (&["diff", "2023", "5", "--algos", "tree,list"], "Unknown variant 'list' for 2023 day 5, expected one of: tree, sorted"),
+        (&["verify", "--answers", "no/such/answers.toml"], "Could not read no/such/answers.toml"),
+        (&["verify", "--cross", "--day", "4"], "2023 day 4 has no brute force to check against"),
+        (&["verify", "--cross", "--seed", "x"], "Invalid seed 'x'"),
+    ];
+    for (args, message) in cases {
+        aoc().args(*args).assert().code(1).stdout("").stderr(predicate::str::contains(*message));
+    }
+}
+
+#[test]
+fn test_diff() {
+    aoc().args(["diff", "2023", "5", "--algos", "tree,sorted", "--part", "1", &example(5)])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("part 1\n"))
+        .stdout(predicate::str::ends_with("  answers match\n"));
+}
+
+#[test]
+fn test_verify() {
+    aoc().args(["verify", "--cross", "--day", "8", "--inputs", "2"])
+        .assert()
+        .success()
+        .stdout("year day part input      status\n2023   8    2 example    agree\n2023   8    2 seed 1     agree\n2023   8    2 seed 2     agree\n3 of 3 checks agree\n");
+
+    aoc().args(["verify", "--cross", "--day", "8", "--inputs", "1", "--seed", "77"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2023   8    2 seed 77    agree\n"));
+
+    // the example is the bundled one, wherever it's run from
+    let dir = tempfile::tempdir().unwrap();
+    aoc().args(["verify", "--cross", "--day", "8", "--inputs", "0"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2023   8    2 example    agree\n"));
+}
+
+#[test]
+fn test_verify_budget() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::copy(example(4), dir.path().join("day-4.txt")).unwrap();
+    let budgets = dir.path().join("budgets.toml");
+    let run = |budget_ms: u64| format!("[[run]]\nyear = 2023\nday = 4\npart = 1\ninput = \"day-4.txt\"\nbudget_ms = {}\n", budget_ms);
+
+    fs::write(&budgets, run(10_000)).unwrap();
+    aoc().args(["verify", "--budget", budgets.to_str().unwrap(), "--runs", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("1 of 1 within budget\n"));
+
+    fs::write(&budgets, run(10_000) + &run(0)).unwrap();
+    aoc().args(["verify", "--budget", budgets.to_str().unwrap(), "--runs", "1"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::ends_with("1 of 2 within budget\n"))
+        .stderr(predicate::str::contains("Not within budget: 2023 day 4 part 1"));
+
+}
+
+#[test]
+fn test_status() {
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.toml");
+    fs::write(&answers, "[[run]]\nyear = 2023\nday = 4\npart = 1\nexpected = \"21138\"\n").unwrap();
+    aoc().args(["status", "--answers", answers.to_str().unwrap(), "--no-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("                 December 2023\n"))
+        .stdout(predicate::str::contains("\n 4 +o   5 oo"))
+        .stdout(predicate::str::ends_with("2023: 10 of 50 parts implemented, 1 solved, 0 verified, 0 wrong, 40 missing\n"));
+
+    aoc().args(["status", "--year", "2015", "--no-run"])
+        .assert()
+        .stdout(predicate::str::ends_with("2015: 0 of 50 parts implemented, 0 solved, 0 verified, 0 wrong, 50 missing\n"));
+
+}
+
+#[test]
+fn test_info() {
+    aoc().args(["info", "2023", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("2023 day 5\n"))
+        .stdout(predicate::str::contains("\npart 1: flatten, O("))
+        .stdout(predicate::str::ends_with("\ntags: interval, range-tree\n"));
+
+    aoc().args(["info", "2023", "6"]).assert().code(1).stderr("No solution for 2023 day 6\n");
+}
+
+#[test]
+fn test_run_example() {
+    // from any directory, since the examples are bundled
+    let dir = tempfile::tempdir().unwrap();
+    Command::cargo_bin("aoc").unwrap()
+        .args(["run", "5", "--example"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout("part 1: 35\npart 2: 46\n");
+
+    // German digit words read the English example differently
+    aoc().args(["run", "1", "--example", "--locale", "de"])
+        .assert()
+        .code(1)
+        .stdout("Teil 2: 209\n")
+        .stderr("Part 2 gives 209 for the example, not 281\n");
+
+    aoc().args(["run", "6", "--example"]).assert().code(1).stderr("No solution for 2023 day 6\n");
+}
+
+#[test]
+fn test_verify_answers() {
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.toml");
+    fs::write(&answers, "[[run]]\nyear = 2023\nday = 4\npart = 1\nexpected = \"1\"\n").unwrap();
+    let assert = aoc().args(["verify", "--answers", answers.to_str().unwrap(), "--no-cache"]).assert();
+    match fixtures::personal_input(4) {
+        Some(_) => {
+            let expected = fixtures::expected(2023, 4, 1).unwrap();
+            assert.code(1)
+                .stdout(predicate::str::contains(format!("  4    1  DIFFER\n--- 2023 day 4 part 1 in {}\n+++ 2023 day 4 part 1 now\n-1\n+{}\n", answers.display(), expected)))
+                .stdout(predicate::str::contains("  4    2  unrecorded\n"))
+                .stderr(predicate::str::ends_with(format!("1 part(s) don't give the answer in {}\n", answers.display())));
+        }
+        None => {
+            assert.success();
+        }
+    }
+
+    aoc().args(["verify", "--answers", "answers.toml", "--year", "2015"]).assert().stderr("No solutions for 2015\n");
+}
+
+#[test]
+fn test_verbose() {
+    let input = example(2);
+    aoc().args(["run", "2", "--input", &input]).assert().stderr(predicate::str::contains("DEBUG").not());
+    aoc().args(["run", "-v", "2", "--input", &input])
+        .assert()
+        .success()
+        .stdout("part 1: 8\npart 2: 2286\n")
+        .stderr(predicate::str::contains("possible games: [1, 2, 5]"))
+        .stderr(predicate::str::contains("TRACE").not());
+}
+
+#[test]
+fn test_log_directives() {
+    let input = example(2);
+    aoc().args(["run", "2", "--log-level", "day_2=debug,aoc_common=off", "--input", &input])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("possible games: [1, 2, 5]"))
+        .stderr(predicate::str::contains("aoc_common").not());
+    aoc().args(["run", "2", "--log-level", "off", "--input", &input]).assert().stderr("");
+}
+
+#[test]
+fn test_run_mem() {
+    let input = example(5);
+    let assert = aoc().args(["run", "5", "--mem", "--input", &input])
+        .assert()
+        .success()
+        .stdout("part 1: 35\npart 2: 46\n")
+        .stderr(predicate::str::contains("parse:\n  allocated "));
+    let err = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    assert_eq!(err.matches(" allocations, peak ").count(), 3, "{}", err);
+    assert!(err.contains("\npeak resident set ") || !cfg!(target_os = "linux"), "{}", err);
+
+    aoc().args(["run", "1", "--mem", "--part", "1", "--input", &input])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::ends_with("Day 1 has no solution for part 1\n"));
+}
+
+#[test]
+fn test_run_all() {
+    let assert = aoc().arg("run-all")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("day   part  answer"))
+        .stdout(predicate::str::contains("\n  2  parse  "))
+        .stdout(predicate::str::contains("\n2023: 6 days, 10 parts in "));
+    let out = stdout(&assert);
+    assert!(out.ends_with(" wall), 0 failed\n") || fixtures::personal_input(1).is_none(), "{}", out);
+    if let (Some(_), Some(expected)) = (fixtures::personal_input(4), fixtures::expected(2023, 4, 1)) {
+        assert!(out.contains(&format!("\n  4      1  {:<20}", expected)), "{}", out);
+    }
+
+    aoc().args(["run-all", "--year", "2015"]).assert().code(1).stderr("No solutions for 2015\n");
+}
+
+#[test]
+fn test_all_by_tag() {
+    // the only graph day, run on its personal input
+    if fixtures::personal_input(8).is_some() {
+        let expected = format!("2023 day 8 part 2: {}\n", fixtures::expected(2023, 8, 2).unwrap());
+        // solved, then from the cache, then solved again without it
+        for args in [&["all", "--tag", "graph"][..], &["all", "--tag", "graph"], &["all", "--tag", "graph", "--no-cache"]] {
+            aoc().args(args).assert().success().stdout(expected.clone());
+        }
+    }
+
+    aoc().args(["all", "--tag", "nothing"]).assert().code(1).stderr("No days tagged 'nothing'\n");
+}
+
+#[test]
+fn test_batch() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::copy(example(4), dir.path().join("day-4.txt")).unwrap();
+    let manifest = dir.path().join("runs.toml");
+    let run = |expected: &str| format!("[[run]]\nyear = 2023\nday = 4\npart = 1\ninput = \"day-4.txt\"\nexpected = \"{}\"\n", expected);
+
+    fs::write(&manifest, run("13")).unwrap();
+    aoc().args(["batch", manifest.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("1 of 1 passed\n"));
+
+    fs::write(&manifest, run("13") + &run("12")).unwrap();
+    aoc().args(["batch", manifest.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("    expected 12, got 13\n"))
+        .stdout(predicate::str::ends_with("1 of 2 passed\n"))
+        .stderr(predicate::str::contains("1 of 2 runs did not pass"));
+
+}
@@ -0,0 +1,114 @@
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::locale::Locale;
+use aoc_common::solution::Solution;
+use aoc_common::strings::Trie;
+
+// A digit either written out as one, or spelled as a word starting here
+fn get_digit_at(line: &str, position: usize, digit_names: &Trie<u32>) -> Option<u32> {
+    if let Some(d) = line[position..].chars().next()?.to_digit(10) {
+        return Some(d)
+    }
+    digit_names.longest_match_at(line, position).map(|(_, d)| *d)
+}
+
+fn get_digits(line: &str, digit_names: &Trie<u32>) -> u32 {
+    let positions: Vec<usize> = line.char_indices().map(|(idx, _)| idx).collect();
+    let first = positions.iter()
+        .find_map(|p| get_digit_at(line, *p, digit_names));
+    let second = positions.iter()
+        .rev()
+        .find_map(|p| get_digit_at(line, *p, digit_names));
+
+    format!("{}{}", first.unwrap_or(0), second.unwrap_or(0))
+        .parse::<u32>()
+        .unwrap_or(0)
+}
+
+pub fn get_calibration_value(contents: &str, locale: &Locale) -> u32 {
+    let digit_names = locale.digit_words();
+    contents
+        .lines()
+        .map(|line| get_digits(line, &digit_names))
+        .sum()
+}
+
+// Lines are read as they're summed, so there's nothing to parse ahead
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 1;
+    const PARTS: &'static [u32] = &[2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(2, "281")];
+    const APPROACH: &'static str = "Digits and spelled out digits matched from each position with a trie of the words, n characters and words of up to w letters";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(2, "O(n·w)")];
+    const TAGS: &'static [&'static str] = &["strings", "trie"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(2, "trie")];
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, AocError> {
+        Ok(input.to_string())
+    }
+
+    fn part_two(input: &String) -> Result<Answer, AocError> {
+        Ok(get_calibration_value(input, &Locale::english()).into())
+    }
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day1::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+aoc_common::register_solution!(2023, 1, Day1, with_solve_in(solve_in));
+
+// With the digits spelled out in the locale's words rather than English
+pub fn solve_in(locale: &Locale, part: u32, input: &str) -> Result<String, String> {
+    match part {
+        2 => {
+            let _span = tracing::info_span!("solve").entered();
+            Ok(get_calibration_value(input, locale).to_string())
+        }
+        _ => Err(format!("Day 1 has no solution for part {}", part)),
+    }
+}
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 1, &[2], solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the part 2 example; part 1 isn't solved on its own
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(2, EXAMPLE), Ok(String::from("281")));
+        assert!(solve(1, EXAMPLE).is_err());
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        let digit_names = Locale::english().digit_words();
+        assert_eq!(get_digits("é1ß", &digit_names), 11);
+        assert_eq!(get_digits("ñtwo→3日本", &digit_names), 23);
+        // an accented letter breaks up a word rather than matching it
+        assert_eq!(get_digits("🎄fïveseven🎅", &digit_names), 77);
+        // only ASCII digits count, not other scripts' or full-width ones
+        assert_eq!(get_digits("٣４nine", &digit_names), 99);
+        assert_eq!(get_digits("éàü", &digit_names), 0);
+        assert_eq!(solve(2, "x1é\nöeight9ß\n"), Ok(String::from("100")));
+    }
+
+    #[test]
+    fn test_other_locale() {
+        let german = Locale::parse("de", "[digits]\neins = 1\nzwei = 2\ndrei = 3\nsieben = 7\n").unwrap();
+        assert_eq!(solve_in(&german, 2, "zweixeins\n4siebenünf\ndrei\n"), Ok(String::from("101")));
+        // English words mean nothing in German
+        assert_eq!(solve_in(&german, 2, "two1nine\n"), Ok(String::from("11")));
+    }
+}
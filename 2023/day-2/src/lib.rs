@@ -0,0 +1,283 @@
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+use std::cmp::max;
+use std::iter::Peekable;
+use std::str::FromStr;
+use strum::EnumString;
+
+/**
+ * I'm well aware that writing a full parser for this 
+ * isn't really necessary, but I wanted to brush up on
+ * parser logic and practice working with iterators.
+ */
+
+#[derive(Debug)]
+enum Token {
+    Colon,
+    Color(Color),
+    Number(u32),
+    Semicolon,
+    Newline,
+}
+
+#[derive(Debug, PartialEq, EnumString)]
+#[strum(ascii_case_insensitive)]
+enum Color {
+    Red,
+    Green,
+    Blue
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Game {
+    id: u32,
+    sets: Vec<RevealSet>,
+}
+
+impl Game {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn sets(&self) -> &[RevealSet] {
+        &self.sets
+    }
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RevealSet {
+    pub red: u32,
+    pub green: u32,
+    pub blue: u32,
+}
+
+
+// A number too big for a u32 isn't one, but all of its digits are consumed
+fn get_number<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Option<u32> {
+    let mut number = Some(iter.next()?.to_digit(10)?);
+    while let Some(digit) = iter.peek().and_then(|c| c.to_digit(10)) {
+        number = number.and_then(|n| n.checked_mul(10)?.checked_add(digit));
+        iter.next();
+    }
+    number
+}
+
+fn get_color<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Option<Color> {
+    let mut word = iter.next()?.to_string();
+    while let Some(letter) = iter.peek() {
+        if !letter.is_alphabetic() {
+            break;
+        }
+        word.push(*letter);
+        iter.next();
+    }
+    Color::from_str(&word).ok()
+}
+
+fn lex(input: &str) -> Vec<Token> {
+    let _span = tracing::info_span!("lex").entered();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut iter = input.chars().peekable();
+    while let Some(&c) = iter.peek() {
+        match c {
+            ':' => {
+                tokens.push(Token::Colon);
+                iter.next();
+            }
+            ';' => {
+                tokens.push(Token::Semicolon);
+                iter.next();
+            }
+            '0'..='9' => {
+                if let Some(num) = get_number(&mut iter) {
+                    tokens.push(Token::Number(num));
+                }
+            }
+            'a'..='z' => {
+                if let Some(color) = get_color(&mut iter) {
+                    tokens.push(Token::Color(color));
+                }
+            }
+            '\n' => {
+                tokens.push(Token::Newline);
+                iter.next();
+            }
+            _ => _ = iter.next()
+        }
+    }
+    tokens
+}
+
+pub fn parse(input: &str) -> Vec<Game> {
+    let _span = tracing::info_span!("parse").entered();
+    let lex_tokens = lex(input);
+
+    let mut games: Vec<Game> = Vec::new();
+    let mut iter = lex_tokens.iter().peekable();
+    while iter.peek().is_some() {
+        games.push(parse_game(&mut iter));
+    }
+    games
+}
+
+fn parse_game<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>) -> Game {
+    let mut game = Game::default();
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Number(num) => {
+                game.id = *num;
+                iter.next();
+            }
+            Token::Colon | Token::Semicolon => {
+                iter.next();
+                game.sets.push(parse_set(iter));
+            }
+            Token::Newline => {
+                iter.next();
+                break
+            },
+            // only sets have colors, but skipping a stray one keeps `parse`
+            // from getting stuck on it
+            Token::Color(_) => _ = iter.next(),
+        }
+    }
+    game
+}
+
+fn parse_set<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>) -> RevealSet {
+    let mut set = RevealSet::default();
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Number(num) => {
+                iter.next();
+                if let Some(Token::Color(col)) = iter.peek() {
+                    match col {
+                        Color::Red => set.red = *num,
+                        Color::Blue => set.blue = *num,
+                        Color::Green => set.green = *num,
+                    }
+                }
+            }
+            Token::Color(_) => _ = iter.next(),
+            _ => break
+        }
+    }
+    set
+}
+
+pub fn possible_games_sum(games: &[Game], available: &RevealSet) -> u64 {
+    let possible_game_ids: Vec<u32> = games
+        .iter()
+        .filter(|g| {
+            let has_impossible_set = g.sets.iter().any(|s| {
+                s.red > available.red || s.green > available.green || s.blue > available.blue
+            });
+            !has_impossible_set
+        })
+        .map(|g| g.id)
+        .collect();
+
+    tracing::debug!("possible games: {:?}", possible_game_ids);
+    possible_game_ids.iter().map(|id| u64::from(*id)).sum()
+}
+
+// The powers can run past a u64 with enough cubes, which is an error rather
+// than a panic
+pub fn sum_of_powers(games: &[Game]) -> Result<u64, AocError> {
+    let minimum_sets: Vec<RevealSet> = games
+        .iter()
+        .map(|g| {
+            let mut minimum = RevealSet::default();
+            for set in g.sets.iter() {
+                minimum.red = max(minimum.red, set.red);
+                minimum.green = max(minimum.green, set.green);
+                minimum.blue = max(minimum.blue, set.blue);
+            }
+            minimum
+        })
+        .collect();
+    tracing::debug!("minimum sets: {:?}", minimum_sets);
+    let too_big = || AocError::Parse(String::from("The sum of powers doesn't fit a u64"));
+    minimum_sets.iter().try_fold(0u64, |sum, s| {
+        let power = u64::from(s.red)
+            .checked_mul(u64::from(s.green))
+            .and_then(|power| power.checked_mul(u64::from(s.blue)))
+            .ok_or_else(too_big)?;
+        sum.checked_add(power).ok_or_else(too_big)
+    })
+}
+
+// The cubes the elf says are in the bag
+pub const AVAILABLE: RevealSet = RevealSet {
+    red: 12,
+    green: 13,
+    blue: 14
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 2;
+    const PARTS: &'static [u32] = &[1, 2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(1, "8"), (2, "2286")];
+    const APPROACH: &'static str = "Each game's reveals lexed into counts per colour, checked against the bag's limits or reduced to the most of each colour, r reveals";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(1, "O(r)"), (2, "O(r)")];
+    const TAGS: &'static [&'static str] = &["parsing"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(1, "reveal-limits"), (2, "max-per-color")];
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Vec<Game>, AocError> {
+        Ok(parse(input))
+    }
+
+    fn part_one(games: &Vec<Game>) -> Result<Answer, AocError> {
+        Ok(possible_games_sum(games, &AVAILABLE).into())
+    }
+
+    fn part_two(games: &Vec<Game>) -> Result<Answer, AocError> {
+        Ok(sum_of_powers(games)?.into())
+    }
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day2::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+aoc_common::register_solution!(2023, 2, Day2);
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 2, &[1, 2], solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    // the tokens are checked in under src/snapshots/, `cargo insta review` to accept changes
+    #[test]
+    fn test_lex_example() {
+        insta::assert_debug_snapshot!("lex_example", lex(EXAMPLE));
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(1, EXAMPLE), Ok(String::from("8")));
+        assert_eq!(solve(2, EXAMPLE), Ok(String::from("2286")));
+        assert_eq!(Day2::solve_part1(EXAMPLE).unwrap(), Answer::U64(8));
+        assert_eq!(Day2::solve_part2(EXAMPLE).unwrap(), Answer::U64(2286));
+    }
+
+    #[test]
+    fn test_large_powers() {
+        assert_eq!(solve(2, "Game 1: 99999 red, 99999 green, 2 blue\n"), Ok(String::from("19999600002")));
+        let overflow = "Game 1: 4294967295 red, 4294967295 green, 2 blue\n";
+        assert_eq!(solve(2, overflow).err(), Some(String::from("The sum of powers doesn't fit a u64")));
+    }
+}
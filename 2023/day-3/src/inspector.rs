@@ -0,0 +1,99 @@
+// A cursor over the schematic and what the matrix knows about the item under
+// it, for stepping around a schematic to see why a number did or didn't
+// count. Only the state and the panel text live here; drawing it is up to
+// whoever shows it.
+
+use aoc_common::grid::Grid;
+use aoc_common::point::Point;
+use aoc_common::spatial::Entry;
+
+use crate::{parse, Highlight, Item, ItemMatrix};
+
+pub struct Inspector {
+    pub schematic: Grid<char>,
+    pub highlights: Grid<Highlight>,
+    pub matrix: ItemMatrix,
+    pub cursor: Point,
+}
+
+fn describe(entry: &Entry<Item>) -> String {
+    let region = entry.region();
+    match entry.value() {
+        Item::Part(part) => format!("number {} at {},{}", part, region.left(), region.top()),
+        Item::Symbol(symbol) => format!("symbol {} at {},{}", symbol, region.left(), region.top()),
+    }
+}
+
+impl Inspector {
+    pub fn new(input: &str) -> Result<Inspector, String> {
+        let schematic: Grid<char> = input.parse()?;
+        let matrix = parse(input)?;
+        let highlights = matrix.highlights(schematic.width(), schematic.height());
+        Ok(Inspector { schematic, highlights, matrix, cursor: Point::new(0, 0) })
+    }
+
+    // Moves the cursor, stopping at the schematic's edges
+    pub fn move_by(&mut self, dx: i64, dy: i64) {
+        let x = (self.cursor.x + dx).clamp(0, self.schematic.width() as i64 - 1);
+        let y = (self.cursor.y + dy).clamp(0, self.schematic.height() as i64 - 1);
+        self.cursor = Point::new(x, y);
+    }
+
+    pub fn panel(&self) -> String {
+        let mut out = format!("cursor {},{}\n", self.cursor.x, self.cursor.y);
+        let Some(entry) = self.matrix.item_at(&self.cursor) else {
+            out.push_str("nothing here\n");
+            return out
+        };
+        out.push_str(&format!("{}\n", describe(entry)));
+        let adjacent = self.matrix.adjacent_items(entry);
+        out.push_str(&format!("touching {}:\n", adjacent.len()));
+        for other in adjacent {
+            out.push_str(&format!("  {}\n", describe(other)));
+        }
+        let verdict = match entry.value() {
+            Item::Part(_) if self.matrix.is_real_part(entry) => String::from("a part, next to a symbol"),
+            Item::Part(_) => String::from("not a part, no symbol next to it"),
+            Item::Symbol(_) => match self.matrix.gear_ratio(entry) {
+                Ok(Some(ratio)) => format!("a gear, ratio {}", ratio),
+                Ok(None) => String::from("not a gear"),
+                Err(err) => format!("a gear, {}", err),
+            },
+        };
+        out.push_str(&verdict);
+        out.push('\n');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_move_by() {
+        let mut inspector = Inspector::new(EXAMPLE).unwrap();
+        inspector.move_by(-1, -1);
+        assert_eq!(inspector.cursor, Point::new(0, 0));
+        inspector.move_by(100, 3);
+        assert_eq!(inspector.cursor, Point::new(9, 3));
+    }
+
+    #[test]
+    fn test_panel() {
+        let mut inspector = Inspector::new(EXAMPLE).unwrap();
+        inspector.move_by(1, 0);
+        assert_eq!(inspector.panel(), "cursor 1,0\nnumber 467 at 0,0\ntouching 1:\n  symbol * at 3,1\na part, next to a symbol\n");
+        inspector.move_by(2, 1);
+        assert_eq!(
+            inspector.panel(),
+            "cursor 3,1\nsymbol * at 3,1\ntouching 2:\n  number 467 at 0,0\n  number 35 at 2,2\na gear, ratio 16345\n",
+        );
+        inspector.move_by(3, -1);
+        assert!(inspector.panel().ends_with("number 114 at 5,0\ntouching 0:\nnot a part, no symbol next to it\n"));
+        inspector.move_by(0, 1);
+        assert_eq!(inspector.panel(), "cursor 6,1\nnothing here\n");
+    }
+}
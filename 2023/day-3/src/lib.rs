@@ -0,0 +1,279 @@
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::grid::Grid;
+use aoc_common::point::Point;
+use aoc_common::prefix_sum::PrefixSum2D;
+use aoc_common::solution::Solution;
+use aoc_common::spatial::{Entry, Region, SpatialIndex};
+use aoc_common::svg::{self, SvgCell};
+
+pub mod inspector;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Item {
+    Part(String),
+    Symbol(char)
+}
+
+// What a cell of the schematic turned out to be, for drawing it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Highlight {
+    Empty,
+    Part,
+    NotPart,
+    Gear,
+    Symbol,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ItemMatrix(SpatialIndex<Item>);
+impl ItemMatrix {
+    pub fn add_symbol(&mut self, symbol: char, point: Point) {
+        self.0.insert_at(point, Item::Symbol(symbol));
+    }
+
+    pub fn add_part(&mut self, part: String, point: Point) {
+        let width = part.chars().count() as i64;
+        if width == 0 {
+            return
+        }
+
+        self.0.insert_region(Region::new(point, width, 1), Item::Part(part));
+    }
+
+    // Number of symbols in every rectangle of the schematic, so checking a
+    // part's surroundings doesn't have to look at each cell around it
+    fn symbol_counts(&self) -> PrefixSum2D {
+        let width = self.0.iter().map(|entry| entry.region().right()).max().unwrap_or(0);
+        let height = self.0.iter().map(|entry| entry.region().bottom()).max().unwrap_or(0);
+        let mut counts = Grid::filled(width as usize, height as usize, 0);
+        for entry in self.0.iter() {
+            if let (Item::Symbol(_), Some(count)) = (entry.value(), counts.get_mut(&entry.region().origin)) {
+                *count += 1;
+            }
+        }
+        PrefixSum2D::new(&counts)
+    }
+
+    pub fn find_parts(&self, area: Region) -> Vec<u32> {
+        self.0.query(area)
+            .iter()
+            .filter_map(|entry| {
+                match entry.value() {
+                    Item::Part(part) => part.parse::<u32>().ok(),
+                    Item::Symbol(_) => None
+                }
+            })
+            .collect()
+    }
+
+    pub fn item_at(&self, point: &Point) -> Option<&Entry<Item>> {
+        self.0.at(point).into_iter().next()
+    }
+
+    // Every item touching the entry, including diagonally
+    pub fn adjacent_items(&self, entry: &Entry<Item>) -> Vec<&Entry<Item>> {
+        self.0.query(entry.region().expand(1))
+            .into_iter()
+            .filter(|other| other.region() != entry.region())
+            .collect()
+    }
+
+    pub fn is_real_part(&self, entry: &Entry<Item>) -> bool {
+        matches!(entry.value(), Item::Part(_))
+            && self.adjacent_items(entry).iter().any(|other| matches!(other.value(), Item::Symbol(_)))
+    }
+
+    // The product of the two parts next to a gear, None for anything else
+    pub fn gear_ratio(&self, entry: &Entry<Item>) -> Result<Option<u64>, AocError> {
+        if !matches!(entry.value(), Item::Symbol('*')) {
+            return Ok(None)
+        }
+        let parts = self.find_parts(entry.region().expand(1));
+        if parts.len() != 2 {
+            return Ok(None)
+        }
+        parts.iter()
+            .try_fold(1u64, |ratio, part| ratio.checked_mul(u64::from(*part)))
+            .map(Some)
+            .ok_or_else(|| AocError::Parse(format!("The gear ratio of {:?} doesn't fit a u64", parts)))
+    }
+
+    pub fn find_real_parts(&self) -> Result<Vec<u32>, AocError> {
+        let symbols = self.symbol_counts();
+        self.0.iter()
+            .filter_map(|entry| {
+                match entry.value() {
+                    Item::Part(part) => {
+                        if symbols.sum(&entry.region().expand(1)) > 0 {
+                            Some(part)
+                        } else {
+                            None
+                        }
+                    }
+                    Item::Symbol(_) => None
+                }
+            })
+            .map(|part| part.parse::<u32>().map_err(|_| AocError::Parse(format!("'{}' isn't a part number", part))))
+            .collect()
+    }
+
+    pub fn highlights(&self, width: usize, height: usize) -> Grid<Highlight> {
+        let symbols = self.symbol_counts();
+        let mut highlights = Grid::filled(width, height, Highlight::Empty);
+        for entry in self.0.iter() {
+            let region = entry.region();
+            let highlight = match entry.value() {
+                Item::Part(_) if symbols.sum(&region.expand(1)) > 0 => Highlight::Part,
+                Item::Part(_) => Highlight::NotPart,
+                Item::Symbol('*') if self.find_parts(region.expand(1)).len() == 2 => Highlight::Gear,
+                Item::Symbol(_) => Highlight::Symbol,
+            };
+            for point in region.points() {
+                if let Some(cell) = highlights.get_mut(&point) {
+                    *cell = highlight;
+                }
+            }
+        }
+        highlights
+    }
+
+    pub fn find_gear_ratios(&self) -> Result<Vec<u64>, AocError> {
+        self.0.iter()
+            .filter_map(|entry| self.gear_ratio(entry).transpose())
+            .collect()
+    }
+}
+
+// The answer's total, an error rather than a panic when it doesn't fit a u64
+pub fn total(values: impl IntoIterator<Item = u64>) -> Result<u64, AocError> {
+    values.into_iter()
+        .try_fold(0u64, |total, value| total.checked_add(value))
+        .ok_or_else(|| AocError::Parse(String::from("The total doesn't fit a u64")))
+}
+
+pub fn parse(input: &str) -> Result<ItemMatrix, AocError> {
+    let _span = tracing::info_span!("parse").entered();
+    input.lines().next().ok_or(AocError::Parse(String::from("Empty input provided")))?;
+
+    let mut matrix = ItemMatrix(SpatialIndex::new());
+    let lines = input.lines().enumerate();
+    for (y, line) in lines {
+        let mut iter = line.chars().enumerate().peekable();
+        while let Some((x, letter)) = iter.next() {
+            let point = Point::new(x as i64, y as i64);
+            if letter == '.' {
+                continue
+            } else if letter.is_numeric() {
+                let mut digits: Vec<char> = vec![letter];
+                while let Some((_, l2)) = &iter.peek() {
+                    if l2.is_numeric() {
+                        digits.push(*l2);
+                    } else {
+                        break
+                    }
+                    iter.next();
+                }
+                let str: String = digits.into_iter().collect();
+                // is_numeric takes more than 0-9, and a part number has to fit a u32
+                if str.parse::<u32>().is_err() {
+                    return Err(AocError::Lex { line: y + 1, message: format!("'{}' isn't a part number", str) })
+                }
+                matrix.add_part(str, point);
+            } else {
+                matrix.add_symbol(letter, point);
+            }
+        }
+    }
+
+    Ok(matrix)
+}
+
+// The schematic drawn as SVG: real parts green, numbers that aren't parts
+// grey, gears gold and every other symbol red
+pub fn render_svg(input: &str) -> Result<String, String> {
+    let schematic: Grid<char> = input.parse()?;
+    let matrix = parse(input)?;
+    let highlights = matrix.highlights(schematic.width(), schematic.height());
+    Ok(svg::render_grid(&schematic, 16, |point, c| {
+        let fill = match highlights.get(point) {
+            Some(Highlight::Part) => "#81c784",
+            Some(Highlight::NotPart) => "#bdbdbd",
+            Some(Highlight::Gear) => "#ffd54f",
+            Some(Highlight::Symbol) => "#e57373",
+            Some(Highlight::Empty) | None => "#fafafa",
+        };
+        match c {
+            '.' => SvgCell::new(fill),
+            c => SvgCell::with_text(fill, &c.to_string()),
+        }
+    }))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 3;
+    const PARTS: &'static [u32] = &[1, 2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(1, "4361"), (2, "467835")];
+    const APPROACH: &'static str = "Numbers and symbols in a spatial index; prefix sums of the symbols find real parts, region queries around each * find gears, on a grid of c cells with p numbers and g stars";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(1, "O(c + p)"), (2, "O(c + g)")];
+    const TAGS: &'static [&'static str] = &["grid", "spatial", "prefix-sum"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(1, "prefix-sum"), (2, "spatial-index")];
+    type Input = ItemMatrix;
+
+    fn parse(input: &str) -> Result<ItemMatrix, AocError> {
+        parse(input)
+    }
+
+    fn part_one(matrix: &ItemMatrix) -> Result<Answer, AocError> {
+        Ok(total(matrix.find_real_parts()?.into_iter().map(u64::from))?.into())
+    }
+
+    fn part_two(matrix: &ItemMatrix) -> Result<Answer, AocError> {
+        Ok(total(matrix.find_gear_ratios()?)?.into())
+    }
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day3::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+aoc_common::register_solution!(2023, 3, Day3);
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 3, &[1, 2], solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(1, EXAMPLE), Ok(String::from("4361")));
+        assert_eq!(solve(2, EXAMPLE), Ok(String::from("467835")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(solve(1, "").err(), Some(String::from("Empty input provided")));
+        assert_eq!(solve(1, "..12*\n.½..3\n").err(), Some(String::from("Line 2: '½' isn't a part number")));
+        assert_eq!(solve(1, "99999999999*\n").err(), Some(String::from("Line 1: '99999999999' isn't a part number")));
+    }
+
+    #[test]
+    fn test_large_parts() {
+        // past what a u32 holds, but every part and gear ratio is counted
+        assert_eq!(solve(2, "99999*99999\n"), Ok(String::from("9999800001")));
+        assert_eq!(solve(1, "4294967295#\n4294967295.\n"), Ok(String::from("8589934590")));
+        assert_eq!(solve(2, "4294967295*4294967295\n"), Ok(String::from("18446744065119617025")));
+        let gears = "4294967295*4294967295\n\n".repeat(2);
+        assert_eq!(solve(2, &gears).err(), Some(String::from("The total doesn't fit a u64")));
+    }
+}
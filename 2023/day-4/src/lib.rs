@@ -0,0 +1,238 @@
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+use std::collections::HashSet;
+use std::iter::Peekable;
+use std::cmp::min;
+
+#[derive(Debug)]
+enum Token {
+    Card(u32),
+    Number(u32),
+    Pipe,
+}
+
+#[derive(Debug)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Card {
+    number: u32,
+    winning_numbers: HashSet<u32>,
+    numbers: HashSet<u32>,
+}
+
+impl Card {
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn matches(&self) -> usize {
+        self.numbers.iter()
+            .filter(|n| self.winning_numbers.contains(n))
+            .count()
+    }
+    pub fn points(&self) -> u32 {
+        let matches = self.matches();
+        if matches == 0 {
+            0
+        } else {
+            let mut value = 1;
+            for _ in 1..matches {
+                value *= 2;
+            }
+            value
+        }
+    }
+}
+
+fn lex_contents(contents: &str) -> Vec<Token> {
+    let _span = tracing::info_span!("lex").entered();
+    let mut iter = contents.chars().peekable();
+    let mut tokens: Vec<Token> = vec![];
+    while let Some(c) = iter.peek() {
+        match c {
+            'C' => {
+                if let Some(card_num) = get_card_number(&mut iter) {
+                    tokens.push(Token::Card(card_num));
+                }
+            }
+            '0'..='9' => {
+                if let Some(num) = get_number(&mut iter) {
+                    tokens.push(Token::Number(num));
+                }
+            }
+            '|' => {
+                tokens.push(Token::Pipe);
+                iter.next();
+            }
+            _ => _ = iter.next()
+        }
+    }
+    tokens
+}
+
+// A number too big for a u32 isn't one, but all of its digits are consumed
+fn get_number<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Option<u32> {
+    let mut number = Some(iter.next()?.to_digit(10)?);
+    while let Some(digit) = iter.peek().and_then(|c| c.to_digit(10)) {
+        number = number.and_then(|n| n.checked_mul(10)?.checked_add(digit));
+        iter.next();
+    }
+    number
+}
+
+fn get_card_number<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Option<u32> {
+    const CARD: &str = "Card ";
+    let mut card_num = None;
+    let mut i = 0;
+    while let Some(c) = iter.peek() {
+        // Check if prefix is not "Card "
+        if i < CARD.len() {
+            if CARD.chars().nth(i) != Some(*c) {
+                break;
+            } else {
+                i += 1;
+                iter.next();
+            }
+        } else {
+            if *c == ' ' {
+                iter.next();
+            } else {
+                card_num = get_number(iter);
+                break
+            }
+        }
+    }
+    card_num
+}
+
+pub fn parse_contents(contents: &str) -> Vec<Card> {
+    let _span = tracing::info_span!("parse").entered();
+    let tokens = lex_contents(contents);
+    let mut cards: Vec<Card> = vec![];
+    let mut iter = tokens.iter().peekable();
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Card(num) => {
+                iter.next();
+                cards.push(parse_card(&mut iter, *num));
+            }
+            _ => _ = iter.next()
+        }
+    }
+
+    cards
+}
+
+fn parse_card<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>, num: u32) -> Card {
+    let mut card = Card { number: num, ..Card::default() };
+    let mut parsing_winning = true;
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Card(_) => {
+                break
+            }
+            Token::Number(num) => {
+                if parsing_winning {
+                    card.winning_numbers.insert(*num);
+                } else {
+                    card.numbers.insert(*num);
+                }
+                iter.next();
+            }
+            Token::Pipe => {
+                parsing_winning = false;
+                iter.next();
+            }
+        }
+    }
+    card
+}
+
+pub fn get_card_point_total(cards: &[Card]) -> u32 {
+    cards
+        .iter()
+        .map(|c| c.points())
+        .sum()
+}
+
+pub fn get_card_copies_total(cards: &[Card]) -> u32 {
+    let mut copies: Vec<(&Card, u32)> = cards
+        .iter()
+        .map(|c| (c, 1))
+        .collect();
+    
+    let mut i = 0;
+    while i < copies.len() {
+        let (card, instance_count) = copies[i];
+        let matches = card.matches();
+        if matches > 0 {
+            let from = i + 1;
+            let to = min(copies.len(), from + matches);
+            for (_, count) in &mut copies[from..to] {
+                *count += instance_count;
+            }
+        }
+        i += 1;
+    }
+    copies
+        .iter()
+        .map(|(_, count)| count)
+        .sum()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 4;
+    const PARTS: &'static [u32] = &[1, 2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(1, "13"), (2, "30")];
+    const APPROACH: &'static str = "Winning numbers in a set per card, then copies carried forward over the following cards, n cards of k numbers";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(1, "O(n·k)"), (2, "O(n·k)")];
+    const TAGS: &'static [&'static str] = &["sets", "dynamic-programming"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(1, "winning-set"), (2, "copy-counts")];
+    type Input = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Vec<Card>, AocError> {
+        Ok(parse_contents(input))
+    }
+
+    fn part_one(cards: &Vec<Card>) -> Result<Answer, AocError> {
+        Ok(get_card_point_total(cards).into())
+    }
+
+    fn part_two(cards: &Vec<Card>) -> Result<Answer, AocError> {
+        Ok(get_card_copies_total(cards).into())
+    }
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day4::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+aoc_common::register_solution!(2023, 4, Day4);
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 4, &[1, 2], solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    // the tokens are checked in under src/snapshots/, `cargo insta review` to accept changes
+    #[test]
+    fn test_lex_example() {
+        insta::assert_debug_snapshot!("lex_example", lex_contents(EXAMPLE));
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(1, EXAMPLE), Ok(String::from("13")));
+        assert_eq!(solve(2, EXAMPLE), Ok(String::from("30")));
+    }
+}
@@ -0,0 +1,114 @@
+// A bar per map layer showing which stretches of the numbers its lines cover
+// and which pass through unchanged, all on one scale up to the largest number
+// any line mentions, so gaps and stray lines stand out. Overlapping lines are
+// counted too, since the puzzle never has any.
+
+use std::ops::Range;
+
+use aoc_common::intervals::DisjointIntervalSet;
+
+use crate::{NumberMapper, RangeMap};
+
+// From nothing covered to all of it
+const SHADES: &[char] = &[' ', '.', ':', '=', '#'];
+
+fn shade(covered: u64, total: u64) -> char {
+    match covered {
+        0 => SHADES[0],
+        _ if covered == total => SHADES[SHADES.len() - 1],
+        _ => SHADES[1 + (covered as u128 * (SHADES.len() - 2) as u128 / total as u128) as usize],
+    }
+}
+
+// Pairs of lines whose sources overlap
+fn overlaps(range_map: &RangeMap) -> usize {
+    let mut sources: Vec<&Range<u64>> = range_map.ranges.iter().map(|pair| &pair.source).collect();
+    sources.sort_by_key(|source| source.start);
+    let mut count = 0;
+    for (idx, source) in sources.iter().enumerate() {
+        count += sources[idx + 1..].iter().take_while(|other| other.start < source.end).filter(|other| !other.is_empty()).count();
+    }
+    count
+}
+
+// `width` columns per bar
+pub fn coverage(mapper: &NumberMapper, width: usize) -> Result<String, String> {
+    let layers = mapper.layers()?;
+    let width = width.max(1) as u64;
+    let domain = layers.iter()
+        .flat_map(|range_map| range_map.ranges.iter().map(|pair| pair.source.end.max(pair.target.end)))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let name_width = layers.iter().map(|range_map| range_map.name().len()).max().unwrap_or(0);
+
+    let mut out = format!(
+        "0..{} in {} columns of about {} ('{}' all mapped by a line, '{}' none)\n",
+        domain,
+        width,
+        domain.div_ceil(width),
+        SHADES[SHADES.len() - 1],
+        SHADES[0],
+    );
+    for range_map in layers {
+        let covered: DisjointIntervalSet<u64> = range_map.ranges.iter().map(|pair| pair.source.clone()).collect();
+        let bar: String = (0..width)
+            .map(|column| {
+                let start = (domain as u128 * column as u128 / width as u128) as u64;
+                let end = (domain as u128 * (column + 1) as u128 / width as u128) as u64;
+                let gaps = covered.complement(&(start..end)).covered_len();
+                shade(end - start - gaps, end - start)
+            })
+            .collect();
+        let in_domain = domain - covered.complement(&(0..domain)).covered_len();
+        out.push_str(&format!(
+            "{:<name_width$} |{}| {:>3}% mapped",
+            range_map.name(),
+            bar,
+            in_domain as u128 * 100 / domain as u128,
+        ));
+        match overlaps(range_map) {
+            0 => out.push('\n'),
+            count => out.push_str(&format!(", {} overlapping line pair(s)\n", count)),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_contents, EXAMPLE};
+
+    #[test]
+    fn test_shade() {
+        assert_eq!(shade(0, 10), ' ');
+        assert_eq!(shade(1, 10), '.');
+        assert_eq!(shade(5, 10), ':');
+        assert_eq!(shade(9, 10), '=');
+        assert_eq!(shade(10, 10), '#');
+    }
+
+    #[test]
+    fn test_coverage() {
+        let (_, mapper) = parse_contents(EXAMPLE).unwrap();
+        let out = coverage(&mapper, 10).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "0..100 in 10 columns of about 10 ('#' all mapped by a line, ' ' none)");
+        // 50..100, half of the scale, in five whole columns
+        assert_eq!(lines[1], "seed-to-soil            |     #####|  50% mapped");
+        // 45..100, so the column for 40..50 is half covered
+        assert_eq!(lines[5], "light-to-temperature    |    :#####|  55% mapped");
+        assert_eq!(lines[6], "temperature-to-humidity |#######   |  70% mapped");
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n0 10 10\n0 15 10\n0 12 1\n0 30 5\n";
+        let (_, mapper) = parse_contents(input).unwrap();
+        assert_eq!(mapper.layers().err(), Some(String::from("No soil map")));
+        // 10..20 overlaps 12..13 and 15..25, and 30..35 none of them
+        assert_eq!(overlaps(&mapper.maps_by_source[&crate::ValueKind::Seed]), 2);
+    }
+}
@@ -0,0 +1,236 @@
+// Steps a seed, or a range of them, through the maps one layer at a time,
+// showing which line of each map every piece went through. Every step is
+// worked out up front, so going backward is only moving the cursor.
+
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+
+use aoc_common::intervals::DisjointIntervalSet;
+
+use crate::{NumberMapper, RangeMap};
+
+// One piece of the ranges going into a layer and where it came out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece {
+    pub source: Range<u64>,
+    pub target: Range<u64>,
+    // the map's line as written in the input, destination start, source start
+    // and length, or None where no line covers the piece and it's kept as is
+    pub line: Option<(u64, u64, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    pub name: String,
+    pub from: Vec<Range<u64>>,
+    pub pieces: Vec<Piece>,
+    // the pieces' targets merged, which is what the next layer gets
+    pub to: Vec<Range<u64>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Debugger {
+    pub layers: Vec<Layer>,
+    pub position: usize,
+}
+
+fn layer(range_map: &RangeMap, from: &[Range<u64>]) -> Layer {
+    let pieces: Vec<Piece> = from.iter()
+        .flat_map(|range| range_map.pairs_for(range))
+        .map(|pair| {
+            let line = range_map.ranges.iter()
+                .find(|original| original.source.contains(&pair.source.start))
+                .map(|original| (original.target.start, original.source.start, original.source.end - original.source.start));
+            Piece { source: pair.source, target: pair.target, line }
+        })
+        .collect();
+    let to = pieces.iter().map(|piece| piece.target.clone()).collect::<DisjointIntervalSet<u64>>().into_ranges();
+    tracing::trace!("{:?} -> {:?}: {} pieces", range_map.source_kind, range_map.target_kind, pieces.len());
+    Layer {
+        name: range_map.name(),
+        from: from.to_vec(),
+        pieces,
+        to,
+    }
+}
+
+impl Debugger {
+    // Every layer from seeds to locations for the seeds in `seeds`
+    pub fn new(mapper: &NumberMapper, seeds: Range<u64>) -> Result<Debugger, String> {
+        let mut layers = vec![];
+        let mut ranges = vec![seeds];
+        for range_map in mapper.layers()? {
+            let next = layer(range_map, &ranges);
+            ranges = next.to.clone();
+            layers.push(next);
+        }
+        Ok(Debugger { layers, position: 0 })
+    }
+
+    pub fn forward(&mut self) {
+        self.position = (self.position + 1).min(self.layers.len().saturating_sub(1));
+    }
+
+    pub fn back(&mut self) {
+        self.position = self.position.saturating_sub(1);
+    }
+
+    // To a layer counting from 1, as `show` numbers them
+    pub fn jump(&mut self, layer: usize) -> Result<(), String> {
+        if layer == 0 || layer > self.layers.len() {
+            return Err(format!("No layer {}, there are {}", layer, self.layers.len()))
+        }
+        self.position = layer - 1;
+        Ok(())
+    }
+
+    pub fn show(&self) -> String {
+        let Some(layer) = self.layers.get(self.position) else { return String::from("No layers\n") };
+        let ranges = |ranges: &[Range<u64>]| ranges.iter().map(|r| format!("{}..{}", r.start, r.end)).collect::<Vec<_>>().join(", ");
+        let mut out = format!("layer {} of {}: {}\n", self.position + 1, self.layers.len(), layer.name);
+        out.push_str(&format!("  from {}\n", ranges(&layer.from)));
+        for piece in layer.pieces.iter() {
+            let how = match piece.line {
+                Some((target, source, length)) => format!("by {} {} {}", target, source, length),
+                None => String::from("no line matched, kept as is"),
+            };
+            out.push_str(&format!(
+                "    {}..{} -> {}..{}  {}\n",
+                piece.source.start,
+                piece.source.end,
+                piece.target.start,
+                piece.target.end,
+                how,
+            ));
+        }
+        out.push_str(&format!("  to {}\n", ranges(&layer.to)));
+        out
+    }
+}
+
+// Reads `seed` or `start..end`, the end exclusive
+pub fn parse_seeds(text: &str) -> Result<Range<u64>, String> {
+    let number = |text: &str| text.trim().parse::<u64>().map_err(|_| format!("Invalid seed '{}'", text.trim()));
+    match text.split_once("..") {
+        Some((start, end)) => {
+            let range = number(start)?..number(end)?;
+            if range.is_empty() {
+                return Err(format!("Empty range {}", text))
+            }
+            Ok(range)
+        }
+        None => {
+            let seed = number(text)?;
+            Ok(seed..seed + 1)
+        }
+    }
+}
+
+// Shows a layer and waits for a command: enter or n for the next layer, b to
+// go back, a number to jump to that layer and q to quit
+pub fn repl<R: BufRead, W: Write>(debugger: &mut Debugger, input: R, out: &mut W) -> io::Result<()> {
+    write!(out, "{}> ", debugger.show())?;
+    out.flush()?;
+    for line in input.lines() {
+        match line?.trim() {
+            "q" | "quit" => return Ok(()),
+            "" | "n" | "next" => debugger.forward(),
+            "b" | "back" => debugger.back(),
+            command => match command.parse::<usize>() {
+                Ok(layer) => {
+                    if let Err(err) = debugger.jump(layer) {
+                        writeln!(out, "{}", err)?;
+                    }
+                }
+                Err(_) => writeln!(out, "Unknown command '{}': enter or n, b, a layer number, q", command)?,
+            },
+        }
+        write!(out, "{}> ", debugger.show())?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_contents, ValueKind, EXAMPLE};
+
+    fn debugger(seeds: Range<u64>) -> Debugger {
+        let (_, mapper) = parse_contents(EXAMPLE).unwrap();
+        Debugger::new(&mapper, seeds).unwrap()
+    }
+
+    #[test]
+    fn test_seed() {
+        let debugger = debugger(79..80);
+        assert_eq!(debugger.layers.len(), 7);
+        assert_eq!(debugger.layers[0].pieces, vec![Piece { source: 79..80, target: 81..82, line: Some((52, 50, 48)) }]);
+        // soil 81 isn't in any soil-to-fertilizer line
+        assert_eq!(debugger.layers[1].pieces[0].line, None);
+        // the location the layers end at is the one location_for_seed gives
+        assert_eq!(debugger.layers[6].to, vec![82..83]);
+    }
+
+    #[test]
+    fn test_range() {
+        let debugger = debugger(79..93);
+        let (ranges, mapper) = crate::parse_content_ranges(EXAMPLE).unwrap();
+        let expected = mapper.map_range(&ranges[0], ValueKind::Seed, ValueKind::Location).unwrap();
+        assert_eq!(debugger.layers[6].to, expected);
+        // pieces cover the layer's input exactly
+        for layer in debugger.layers.iter() {
+            let length = |ranges: &mut dyn Iterator<Item = Range<u64>>| ranges.map(|r| r.end - r.start).sum::<u64>();
+            assert_eq!(length(&mut layer.pieces.iter().map(|piece| piece.source.clone())), length(&mut layer.from.iter().cloned()));
+        }
+    }
+
+    #[test]
+    fn test_moves() {
+        let mut debugger = debugger(79..80);
+        debugger.back();
+        assert_eq!(debugger.position, 0);
+        for _ in 0..10 {
+            debugger.forward();
+        }
+        assert_eq!(debugger.position, 6);
+        assert_eq!(debugger.jump(3), Ok(()));
+        assert_eq!(debugger.position, 2);
+        assert_eq!(debugger.jump(0), Err(String::from("No layer 0, there are 7")));
+        assert_eq!(debugger.jump(8), Err(String::from("No layer 8, there are 7")));
+    }
+
+    #[test]
+    fn test_show() {
+        let mut debugger = debugger(79..80);
+        assert_eq!(
+            debugger.show(),
+            "layer 1 of 7: seed-to-soil\n  from 79..80\n    79..80 -> 81..82  by 52 50 48\n  to 81..82\n",
+        );
+        debugger.forward();
+        assert!(debugger.show().contains("    81..82 -> 81..82  no line matched, kept as is\n"));
+    }
+
+    #[test]
+    fn test_parse_seeds() {
+        assert_eq!(parse_seeds("79"), Ok(79..80));
+        assert_eq!(parse_seeds("79..93"), Ok(79..93));
+        assert_eq!(parse_seeds("9..9"), Err(String::from("Empty range 9..9")));
+        assert_eq!(parse_seeds("x..9"), Err(String::from("Invalid seed 'x'")));
+    }
+
+    #[test]
+    fn test_repl() {
+        let mut debugger = debugger(79..80);
+        let mut out = vec![];
+        repl(&mut debugger, "n\n\nb\n7\n9\nzz\nq\nn\n".as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let shown: Vec<&str> = out.lines().filter(|line| line.contains("layer ") && line.contains(" of 7")).collect();
+        assert_eq!(shown.len(), 7);
+        assert!(shown[3].contains("layer 2 of 7"), "{}", out);
+        assert!(out.contains("No layer 9, there are 7\n"));
+        assert!(out.contains("Unknown command 'zz'"));
+        // stopped at q, so the last n never ran
+        assert_eq!(debugger.position, 6);
+    }
+}
@@ -0,0 +1,1321 @@
+use aoc_common::answer::Answer;
+use aoc_common::error::AocError;
+use aoc_common::intervals::DisjointIntervalSet;
+use aoc_common::progress::Progress;
+use aoc_common::solution::Solution;
+use aoc_common::verify::Verifier;
+use std::collections::HashMap;
+use std::cmp::{max, min, Ord};
+use std::iter::Peekable;
+use std::ops::{Add, Range, Sub};
+use std::str::FromStr;
+use strum::EnumString;
+#[cfg(test)]
+use proptest::prelude::*;
+
+pub mod coverage;
+pub mod debugger;
+
+#[cfg(test)]
+const EXAMPLE: &str = include_str!("../example.txt");
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[strum(serialize_all = "lowercase")]
+enum ValueKind {
+    Seed,
+    Soil,
+    Fertilizer,
+    Water,
+    Light,
+    Temperature,
+    Humidity,
+    Location,
+}
+
+impl ValueKind {
+    fn name(&self) -> String {
+        format!("{:?}", self).to_lowercase()
+    }
+}
+
+// How a map finds the ranges a value falls in. The answers don't depend on
+// it, which `aoc diff` checks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Lookup {
+    // the balanced interval tree
+    Tree,
+    // a binary search over the ranges sorted by source start
+    Sorted,
+}
+
+pub const LOOKUPS: &[&str] = &["tree", "sorted"];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Value {
+    kind: ValueKind,
+    number: u64,
+}
+
+// Numeric types usable as interval endpoints, e.g. u64 for day 5 or
+// i64 for coordinates that can go negative
+pub trait RangeNum: Ord + Copy + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Ord + Copy + Add<Output = T> + Sub<Output = T>> RangeNum for T {}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RangePair<T = u64> {
+    pub source: Range<T>,
+    pub target: Range<T>,
+}
+
+impl<T: RangeNum> RangePair<T> {
+    fn subrange(&self, range: &Range<T>) -> Option<RangePair<T>> {
+        // checking that the subrange is contained within the source range
+        if self.source.start <= range.start && self.source.end >= range.end {
+            let start_offset = range.start - self.source.start;
+            let range_length = range.end - range.start;
+            let target_start = self.target.start + start_offset;
+            let target_end = target_start + range_length;
+            Some(RangePair { source: range.clone(), target: target_start..target_end })
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RangeTreeNode<T = u64> {
+    range: RangePair<T>,
+    max: T,
+    left: Option<Box<RangeTreeNode<T>>>,
+    right: Option<Box<RangeTreeNode<T>>>,
+}
+
+// Ranges are half-open, so ranges that merely touch (0..5 and 5..10) don't
+// overlap, and an empty range never overlaps anything
+fn ranges_overlap<T: Ord + Copy>(r1: &Range<T>, r2: &Range<T>) -> bool {
+    max(r1.start, r2.start) < min(r1.end, r2.end)
+}
+
+fn range_intersection<T: Ord + Copy>(r1: &Range<T>, r2: &Range<T>) -> Option<Range<T>> {
+    if ranges_overlap(r1, r2) {
+        let start = max(r1.start, r2.start);
+        let end = min(r1.end, r2.end);
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+impl<T: RangeNum> RangeTreeNode<T> {
+    fn find_intersections(&self, range: &Range<T>) -> Vec<RangePair<T>> {
+        let mut intersections: Vec<RangePair<T>> = vec![];
+
+        if let Some(intersection) = range_intersection(&self.range.source, range) {
+            if let Some(subrange) = self.range.subrange(&intersection) {
+                intersections.push(subrange);
+            }
+        }
+
+        if let Some(left) = &self.left {
+            if left.max > range.start {
+                for intersection in left.find_intersections(range) {
+                    intersections.push(intersection);
+                }
+            }
+        }
+
+        // everything to the right starts at or after this node's start
+        if self.range.source.start >= range.end {
+            return intersections;
+        }
+
+        if let Some(right) = &self.right {
+            if right.max > range.start {
+                for intersection in right.find_intersections(range) {
+                    intersections.push(intersection);
+                }
+            }
+        }
+
+        intersections
+    }
+
+    // The middle range becomes the node and each half its subtrees, so the
+    // heights of the two sides never differ by more than one
+    fn from_sorted(ranges: &[RangePair<T>]) -> Option<Box<RangeTreeNode<T>>> {
+        if ranges.is_empty() {
+            return None
+        }
+        let middle = ranges.len() / 2;
+        let left = RangeTreeNode::from_sorted(&ranges[..middle]);
+        let right = RangeTreeNode::from_sorted(&ranges[middle + 1..]);
+        let max = [left.as_ref().map(|node| node.max), right.as_ref().map(|node| node.max)]
+            .into_iter()
+            .flatten()
+            .fold(ranges[middle].source.end, |a, b| a.max(b));
+        Some(Box::new(RangeTreeNode { range: ranges[middle].clone(), max, left, right }))
+    }
+}
+
+// Only the tests build trees one insert at a time, to compare with the
+// balanced ones, or walk and measure them
+#[cfg(test)]
+impl<T: RangeNum> RangeTreeNode<T> {
+    fn new(range: &RangePair<T>) -> RangeTreeNode<T> {
+        let max = range.source.end;
+        RangeTreeNode { 
+            range: range.clone(), 
+            max, 
+            left: None, 
+            right: None 
+        }
+    }
+
+    fn insert(&mut self, range: &RangePair<T>) {
+        if self.max < range.source.end {
+            self.max = range.source.end;
+        }
+
+        if range.source.start < self.range.source.start {
+            if let Some(left) = &mut self.left {
+                left.insert(range);
+            } else {
+                self.left = Some(Box::new(RangeTreeNode::new(range)));
+            }
+        } else {
+            if let Some(right) = &mut self.right {
+                right.insert(range);
+            } else {
+                self.right = Some(Box::new(RangeTreeNode::new(range)));
+            }
+        }
+    }
+
+    fn iter(&self) -> RangeTreeIter<'_, T> {
+        let mut iter = RangeTreeIter { stack: vec![] };
+        iter.push_left(self);
+        iter
+    }
+
+    fn height(&self) -> usize {
+        let left = self.left.as_ref().map_or(0, |node| node.height());
+        let right = self.right.as_ref().map_or(0, |node| node.height());
+        1 + left.max(right)
+    }
+}
+
+// Interval tree over the source ranges of a map. Maps are built once and
+// queried for every seed, so the tree is built balanced up front rather than
+// one insert at a time, which degenerates into a list for sorted input.
+#[derive(Debug)]
+pub struct RangeTree<T = u64> {
+    root: Option<Box<RangeTreeNode<T>>>,
+}
+
+impl<T: RangeNum> RangeTree<T> {
+    // The ranges have to be sorted by source start
+    pub fn from_sorted(ranges: &[RangePair<T>]) -> RangeTree<T> {
+        debug_assert!(ranges.windows(2).all(|pair| pair[0].source.start <= pair[1].source.start));
+        RangeTree { root: RangeTreeNode::from_sorted(ranges) }
+    }
+
+    pub fn find_intersections(&self, range: &Range<T>) -> Vec<RangePair<T>> {
+        match &self.root {
+            Some(root) => root.find_intersections(range),
+            None => vec![],
+        }
+    }
+
+    pub fn iter(&self) -> RangeTreeIter<'_, T> {
+        let mut iter = RangeTreeIter { stack: vec![] };
+        if let Some(root) = &self.root {
+            iter.push_left(root);
+        }
+        iter
+    }
+}
+
+#[cfg(test)]
+impl<T: RangeNum> RangeTree<T> {
+    fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |root| root.height())
+    }
+}
+
+// In-order traversal of the tree, yielding ranges sorted by source start
+pub struct RangeTreeIter<'a, T = u64> {
+    stack: Vec<&'a RangeTreeNode<T>>,
+}
+
+impl<'a, T> RangeTreeIter<'a, T> {
+    fn push_left(&mut self, node: &'a RangeTreeNode<T>) {
+        let mut current = Some(node);
+        while let Some(node) = current {
+            self.stack.push(node);
+            current = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for RangeTreeIter<'a, T> {
+    type Item = &'a RangePair<T>;
+
+    fn next(&mut self) -> Option<&'a RangePair<T>> {
+        let node = self.stack.pop()?;
+        if let Some(right) = &node.right {
+            self.push_left(right);
+        }
+        Some(&node.range)
+    }
+}
+
+impl<'a, T: RangeNum> IntoIterator for &'a RangeTree<T> {
+    type Item = &'a RangePair<T>;
+    type IntoIter = RangeTreeIter<'a, T>;
+
+    fn into_iter(self) -> RangeTreeIter<'a, T> {
+        self.iter()
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct RangeMap {
+    source_kind: ValueKind,
+    target_kind: ValueKind,
+    ranges: Vec<RangePair>,
+    // rebuilt from the ranges, so there's nothing new in it to dump
+    #[cfg_attr(feature = "serde", serde(skip))]
+    range_tree: Option<RangeTree>,
+}
+
+impl RangeMap {
+    fn new(
+        source_kind: ValueKind, 
+        target_kind: ValueKind, 
+        ranges: Vec<RangePair>
+    ) -> RangeMap {
+        RangeMap::with_lookup(source_kind, target_kind, ranges, Lookup::Tree)
+    }
+
+    // Without a tree the ranges are kept sorted instead, which the lookups
+    // rely on
+    fn with_lookup(
+        source_kind: ValueKind,
+        target_kind: ValueKind,
+        ranges: Vec<RangePair>,
+        lookup: Lookup
+    ) -> RangeMap {
+        let mut sorted = ranges.clone();
+        sorted.sort_by_key(|pair| pair.source.start);
+        match lookup {
+            Lookup::Tree => RangeMap {
+                source_kind,
+                target_kind,
+                range_tree: Some(RangeTree::from_sorted(&sorted)),
+                ranges,
+            },
+            Lookup::Sorted => RangeMap { source_kind, target_kind, ranges: sorted, range_tree: None },
+        }
+    }
+
+    // As the map's heading in the input, e.g. seed-to-soil
+    fn name(&self) -> String {
+        format!("{}-to-{}", self.source_kind.name(), self.target_kind.name())
+    }
+
+    fn lookup(&self) -> Lookup {
+        match self.range_tree {
+            Some(_) => Lookup::Tree,
+            None => Lookup::Sorted,
+        }
+    }
+
+    fn value_for(&self, value: &Value) -> Option<Value> {
+        if value.kind != self.source_kind {
+            return None
+        }
+
+        let number = value.number;
+        let target_number = match &self.range_tree {
+            Some(tree) => tree.find_intersections(&(number..number.saturating_add(1)))
+                .first()
+                .map(|pair| pair.target.start),
+            None => {
+                let after = self.ranges.partition_point(|pair| pair.source.start <= number);
+                after.checked_sub(1)
+                    .map(|idx| &self.ranges[idx])
+                    .filter(|pair| pair.source.contains(&number))
+                    .map(|pair| pair.target.start + (number - pair.source.start))
+            }
+        };
+        Some(Value { kind: self.target_kind, number: target_number.unwrap_or(number) })
+    }
+
+    fn ranges_for(&self, range: &Range<u64>) -> Vec<Range<u64>> {
+        self.pairs_for(range)
+            .into_iter()
+            .map(|pair| pair.target)
+            .collect()
+    }
+
+    // Splits the range into the pieces covered by each mapping plus the
+    // identity-mapped gaps between them, in source order
+    fn pairs_for(&self, range: &Range<u64>) -> Vec<RangePair> {
+        let mut pairs = match &self.range_tree {
+            Some(tree) => tree.find_intersections(range),
+            None => {
+                // the sources don't overlap, so sorted by start they're sorted by end too
+                let first = self.ranges.partition_point(|pair| pair.source.end <= range.start);
+                self.ranges[first..].iter()
+                    .take_while(|pair| pair.source.start < range.end)
+                    .filter_map(|pair| pair.subrange(&range_intersection(&pair.source, range)?))
+                    .collect()
+            }
+        };
+
+        // anything no mapping covers maps to the same value
+        let covered: DisjointIntervalSet<u64> = pairs.iter()
+            .map(|pair| pair.source.clone())
+            .collect();
+        for gap in covered.complement(range).into_ranges() {
+            pairs.push(RangePair { source: gap.clone(), target: gap });
+        }
+        pairs.sort_by_key(|pair| pair.source.start);
+
+        pairs
+    }
+
+    // A single map equivalent to applying `self` and then `other`
+    fn compose(&self, other: &RangeMap) -> RangeMap {
+        debug_assert_eq!(self.target_kind, other.source_kind);
+
+        let mut ranges: Vec<RangePair> = vec![];
+        for pair in self.pairs_for(&(0..u64::MAX)) {
+            for piece in other.pairs_for(&pair.target) {
+                // values that pass through both maps unchanged don't need a range
+                if pair.source == pair.target && piece.source == piece.target {
+                    continue
+                }
+                let offset = piece.source.start - pair.target.start;
+                let length = piece.source.end - piece.source.start;
+                let source_start = pair.source.start + offset;
+                ranges.push(RangePair {
+                    source: source_start..(source_start + length),
+                    target: piece.target,
+                });
+            }
+        }
+
+        RangeMap::with_lookup(self.source_kind, other.target_kind, ranges, self.lookup())
+    }
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NumberMapper {
+    maps_by_source: HashMap<ValueKind, RangeMap>,
+}
+
+impl NumberMapper {
+    fn insert(&mut self, range_map: RangeMap) {
+        self.maps_by_source.insert(range_map.source_kind, range_map);
+    }
+
+    // The same maps, looking values up the given way
+    pub fn with_lookup(self, lookup: Lookup) -> NumberMapper {
+        let maps_by_source = self.maps_by_source.into_iter()
+            .map(|(kind, map)| (kind, RangeMap::with_lookup(map.source_kind, map.target_kind, map.ranges, lookup)))
+            .collect();
+        NumberMapper { maps_by_source }
+    }
+
+    // The maps in the order a seed goes through them, seeds to locations
+    fn layers(&self) -> Result<Vec<&RangeMap>, String> {
+        self.layers_between(ValueKind::Seed, ValueKind::Location)
+    }
+
+    // The maps a value goes through from one kind to the other, in order.
+    // Each kind has a single map from it, so a walk through more maps than
+    // there are has gone round a loop and would never get there.
+    fn layers_between(&self, source_kind: ValueKind, target_kind: ValueKind) -> Result<Vec<&RangeMap>, String> {
+        let mut layers = vec![];
+        let mut kind = source_kind;
+        while kind != target_kind {
+            let range_map = self.maps_by_source.get(&kind).ok_or(format!("No {} map", kind.name()))?;
+            if layers.len() == self.maps_by_source.len() {
+                return Err(format!("The maps from {} go round in a loop without reaching {}", source_kind.name(), target_kind.name()))
+            }
+            kind = range_map.target_kind;
+            layers.push(range_map);
+        }
+        Ok(layers)
+    }
+
+    // Follows one seed through every map a layer at a time, without the
+    // flattening the solutions use
+    pub fn location_for_seed(&self, seed: u64) -> Option<u64> {
+        self.map(&Value { kind: ValueKind::Seed, number: seed }, ValueKind::Location)
+            .map(|value| value.number)
+    }
+
+    fn map(
+        &self,
+        value: &Value, 
+        target_kind: ValueKind
+    ) -> Option<Value> {
+        self.layers_between(value.kind, target_kind)
+            .ok()?
+            .into_iter()
+            .try_fold(*value, |value, range_map| range_map.value_for(&value))
+    }
+
+    // Composes every map between the two kinds into one, so a lookup is a
+    // single tree query instead of a walk through each layer
+    fn flatten(&self, source_kind: ValueKind, target_kind: ValueKind) -> Result<RangeMap, String> {
+        let lookup = self.maps_by_source.values().next().map_or(Lookup::Tree, |map| map.lookup());
+        let mut flattened = RangeMap::with_lookup(source_kind, source_kind, vec![], lookup);
+        for range_map in self.layers_between(source_kind, target_kind)? {
+            flattened = flattened.compose(range_map);
+            aoc_common::event!("layer", map = range_map.name(), flattened_lines = flattened.ranges.len());
+        }
+        Ok(flattened)
+    }
+
+    fn map_range(
+        &self,
+        range: &Range<u64>,
+        source_kind: ValueKind,
+        target_kind: ValueKind
+    ) -> Result<Vec<Range<u64>>, String> {
+        let mut mapped_ranges = vec![range.clone()];
+        for range_map in self.layers_between(source_kind, target_kind)? {
+            if mapped_ranges.is_empty() {
+                break
+            }
+            tracing::debug!("mapping {:?} -> {:?}", range_map.source_kind, range_map.target_kind);
+            for range in &mapped_ranges {
+                tracing::trace!("from [{}..{}] ({})", range.start, range.end, range.end - range.start);
+            }
+            // merging keeps overlapping pieces from being mapped twice by the next layer
+            let from = mapped_ranges.clone();
+            mapped_ranges = mapped_ranges.iter()
+                .flat_map(|r| range_map.ranges_for(r))
+                .collect::<DisjointIntervalSet<u64>>()
+                .into_ranges();
+            aoc_common::event!("layer", map = range_map.name(), from = format_ranges(&from), to = format_ranges(&mapped_ranges));
+            for range in &mapped_ranges {
+                tracing::trace!("to [{}..{}] ({})", range.start, range.end, range.end - range.start);
+            }
+        }
+        Ok(mapped_ranges)
+    }
+}
+
+#[derive(Debug)]
+enum Token {
+    Seeds,
+    Number(u64),
+    Map(ValueKind, ValueKind),
+    Newline,
+}
+
+impl Token {
+    fn as_number(&self) -> Option<u64> {
+        match self {
+            Token::Number(num) => Some(*num),
+            _ => None
+        }
+    }
+}
+
+fn lex_contents(contents: &str) -> Result<Vec<Token>, AocError> {
+    let _span = tracing::info_span!("lex").entered();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut iter = contents.chars().peekable();
+    let mut line = 1;
+    while let Some(&c) = iter.peek() {
+        match c {
+            'a'..='z' => {
+                if let Some(token) = lex_alphabetical(&mut iter).map_err(|message| AocError::Lex { line, message })? {
+                    tokens.push(token);
+                }
+            }
+            '0'..='9' => {
+                tokens.push(lex_number(&mut iter).map_err(|message| AocError::Lex { line, message })?);
+            }
+            '\n' => {
+                tokens.push(Token::Newline);
+                iter.next();
+                line += 1;
+            }
+            _ => _ = iter.next()
+        }
+    }
+    Ok(tokens)
+}
+
+// The seeds heading or a map's, skipping any other word; a map between
+// kinds that aren't in the almanac is an error
+fn lex_alphabetical<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Result<Option<Token>, String> {
+    let Some(first) = iter.next() else { return Ok(None) };
+    let mut word = first.to_string();
+    while let Some(&letter) = iter.peek() {
+        if !letter.is_alphabetic() && letter != ' ' && letter != '-' {
+            break;
+        }
+        word.push(letter);
+        iter.next();
+    }
+
+    if word.contains("seeds") {
+        Ok(Some(Token::Seeds))
+    } else if word.contains("map") {
+        let heading = word.split(' ').next().unwrap_or_default();
+        let kinds = heading.split_once("-to-")
+            .and_then(|(source, destination)| Some((ValueKind::from_str(source).ok()?, ValueKind::from_str(destination).ok()?)));
+        match kinds {
+            Some((source, destination)) => Ok(Some(Token::Map(source, destination))),
+            None => Err(format!("'{}' isn't a map between two kinds of value", heading)),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+// All of a number's digits, which have to fit in a u64
+fn lex_number<T: Iterator<Item = char>>(iter: &mut Peekable<T>) -> Result<Token, String> {
+    let mut digits = String::new();
+    while let Some(digit) = iter.peek().filter(|c| c.is_ascii_digit()) {
+        digits.push(*digit);
+        iter.next();
+    }
+    digits.parse().map(Token::Number).map_err(|_| format!("{} is too big a number", digits))
+}
+
+pub fn parse_contents(contents: &str) -> Result<(Vec<u64>, NumberMapper), AocError> {
+    let _span = tracing::info_span!("parse").entered();
+    let tokens = lex_contents(contents)?;
+    aoc_common::event!("lexed", tokens = tokens.len());
+    let mut iter = tokens.iter().peekable();
+    let mut seeds: Option<Vec<u64>> = None;
+    let mut number_mapper: Option<NumberMapper> = None;
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Seeds => seeds = Some(parse_seeds(&mut iter)),
+            Token::Map(_, _) => number_mapper = Some(parse_number_mapper(&mut iter)?),
+            _ => _ = iter.next()
+        }
+    }
+    let seeds = seeds.ok_or(AocError::Parse(String::from("No seeds line")))?;
+    let number_mapper = number_mapper.ok_or(AocError::Parse(String::from("No maps")))?;
+    aoc_common::event!("parsed", seeds = seeds.len(), maps = number_mapper.maps_by_source.len());
+    Ok((seeds, number_mapper))
+}
+
+fn parse_seeds<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>) -> Vec<u64> {
+    let mut seeds: Vec<u64> = vec![];
+    if let Some(Token::Seeds) = iter.next() {
+        while let Some(Token::Number(num)) = iter.next() {
+            seeds.push(*num);
+        }
+    }
+    seeds
+}
+
+pub fn parse_content_ranges(contents: &str) -> Result<(Vec<Range<u64>>, NumberMapper), AocError> {
+    let _span = tracing::info_span!("parse").entered();
+    let tokens = lex_contents(contents)?;
+    aoc_common::event!("lexed", tokens = tokens.len());
+    let mut iter = tokens.iter().peekable();
+    let mut seed_ranges: Option<Vec<Range<u64>>> = None;
+    let mut number_mapper: Option<NumberMapper> = None;
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Seeds => seed_ranges = Some(parse_seed_ranges(&mut iter)),
+            Token::Map(_, _) => number_mapper = Some(parse_number_mapper(&mut iter)?),
+            _ => _ = iter.next()
+        }
+    }
+    let seed_ranges = seed_ranges.ok_or(AocError::Parse(String::from("No seeds line")))?;
+    let number_mapper = number_mapper.ok_or(AocError::Parse(String::from("No maps")))?;
+    aoc_common::event!("parsed", seed_ranges = seed_ranges.len(), maps = number_mapper.maps_by_source.len());
+    Ok((seed_ranges, number_mapper))
+}
+
+fn parse_seed_ranges<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>) -> Vec<Range<u64>> {
+    seed_ranges(&parse_seeds(iter))
+}
+
+// Part 2's reading of the seeds line, as pairs of a start and a length. A
+// start left without a length is dropped.
+pub fn seed_ranges(seeds: &[u64]) -> Vec<Range<u64>> {
+    seeds.chunks_exact(2)
+        .map(|pair| pair[0]..pair[0].saturating_add(pair[1]))
+        .collect()
+}
+
+fn parse_number_mapper<'a, T: Iterator<Item = &'a Token>>(iter: &mut Peekable<T>) -> Result<NumberMapper, AocError> {
+    let mut number_mapper = NumberMapper::default();
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Map(source, target) => {
+                iter.next();
+                iter.next();
+                number_mapper.insert(parse_range_map(iter, source, target)?);
+            },
+            _ => _ = iter.next()
+        }
+    }
+    Ok(number_mapper)
+}
+
+fn parse_range_map<'a, T: Iterator<Item = &'a Token>>(
+    iter: &mut Peekable<T>, 
+    source_kind: &ValueKind, 
+    target_kind: &ValueKind
+) -> Result<RangeMap, AocError> {
+    let name = || format!("{}-to-{}", source_kind.name(), target_kind.name());
+    let mut range_pairs: Vec<RangePair> = vec![];
+    while let Some(token) = iter.peek() {
+        match token {
+            Token::Number(_) => {
+                let mut number = || iter.next().and_then(Token::as_number)
+                    .ok_or_else(|| AocError::Parse(format!("A line of the {} map doesn't have three numbers", name())));
+                let target_start = number()?;
+                let source_start = number()?;
+                let offset = number()?;
+
+                let overflow = || AocError::Parse(format!("A range of the {} map goes past {}", name(), u64::MAX));
+                let source = source_start..source_start.checked_add(offset).ok_or_else(overflow)?;
+                let target = target_start..target_start.checked_add(offset).ok_or_else(overflow)?;
+                range_pairs.push(RangePair { source, target });
+            }
+            Token::Newline => _ = iter.next(),
+            _ => break,
+        }
+    }
+
+    Ok(RangeMap::new(*source_kind, *target_kind, range_pairs))
+}
+
+// Ranges as `a..b` separated by spaces, for recorded events
+fn format_ranges(ranges: &[Range<u64>]) -> String {
+    ranges.iter().map(|range| format!("{:?}", range)).collect::<Vec<String>>().join(" ")
+}
+
+pub fn find_smallest_location(seeds: Vec<u64>, mapper: &NumberMapper) -> Result<u64, String> {
+    let seed_to_location = mapper.flatten(ValueKind::Seed, ValueKind::Location)?;
+    let smallest = seeds
+        .iter()
+        .filter_map(|s| {
+            let value = Value { kind: ValueKind::Seed, number: *s };
+            let result = seed_to_location.value_for(&value);
+            if let Some(location) = result {
+                tracing::trace!("seed {} -> location {}", s, location.number);
+                aoc_common::event!("seed", seed = s, location = location.number);
+            }
+            result.map(|r| r.number)
+        })
+        .min();
+    smallest.ok_or(String::from("Couldn't map any seeds to locations"))
+}
+
+pub fn find_smallest_location_ranges(seed_ranges: Vec<Range<u64>>, mapper: &NumberMapper) -> Result<u64, String> {
+    let mut smallest = None;
+    for range in seed_ranges.iter() {
+        let locations = mapper.map_range(range, ValueKind::Seed, ValueKind::Location)?;
+        smallest = locations.iter().map(|r| r.start).chain(smallest).min();
+    }
+    smallest.ok_or(String::from("Couldn't map any seeds to locations"))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 5;
+    const PARTS: &'static [u32] = &[1, 2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(1, "35"), (2, "46")];
+    const APPROACH: &'static str = "Maps held as sorted ranges in a tree; seeds go through all the maps flattened into one, seed ranges are split at the lines they cross, s seeds and m lines";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(1, "O(m² + s·log m)"), (2, "O(s·m·log m)")];
+    const TAGS: &'static [&'static str] = &["interval", "range-tree"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(1, "flatten"), (2, "range-map")];
+    type Input = (Vec<u64>, NumberMapper);
+
+    fn parse(input: &str) -> Result<(Vec<u64>, NumberMapper), AocError> {
+        parse_contents(input)
+    }
+
+    fn part_one((seeds, mapper): &(Vec<u64>, NumberMapper)) -> Result<Answer, AocError> {
+        smallest_location(find_smallest_location(seeds.clone(), mapper))
+    }
+
+    fn part_two((seeds, mapper): &(Vec<u64>, NumberMapper)) -> Result<Answer, AocError> {
+        smallest_location(find_smallest_location_ranges(seed_ranges(seeds), mapper))
+    }
+}
+
+fn smallest_location(location: Result<u64, String>) -> Result<Answer, AocError> {
+    location.map(Answer::from).map_err(AocError::Parse)
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day5::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+aoc_common::register_solution!(2023, 5, Day5, with_variants(LOOKUPS, solve_variant), with_verifier(&BruteForce));
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 5, &[1, 2], solve);
+
+// As Day5::solve, with the maps looking lines up the given way
+pub fn solve_with(part: u32, input: &str, lookup: Lookup) -> Result<String, String> {
+    if !Day5::PARTS.contains(&part) {
+        return Err(format!("Day 5 has no solution for part {}", part))
+    }
+    let (seeds, mapper) = Day5::parse(input)?;
+    let almanac = (seeds, mapper.with_lookup(lookup));
+    let _span = tracing::info_span!("solve").entered();
+    let answer = match part {
+        1 => Day5::part_one(&almanac),
+        _ => Day5::part_two(&almanac),
+    };
+    answer.map(|answer| answer.to_string()).map_err(String::from)
+}
+
+// As `solve_with`, the lookup named as in LOOKUPS
+pub fn solve_variant(part: u32, variant: &str, input: &str) -> Result<String, String> {
+    let lookup = variant.parse().map_err(|_| format!("Unknown lookup '{}'", variant))?;
+    solve_with(part, input, lookup)
+}
+
+// Straight from the puzzle text, to check the range mapping against: every
+// seed goes through every map, trying each of its lines in turn. Part 2 walks
+// every seed of every range, so it's only fit for small inputs.
+pub fn brute_force(part: u32, input: &str) -> Result<Answer, String> {
+    let numbers = |text: &str| -> Result<Vec<u64>, String> {
+        text.split_whitespace()
+            .map(|number| number.parse().map_err(|_| format!("Invalid number '{}'", number)))
+            .collect()
+    };
+    let mut sections = input.split("\n\n");
+    let seeds = numbers(sections.next().and_then(|line| line.strip_prefix("seeds:")).ok_or("No seeds line")?)?;
+    let mut maps: Vec<Vec<(u64, u64, u64)>> = vec![];
+    for section in sections {
+        let mut map = vec![];
+        // the first line only names the map, and they come in order
+        for line in section.lines().skip(1) {
+            match numbers(line)?[..] {
+                [target, source, length] => map.push((target, source, length)),
+                _ => return Err(format!("Invalid map line '{}'", line)),
+            }
+        }
+        maps.push(map);
+    }
+
+    let location = |seed: u64| maps.iter().fold(seed, |value, map| {
+        map.iter()
+            .find(|(_, source, length)| *source <= value && value - source < *length)
+            .map(|(target, source, _)| target + (value - source))
+            .unwrap_or(value)
+    });
+    let smallest_location = match part {
+        1 => seeds.into_iter().map(location).min(),
+        2 if seeds.len() % 2 == 0 => {
+            let mut progress = Progress::new("day 5 brute force", seeds.chunks(2).map(|pair| pair[1]).fold(0, u64::saturating_add));
+            let smallest = seeds.chunks(2)
+                .flat_map(|pair| pair[0]..pair[0].saturating_add(pair[1]))
+                .map(|seed| {
+                    progress.advance(1);
+                    location(seed)
+                })
+                .min();
+            progress.finish();
+            smallest
+        }
+        2 => return Err(String::from("Seeds don't come in pairs")),
+        _ => return Err(format!("Day 5 has no solution for part {}", part)),
+    };
+    smallest_location
+        .map(Answer::from)
+        .ok_or(String::from("Couldn't map any seeds to locations"))
+}
+
+// Checks `solve` against `brute_force`
+pub struct BruteForce;
+
+impl Verifier for BruteForce {
+    fn parts(&self) -> &[u32] {
+        &[1, 2]
+    }
+
+    fn solve(&self, part: u32, input: &str) -> Result<Answer, String> {
+        Ok(Day5::solve(part, input)?)
+    }
+
+    fn brute_force(&self, part: u32, input: &str) -> Result<Answer, String> {
+        brute_force(part, input)
+    }
+}
+
+#[test]
+fn range_map_test() {
+    let source: Range<u64> = 1..2;
+    let target:  Range<u64> = 4..6;
+
+    let map = RangeMap {
+        source_kind: ValueKind::Seed,
+        target_kind: ValueKind::Soil,
+        ranges: vec![RangePair { source, target }],
+        range_tree: None
+    };
+    let seed = Value { kind: ValueKind::Seed, number: 1 };
+    let soil = map.value_for(&seed).unwrap();
+    assert_eq!(soil.number, 4);
+}
+
+#[test]
+fn value_mapper_test() {
+    let seeds_1: Range<u64> = 1..2;
+    let soils_1: Range<u64> = 4..6;
+    let seeds_2: Range<u64> = 5..7;
+    let soils_2: Range<u64> = 7..9;
+    let humidities: Range<u64> = 9..10;
+
+    let seed_to_soil = RangeMap {
+        source_kind: ValueKind::Seed,
+        target_kind: ValueKind::Soil,
+        ranges: vec![
+            RangePair { source: seeds_1.clone(), target: soils_1.clone() },
+            RangePair { source: seeds_2.clone(), target: soils_2.clone() }
+        ],
+        range_tree: None
+    };
+    let soil_to_humidity = RangeMap {
+        source_kind: ValueKind::Soil,
+        target_kind: ValueKind::Humidity,
+        ranges: vec![
+            RangePair { source: soils_1.clone(), target: humidities.clone() }
+        ],
+        range_tree: None
+    };
+    let mut mapper = NumberMapper::default();
+    mapper.insert(seed_to_soil);
+    mapper.insert(soil_to_humidity);
+    let humidity = mapper.map(&Value { kind: ValueKind::Seed, number: 1 }, ValueKind::Humidity).unwrap();
+    assert_eq!(humidity.number, 9);
+    let soil = mapper.map(&Value { kind: ValueKind::Seed, number: 5 }, ValueKind::Soil).unwrap();
+    assert_eq!(soil.number, 7);
+}
+
+#[test]
+fn parse_contents_test() {
+    let (seeds, mapper) = parse_contents(EXAMPLE).expect("Could not parse input");
+    assert_eq!(seeds, vec![79, 14, 55, 13]);
+    let smallest_location = find_smallest_location(seeds, &mapper)
+        .expect("Couldn't map any seeds to locations");
+    assert_eq!(smallest_location, 35);
+}
+
+#[test]
+fn parse_content_ranges_test() {
+    let (seed_ranges, mapper) = parse_content_ranges(EXAMPLE).expect("Could not parse input");
+    assert_eq!(seed_ranges, vec![79..93, 55..68]);
+    let smallest_location = find_smallest_location_ranges(seed_ranges, &mapper)
+        .expect("Couldn't map any seeds to locations");
+    assert_eq!(smallest_location, 46);
+}
+
+// the tokens are checked in under src/snapshots/, `cargo insta review` to accept changes
+#[test]
+fn lex_example_test() {
+    insta::assert_debug_snapshot!("lex_example", lex_contents(EXAMPLE).unwrap());
+}
+
+#[test]
+fn parse_errors_test() {
+    let error = |input: &str| parse_contents(input).unwrap_err().to_string();
+    assert_eq!(error("seeds: 1\n\nseed-to-sand map:\n1 2 3\n"), "Line 3: 'seed-to-sand' isn't a map between two kinds of value");
+    assert_eq!(error("seeds: 1 99999999999999999999\n"), "Line 1: 99999999999999999999 is too big a number");
+    assert_eq!(error("seed-to-soil map:\n1 2 3\n"), "No seeds line");
+    assert_eq!(error("seeds: 1 2\n"), "No maps");
+    assert_eq!(error("seeds: 1\n\nseed-to-soil map:\n1 2\n"), "A line of the seed-to-soil map doesn't have three numbers");
+    assert!(matches!(parse_content_ranges("seeds: 1 x99999999999999999999"), Err(AocError::Lex { line: 1, .. })));
+}
+
+#[test]
+fn example_test() {
+    assert_eq!(solve(1, EXAMPLE), Ok(String::from("35")));
+    assert_eq!(solve(2, EXAMPLE), Ok(String::from("46")));
+}
+
+#[test]
+fn recorded_events_test() {
+    let (answer, recording) = aoc_common::events::record(|| solve(2, EXAMPLE));
+    assert_eq!(answer, Ok(String::from("46")));
+    let parsed: Vec<&aoc_common::events::Event> = recording.of_kind("parsed").collect();
+    assert_eq!(parsed.len(), 1);
+    assert_eq!((parsed[0].field("seeds"), parsed[0].field("maps")), (Some("4"), Some("7")));
+    // both seed ranges go through all seven layers
+    let layers: Vec<&aoc_common::events::Event> = recording.of_kind("layer").collect();
+    assert_eq!(layers.len(), 14);
+    assert_eq!(layers[0].field("from"), Some("79..93"));
+    assert_eq!(layers[6].field("map"), Some("humidity-to-location"));
+}
+
+#[test]
+fn personal_input_test() {
+    let Some(input) = aoc_common::fixtures::personal_input(5) else { return };
+    for part in [1, 2] {
+        let Some(expected) = aoc_common::fixtures::expected(2023, 5, part) else { continue };
+        for lookup in [Lookup::Tree, Lookup::Sorted] {
+            assert_eq!(solve_with(part, &input, lookup), Ok(expected.clone()), "part {} with {:?}", part, lookup);
+        }
+    }
+}
+
+#[test]
+fn interval_tree_test() {
+    let intervals = [
+        RangePair { source: 100..200, target: 50..150 },
+        RangePair { source: 32..48, target: 62..78 },
+        RangePair { source: 10..20, target: 90..100 },
+        RangePair { source: 255..260, target: 100..105 },
+        RangePair { source: 400..420, target: 800..820 },
+    ];
+    let mut iter = intervals.iter();
+    let mut root = RangeTreeNode::new(iter.next().unwrap());
+    for interval in iter {
+        root.insert(interval);
+    }
+    let sources: Vec<Range<u64>> = root.iter().map(|r| r.source.clone()).collect();
+    assert_eq!(sources, vec![10..20, 32..48, 100..200, 255..260, 400..420]);
+
+    let mut intersections = root.find_intersections(&(120..300));
+    intersections.sort_by_key(|r| r.source.start);
+    let sources: Vec<Range<u64>> = intersections.iter().map(|r| r.source.clone()).collect();
+    assert_eq!(sources, vec![120..200, 255..260]);
+    let targets: Vec<Range<u64>> = intersections.iter().map(|r| r.target.clone()).collect();
+    assert_eq!(targets, vec![70..150, 100..105]);
+}
+
+#[test]
+fn signed_interval_tree_test() {
+    let intervals: Vec<RangePair<i64>> = vec![
+        RangePair { source: -10..0, target: 0..10 },
+        RangePair { source: -50..-20, target: 20..50 },
+        RangePair { source: 5..15, target: -15..-5 },
+    ];
+    let mut iter = intervals.iter();
+    let mut root = RangeTreeNode::new(iter.next().unwrap());
+    for interval in iter {
+        root.insert(interval);
+    }
+
+    let sources: Vec<Range<i64>> = root.iter().map(|r| r.source.clone()).collect();
+    assert_eq!(sources, vec![-50..-20, -10..0, 5..15]);
+
+    let mut intersections = root.find_intersections(&(-30..10));
+    intersections.sort_by_key(|r| r.source.start);
+    let targets: Vec<Range<i64>> = intersections.iter().map(|r| r.target.clone()).collect();
+    assert_eq!(targets, vec![40..50, 0..10, -15..-10]);
+}
+
+#[test]
+fn balanced_range_tree_test() {
+    let ranges: Vec<RangePair> = (0..100u64)
+        .map(|n| RangePair { source: (n * 10)..(n * 10 + 5), target: (n * 3)..(n * 3 + 5) })
+        .collect();
+    let tree = RangeTree::from_sorted(&ranges);
+    // inserting the same sorted ranges one by one gives a tree 100 deep
+    assert_eq!(tree.height(), 7);
+    let sources: Vec<Range<u64>> = tree.iter().map(|r| r.source.clone()).collect();
+    let expected: Vec<Range<u64>> = ranges.iter().map(|r| r.source.clone()).collect();
+    assert_eq!(sources, expected);
+    assert_eq!((&tree).into_iter().count(), ranges.len());
+
+    let mut intersections = tree.find_intersections(&(13..42));
+    intersections.sort_by_key(|r| r.source.start);
+    let sources: Vec<Range<u64>> = intersections.iter().map(|r| r.source.clone()).collect();
+    assert_eq!(sources, vec![13..15, 20..25, 30..35, 40..42]);
+    assert_eq!(intersections[3].target, 12..14);
+
+    assert_eq!(RangeTree::<u64>::from_sorted(&[]).height(), 0);
+    assert!(RangeTree::<u64>::from_sorted(&[]).find_intersections(&(0..10)).is_empty());
+}
+
+// Ranges with both ends at most `bound`, empty ones included
+#[cfg(test)]
+fn range_below(bound: u64) -> impl Strategy<Value = Range<u64>> {
+    (0..=bound, 0..=bound).prop_map(|(a, b)| a.min(b)..a.max(b))
+}
+
+// Up to `count` pairs with non-empty, pairwise disjoint sources below
+// `bound`, like a real almanac map, and targets starting in `targets`
+#[cfg(test)]
+fn range_pairs(count: Range<usize>, bound: u64, targets: Range<u64>) -> impl Strategy<Value = Vec<RangePair>> {
+    proptest::collection::vec((range_below(bound), targets), count).prop_map(|candidates| {
+        let mut pairs: Vec<RangePair> = vec![];
+        for (source, target_start) in candidates {
+            if source.is_empty() || pairs.iter().any(|p| ranges_overlap(&p.source, &source)) {
+                continue
+            }
+            let target = target_start..(target_start + source.end - source.start);
+            pairs.push(RangePair { source, target });
+        }
+        pairs
+    })
+}
+
+#[test]
+fn touching_ranges_test() {
+    assert!(!ranges_overlap(&(0..5), &(5..10)));
+    assert!(!ranges_overlap(&(5..10), &(0..5)));
+    assert!(ranges_overlap(&(0..6), &(5..10)));
+    assert!(!ranges_overlap(&(3..3), &(0..10)));
+    assert_eq!(range_intersection(&(0..5), &(5..10)), None);
+    assert_eq!(range_intersection(&(0..10), &(2..4)), Some(2..4));
+
+    let mut root = RangeTreeNode::new(&RangePair { source: 5..10, target: 105..110 });
+    root.insert(&RangePair { source: 10..15, target: 210..215 });
+    root.insert(&RangePair { source: 0..5, target: 300..305 });
+    let intersections = root.find_intersections(&(5..10));
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].target, 105..110);
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn ranges_overlap_property_test(r1 in range_below(40), r2 in range_below(40)) {
+        let shares_point = r1.clone().any(|x| r2.contains(&x));
+        prop_assert_eq!(ranges_overlap(&r1, &r2), shares_point);
+
+        let expected: Vec<u64> = r1.clone().filter(|x| r2.contains(x)).collect();
+        let actual: Vec<u64> = range_intersection(&r1, &r2).into_iter().flatten().collect();
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn find_intersections_property_test(mut pairs in range_pairs(1..7, 60, 1000..2000), query in range_below(60)) {
+        prop_assume!(!pairs.is_empty());
+        let mut root = RangeTreeNode::new(&pairs[0]);
+        for pair in pairs[1..].iter() {
+            root.insert(pair);
+        }
+
+        let mut expected: Vec<(u64, u64)> = query.clone()
+            .filter_map(|x| {
+                let pair = pairs.iter().find(|p| p.source.contains(&x))?;
+                Some((x, pair.target.start + (x - pair.source.start)))
+            })
+            .collect();
+        let mut actual: Vec<(u64, u64)> = root.find_intersections(&query)
+            .iter()
+            .flat_map(|p| p.source.clone().zip(p.target.clone()))
+            .collect();
+        expected.sort();
+        actual.sort();
+        prop_assert_eq!(&actual, &expected);
+
+        pairs.sort_by_key(|p| p.source.start);
+        let mut balanced: Vec<(u64, u64)> = RangeTree::from_sorted(&pairs)
+            .find_intersections(&query)
+            .iter()
+            .flat_map(|p| p.source.clone().zip(p.target.clone()))
+            .collect();
+        balanced.sort();
+        prop_assert_eq!(balanced, expected);
+    }
+}
+
+#[test]
+fn range_map_compose_test() {
+    let seed_to_soil = RangeMap::new(ValueKind::Seed, ValueKind::Soil, vec![
+        RangePair { source: 98..100, target: 50..52 },
+        RangePair { source: 50..98, target: 52..100 },
+    ]);
+    let soil_to_fertilizer = RangeMap::new(ValueKind::Soil, ValueKind::Fertilizer, vec![
+        RangePair { source: 15..52, target: 0..37 },
+        RangePair { source: 52..54, target: 37..39 },
+        RangePair { source: 0..15, target: 39..54 },
+    ]);
+    let composed = seed_to_soil.compose(&soil_to_fertilizer);
+    assert_eq!(composed.source_kind, ValueKind::Seed);
+    assert_eq!(composed.target_kind, ValueKind::Fertilizer);
+
+    for number in 0..120 {
+        let seed = Value { kind: ValueKind::Seed, number };
+        let soil = seed_to_soil.value_for(&seed).unwrap();
+        let expected = soil_to_fertilizer.value_for(&soil).unwrap();
+        assert_eq!(composed.value_for(&seed), Some(expected), "seed {}", number);
+    }
+
+    // composed sources stay disjoint, so range lookups agree too
+    for range in (0..120).flat_map(|start| (start..=120).map(move |end| start..end)) {
+        let mut expected: Vec<Range<u64>> = seed_to_soil.ranges_for(&range)
+            .iter()
+            .flat_map(|r| soil_to_fertilizer.ranges_for(r))
+            .collect();
+        let mut actual = composed.ranges_for(&range);
+        let points = |ranges: &mut Vec<Range<u64>>| -> Vec<u64> {
+            let mut points: Vec<u64> = ranges.drain(..).flatten().collect();
+            points.sort();
+            points
+        };
+        assert_eq!(points(&mut actual), points(&mut expected), "range {:?}", range);
+    }
+}
+
+// A range no mapping touches maps to itself, rather than being dropped
+#[test]
+fn unmapped_range_test() {
+    for lookup in [Lookup::Tree, Lookup::Sorted] {
+        let seed_to_soil = RangeMap::with_lookup(ValueKind::Seed, ValueKind::Soil, vec![
+            RangePair { source: 10..20, target: 50..60 },
+        ], lookup);
+        assert_eq!(seed_to_soil.ranges_for(&(0..5)), vec![0..5], "{:?}", lookup);
+        assert_eq!(seed_to_soil.ranges_for(&(30..40)), vec![30..40], "{:?}", lookup);
+        assert_eq!(seed_to_soil.ranges_for(&(5..25)), vec![5..10, 50..60, 20..25], "{:?}", lookup);
+
+        let soil_to_fertilizer = RangeMap::with_lookup(ValueKind::Soil, ValueKind::Fertilizer, vec![], lookup);
+        let composed = seed_to_soil.compose(&soil_to_fertilizer);
+        assert_eq!(composed.ranges_for(&(0..5)), vec![0..5], "{:?}", lookup);
+        assert_eq!(composed.ranges_for(&(15..25)), vec![55..60, 20..25], "{:?}", lookup);
+    }
+}
+
+#[test]
+fn flatten_test() {
+    let (seeds, mapper) = parse_contents(EXAMPLE).expect("Could not parse input");
+    let seed_to_location = mapper.flatten(ValueKind::Seed, ValueKind::Location).unwrap();
+    assert_eq!(seed_to_location.source_kind, ValueKind::Seed);
+    assert_eq!(seed_to_location.target_kind, ValueKind::Location);
+
+    for seed in seeds {
+        let value = Value { kind: ValueKind::Seed, number: seed };
+        assert_eq!(
+            seed_to_location.value_for(&value),
+            mapper.map(&value, ValueKind::Location)
+        );
+    }
+    assert_eq!(mapper.flatten(ValueKind::Location, ValueKind::Seed).err(), Some(String::from("No location map")));
+}
+
+// Maps that lead back to a kind already passed through, or nowhere, can't
+// get a seed to a location, and say so instead of looking forever
+#[test]
+fn looping_maps_test() {
+    let mut mapper = NumberMapper::default();
+    mapper.insert(RangeMap::new(ValueKind::Seed, ValueKind::Soil, vec![RangePair { source: 0..10, target: 5..15 }]));
+    mapper.insert(RangeMap::new(ValueKind::Soil, ValueKind::Seed, vec![]));
+    let looping = String::from("The maps from seed go round in a loop without reaching location");
+    assert_eq!(mapper.flatten(ValueKind::Seed, ValueKind::Location).err(), Some(looping.clone()));
+    assert_eq!(mapper.map_range(&(0..5), ValueKind::Seed, ValueKind::Location), Err(looping.clone()));
+    assert_eq!(mapper.map(&Value { kind: ValueKind::Seed, number: 1 }, ValueKind::Location), None);
+    assert_eq!(find_smallest_location(vec![1], &mapper), Err(looping.clone()));
+
+    let mut mapper = NumberMapper::default();
+    mapper.insert(RangeMap::new(ValueKind::Seed, ValueKind::Soil, vec![]));
+    assert_eq!(find_smallest_location_ranges(vec![0..5, 10..15], &mapper), Err(String::from("No soil map")));
+
+    let input = "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n1 2 3\n";
+    assert_eq!(solve(1, input), Err(looping.clone()));
+    assert_eq!(solve(2, input), Err(looping));
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn lookups_agree_property_test(pairs in range_pairs(1..7, 60, 0..100), range in range_below(70)) {
+        let tree = RangeMap::with_lookup(ValueKind::Seed, ValueKind::Soil, pairs.clone(), Lookup::Tree);
+        let sorted = RangeMap::with_lookup(ValueKind::Seed, ValueKind::Soil, pairs.clone(), Lookup::Sorted);
+        prop_assert_eq!(sorted.lookup(), Lookup::Sorted);
+        for number in 0..70 {
+            let seed = Value { kind: ValueKind::Seed, number };
+            prop_assert_eq!(tree.value_for(&seed), sorted.value_for(&seed), "seed {}", number);
+        }
+        prop_assert_eq!(tree.ranges_for(&range), sorted.ranges_for(&range));
+    }
+}
+
+#[test]
+fn lookups_agree_test() {
+    for part in [1, 2] {
+        assert_eq!(solve_with(part, EXAMPLE, Lookup::Sorted), solve(part, EXAMPLE));
+    }
+    assert_eq!("sorted".parse(), Ok(Lookup::Sorted));
+}
+
+#[test]
+fn brute_force_test() {
+    assert_eq!(brute_force(1, EXAMPLE), Ok(Answer::U64(35)));
+    assert_eq!(brute_force(2, EXAMPLE), Ok(Answer::U64(46)));
+    assert!(BruteForce.cross_check(EXAMPLE).iter().all(|check| check.agrees()));
+
+    // the range runs past u64::MAX, so it's cut short rather than overflowing
+    assert_eq!(brute_force(2, "seeds: 18446744073709551614 5\n"), Ok(Answer::U64(u64::MAX - 1)));
+    assert_eq!(brute_force(2, "seeds: 79 14 55\n"), Err(String::from("Seeds don't come in pairs")));
+    assert_eq!(brute_force(1, "seeds: 1\n\nseed-to-soil map:\n50 98\n"), Err(String::from("Invalid map line '50 98'")));
+}
+
+#[cfg(test)]
+proptest! {
+    #[test]
+    fn ranges_for_property_test(
+        lookup in prop_oneof![Just(Lookup::Tree), Just(Lookup::Sorted)],
+        pairs in range_pairs(0..6, 60, 0..60),
+        query in range_below(80),
+    ) {
+        let map = RangeMap::with_lookup(ValueKind::Seed, ValueKind::Soil, pairs, lookup);
+        let mapped = map.ranges_for(&query);
+
+        let length = |ranges: &[Range<u64>]| ranges.iter().map(|r| r.end - r.start).sum::<u64>();
+        prop_assert_eq!(length(&mapped), query.end - query.start);
+
+        // pieces come in source order, so walking them walks the query
+        let points: Vec<u64> = mapped.iter().cloned().flatten().collect();
+        let expected: Vec<u64> = query.clone()
+            .map(|number| map.value_for(&Value { kind: ValueKind::Seed, number }).unwrap().number)
+            .collect();
+        prop_assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn map_range_property_test(
+        lookup in prop_oneof![Just(Lookup::Tree), Just(Lookup::Sorted)],
+        layers in proptest::collection::vec(range_pairs(0..6, 50, 0..50), 3),
+        query in range_below(60),
+    ) {
+        let kinds = [ValueKind::Seed, ValueKind::Soil, ValueKind::Fertilizer, ValueKind::Location];
+        let mut mapper = NumberMapper::default();
+        for (kinds, pairs) in kinds.windows(2).zip(layers) {
+            mapper.insert(RangeMap::with_lookup(kinds[0], kinds[1], pairs, lookup));
+        }
+        let mapped = mapper.map_range(&query, ValueKind::Seed, ValueKind::Location).unwrap();
+
+        // merged after every layer, so sorted, disjoint and not touching
+        for pair in mapped.windows(2) {
+            prop_assert!(pair[0].end < pair[1].start, "{:?}", mapped);
+        }
+        prop_assert!(mapped.iter().all(|r| !r.is_empty()), "{:?}", mapped);
+
+        let mut expected: Vec<u64> = query.clone()
+            .map(|number| mapper.location_for_seed(number).unwrap())
+            .collect();
+        expected.sort();
+        expected.dedup();
+        let points: Vec<u64> = mapped.iter().cloned().flatten().collect();
+        prop_assert_eq!(points, expected);
+
+        let length: u64 = mapped.iter().map(|r| r.end - r.start).sum();
+        prop_assert!(length <= query.end - query.start);
+    }
+}
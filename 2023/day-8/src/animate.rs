@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::network::{Network, Step};
+
+// How many of the latest nodes each ghost's trail shows
+const TRAIL: usize = 6;
+
+// The ghosts walked a step at a time, as brute_force does it, to watch them
+// go rather than to find the answer; on a real input they take trillions of
+// steps to line up, which is what the LCM in `solve` is there for.
+#[derive(Debug, Clone)]
+pub struct Ghosts<'a> {
+    network: &'a Network,
+    steps: &'a [Step],
+    pub count: u64,
+    // each ghost's latest nodes, where it is now last
+    pub trails: Vec<VecDeque<&'a str>>,
+}
+
+impl<'a> Ghosts<'a> {
+    // A ghost on every node ending in A, in name order
+    pub fn new(network: &'a Network, steps: &'a [Step]) -> Result<Ghosts<'a>, String> {
+        if steps.is_empty() {
+            return Err(String::from("No instructions"))
+        }
+        let mut starts: Vec<&str> = network.nodes.keys().map(|name| name.as_str()).filter(|name| name.ends_with('A')).collect();
+        if starts.is_empty() {
+            return Err(String::from("No starting nodes found"))
+        }
+        starts.sort();
+        let trails = starts.into_iter().map(|start| VecDeque::from([start])).collect();
+        Ok(Ghosts { network, steps, count: 0, trails })
+    }
+
+    pub fn positions(&self) -> Vec<&'a str> {
+        self.trails.iter().map(|trail| *trail.back().unwrap()).collect()
+    }
+
+    pub fn done(&self) -> bool {
+        self.positions().iter().all(|position| position.ends_with('Z'))
+    }
+
+    pub fn advance(&mut self) -> Result<(), String> {
+        let step = &self.steps[self.count as usize % self.steps.len()];
+        for trail in self.trails.iter_mut() {
+            let current = *trail.back().unwrap();
+            let (left, right) = self.network.nodes.get(current).ok_or(format!("No node named {}", current))?;
+            trail.push_back(match step {
+                Step::Left => left,
+                Step::Right => right,
+            });
+            if trail.len() > TRAIL {
+                trail.pop_front();
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    // The instructions with the next one marked, then a line per ghost with
+    // its trail, the node it's on in brackets
+    pub fn frame(&self) -> String {
+        let mut out = format!("step {}\n", self.count);
+        let instructions: String = self.steps.iter().map(|step| if matches!(step, Step::Left) { 'L' } else { 'R' }).collect();
+        let _ = writeln!(out, "{}", instructions);
+        let _ = writeln!(out, "{}^", " ".repeat(self.count as usize % self.steps.len()));
+        for (idx, trail) in self.trails.iter().enumerate() {
+            let mut nodes: Vec<String> = trail.iter().map(|node| node.to_string()).collect();
+            let current = nodes.pop().unwrap_or_default();
+            nodes.push(format!("[{}]", current));
+            let goal = if current.ends_with('Z') { "  at goal" } else { "" };
+            let _ = writeln!(out, "ghost {:<2} {}{}", idx + 1, nodes.join(" "), goal);
+        }
+        out
+    }
+}
+
+// Draws a frame per step, `delay` apart, until every ghost is on a Z node or
+// `max_frames` have been drawn. Returns the steps taken. With `redraw` each
+// frame clears the terminal first, otherwise they follow one another.
+pub fn animate<W: Write>(out: &mut W, ghosts: &mut Ghosts, delay: Duration, max_frames: Option<u64>, redraw: bool) -> io::Result<u64> {
+    let clear = if redraw { "\x1b[2J\x1b[H" } else { "" };
+    let mut frames = 0;
+    loop {
+        write!(out, "{}{}", clear, ghosts.frame())?;
+        out.flush()?;
+        frames += 1;
+        if ghosts.done() || max_frames.is_some_and(|max| frames >= max) {
+            return Ok(ghosts.count)
+        }
+        thread::sleep(delay);
+        ghosts.advance().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if !redraw {
+            writeln!(out)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_network_and_steps;
+
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_ghosts() {
+        let (network, steps) = parse_network_and_steps(EXAMPLE).unwrap();
+        let mut ghosts = Ghosts::new(&network, &steps).unwrap();
+        assert_eq!(ghosts.positions(), vec!["11A", "22A"]);
+        while !ghosts.done() {
+            ghosts.advance().unwrap();
+        }
+        // the same answer as the LCM gives
+        assert_eq!(ghosts.count, 6);
+        assert_eq!(ghosts.positions(), vec!["11Z", "22Z"]);
+    }
+
+    #[test]
+    fn test_frame() {
+        let (network, steps) = parse_network_and_steps(EXAMPLE).unwrap();
+        let mut ghosts = Ghosts::new(&network, &steps).unwrap();
+        for _ in 0..3 {
+            ghosts.advance().unwrap();
+        }
+        assert_eq!(
+            ghosts.frame(),
+            "step 3
+LR
+ ^
+ghost 1  11A 11B 11Z [11B]
+ghost 2  22A 22B 22C [22Z]  at goal
+",
+        );
+        for _ in 0..10 {
+            ghosts.advance().unwrap();
+        }
+        assert!(ghosts.trails.iter().all(|trail| trail.len() == TRAIL));
+    }
+
+    #[test]
+    fn test_animate() {
+        let (network, steps) = parse_network_and_steps(EXAMPLE).unwrap();
+        let mut ghosts = Ghosts::new(&network, &steps).unwrap();
+        let mut out = vec![];
+        assert_eq!(animate(&mut out, &mut ghosts, Duration::ZERO, None, false).unwrap(), 6);
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("step ").count(), 7);
+        assert!(out.ends_with("ghost 2  22B 22C 22Z 22B 22C [22Z]  at goal\n"), "{}", out);
+
+        let mut ghosts = Ghosts::new(&network, &steps).unwrap();
+        assert_eq!(animate(&mut vec![], &mut ghosts, Duration::ZERO, Some(2), true).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (network, steps) = parse_network_and_steps("L\n\nBBB = (BBB, BBB)\n").unwrap();
+        assert_eq!(Ghosts::new(&network, &steps).err(), Some(String::from("No starting nodes found")));
+        assert_eq!(Ghosts::new(&network, &[]).err(), Some(String::from("No instructions")));
+
+        let (network, steps) = parse_network_and_steps("L\n\nAAA = (BBB, BBB)\n").unwrap();
+        let mut ghosts = Ghosts::new(&network, &steps).unwrap();
+        ghosts.advance().unwrap();
+        assert_eq!(ghosts.advance(), Err(String::from("No node named BBB")));
+    }
+}
@@ -5,10 +5,67 @@ pub fn gcd(a: u64, b: u64) -> u64 {
     gcd(b % a, a)
 }
 
+// The gcd of 0 and 0 is undefined, everything else has one
+pub fn checked_gcd(a: u64, b: u64) -> Option<u64> {
+    if a == 0 && b == 0 {
+        None
+    } else {
+        Some(gcd(a, b))
+    }
+}
+
 pub fn lcm(a: u64, b: u64) -> u64 {
-    a * b / gcd(a, b)
+    checked_lcm(a, b).expect("lcm does not fit in a u64")
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    let Some(gcd) = checked_gcd(a, b) else { return Some(0) };
+    // dividing first keeps the intermediate small, and u128 holds any
+    // product of two u64s, so only the final narrowing can fail
+    let lcm = (a / gcd) as u128 * b as u128;
+    u64::try_from(lcm).ok()
+}
+
+// None if the result would overflow a u64
+pub fn lcm_all(inputs: Vec<u64>) -> Option<u64> {
+    inputs.iter().try_fold(1, |a, b| checked_lcm(a, *b))
 }
 
-pub fn lcm_all(inputs: Vec<u64>) -> u64 {
-    inputs.iter().fold(1, |a, b| lcm(a, *b))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_all(vec![2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(vec![]), Some(1));
+    }
+
+    #[test]
+    fn test_lcm_near_u64_max() {
+        // a * b overflows u64 in all of these even though the lcm fits
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(lcm(u64::MAX, 3), u64::MAX);
+        let big = 1u64 << 62;
+        assert_eq!(lcm(big, big >> 1), big);
+        assert_eq!(lcm_all(vec![u64::MAX / 5, u64::MAX / 3, u64::MAX / 15]), Some(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn test_lcm_overflow_panics() {
+        lcm(u64::MAX, u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_checked_variants() {
+        assert_eq!(checked_gcd(12, 18), Some(6));
+        assert_eq!(checked_gcd(0, 7), Some(7));
+        assert_eq!(checked_gcd(0, 0), None);
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm(1 << 32, 1 << 31), Some(1 << 32));
+        assert_eq!(lcm_all(vec![u64::MAX, 2]), None);
+    }
 }
@@ -0,0 +1,236 @@
+pub mod animate;
+pub mod lcm;
+pub mod network;
+
+use std::collections::HashMap;
+
+use aoc_common::answer::Answer;
+use aoc_common::dot::ToDot;
+use aoc_common::error::AocError;
+use aoc_common::solution::Solution;
+use aoc_common::verify::Verifier;
+
+use crate::network::{Network, Step};
+
+// The steps on the first line, then a node per line after it, with blank
+// lines left out
+pub fn parse_network_and_steps(input: &str) -> Result<(Network, Vec<Step>), AocError> {
+    let _span = tracing::info_span!("parse").entered();
+    let mut lines = input.lines();
+    let Some(steps_line) = lines.next() else {
+        return Err(AocError::Parse(String::from("No steps line")));
+    };
+    let steps = parse_steps(steps_line);
+    let mut network_map: HashMap<String, (String, String)> = HashMap::new();
+    for (idx, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue
+        }
+        let Some(map_line) = parse_map_line(line) else {
+            return Err(AocError::Lex { line: idx + 2, message: format!("'{}' isn't a node, as in AAA = (BBB, CCC)", line) });
+        };
+        network_map.insert(map_line.0, map_line.1);
+    }
+    let network = Network {
+        nodes: network_map
+    };
+    aoc_common::event!("parsed", steps = steps.len(), nodes = network.nodes.len());
+
+    Ok((network, steps))
+}
+
+fn parse_steps(input: &str) -> Vec<Step> {
+    input.chars()
+        .filter_map(|c| {
+            match c {
+                'L' => Some(Step::Left),
+                'R' => Some(Step::Right),
+                _ => None
+            }
+        })
+        .collect()
+}
+
+fn parse_map_line(input: &str) -> Option<(String, (String, String))> {
+    let mut split_input = input.split("=");
+    let start_split = split_input.next()?;
+    let pointers = split_input.next()?;
+
+    let open_paren_idx = pointers.char_indices().find(|c| c.1 == '(').map(|c| c.0)?;
+    let close_paren_idx = pointers.char_indices().find(|c| c.1 == ')').map(|c| c.0)?;
+
+    // names are three characters, so anything shorter isn't a map line
+    let start = start_split.get(0..3)?.to_string();
+    let left = pointers.get(open_paren_idx + 1..open_paren_idx + 4)?.to_string();
+    let right = pointers.get(close_paren_idx.checked_sub(3)?..close_paren_idx)?.to_string();
+    Some((start, (left, right)))
+}
+
+// Steps until every node ending in A is on a node ending in Z at once
+pub fn ghost_steps(network: &Network, steps: &[Step]) -> Result<u64, String> {
+    network.navigate(|n| n.ends_with("A"), |n| n.ends_with("Z"), steps)
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2023;
+    const DAY: u32 = 8;
+    const PARTS: &'static [u32] = &[2];
+    const EXAMPLE: &'static str = include_str!("../example.txt");
+    const EXAMPLE_ANSWERS: &'static [(u32, &'static str)] = &[(2, "6")];
+    const APPROACH: &'static str = "Each ghost walked round the network until it cycles back to a Z node, the answer the LCM of the cycle lengths, g ghosts and n nodes with l instructions";
+    const COMPLEXITY: &'static [(u32, &'static str)] = &[(2, "O(g·n·l)")];
+    const TAGS: &'static [&'static str] = &["graph", "cycles", "lcm"];
+    const ALGORITHMS: &'static [(u32, &'static str)] = &[(2, "cycle-lcm")];
+    type Input = (Network, Vec<Step>);
+
+    fn parse(input: &str) -> Result<(Network, Vec<Step>), AocError> {
+        parse_network_and_steps(input)
+    }
+
+    fn part_two((network, steps): &(Network, Vec<Step>)) -> Result<Answer, AocError> {
+        ghost_steps(network, steps).map(Answer::from).map_err(AocError::Parse)
+    }
+}
+
+pub fn solve(part: u32, input: &str) -> Result<String, String> {
+    Day8::solve(part, input).map(|answer| answer.to_string()).map_err(String::from)
+}
+
+// The network as a Graphviz graph, each edge labelled with its instruction
+pub fn to_dot(input: &str) -> Result<String, String> {
+    let (network, _) = parse_network_and_steps(input)?;
+    Ok(network.to_dot())
+}
+
+aoc_common::register_solution!(2023, 8, Day8, with_verifier(&BruteForce), with_to_dot(to_dot));
+
+// The day as a plugin `aoc` loads at run time, see aoc_common::plugin
+#[cfg(feature = "plugin")]
+aoc_common::export_plugin!(2023, 8, &[2], solve);
+
+const BRUTE_FORCE_STEPS: u64 = 1_000_000;
+
+// Every ghost walked a step at a time until they're all on a Z node at once,
+// to check the cycle lengths against. That takes as many steps as the answer,
+// so it's only fit for small inputs and gives up after BRUTE_FORCE_STEPS.
+pub fn brute_force(part: u32, input: &str) -> Result<Answer, String> {
+    if part != 2 {
+        return Err(format!("Day 8 has no solution for part {}", part))
+    }
+    let mut lines = input.lines();
+    let steps: Vec<char> = lines.next().unwrap_or_default().trim().chars().collect();
+    if steps.is_empty() {
+        return Err(String::from("No instructions"))
+    }
+    let mut nodes: HashMap<&str, (&str, &str)> = HashMap::new();
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let (name, pointers) = line.split_once(" = ").ok_or(format!("Invalid node line '{}'", line))?;
+        let (left, right) = pointers.trim_start_matches('(')
+            .trim_end_matches(')')
+            .split_once(", ")
+            .ok_or(format!("Invalid node line '{}'", line))?;
+        nodes.insert(name, (left, right));
+    }
+
+    let mut ghosts: Vec<&str> = nodes.keys().filter(|name| name.ends_with('A')).copied().collect();
+    for count in 0..BRUTE_FORCE_STEPS {
+        if ghosts.iter().all(|ghost| ghost.ends_with('Z')) {
+            return Ok(count.into())
+        }
+        let step = steps[count as usize % steps.len()];
+        for ghost in ghosts.iter_mut() {
+            let (left, right) = nodes.get(*ghost).ok_or(format!("No node named {}", ghost))?;
+            *ghost = match step {
+                'L' => left,
+                'R' => right,
+                _ => return Err(format!("Invalid instruction '{}'", step)),
+            };
+        }
+    }
+    Err(format!("No answer within {} steps", BRUTE_FORCE_STEPS))
+}
+
+// Checks `solve` against `brute_force`
+pub struct BruteForce;
+
+impl Verifier for BruteForce {
+    fn parts(&self) -> &[u32] {
+        &[2]
+    }
+
+    fn solve(&self, part: u32, input: &str) -> Result<Answer, String> {
+        Ok(Day8::solve(part, input)?)
+    }
+
+    fn brute_force(&self, part: u32, input: &str) -> Result<Answer, String> {
+        brute_force(part, input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the part 2 example; part 1 isn't solved
+    const EXAMPLE: &str = include_str!("../example.txt");
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(2, EXAMPLE), Ok(String::from("6")));
+        assert!(solve(1, EXAMPLE).is_err());
+    }
+
+    #[test]
+    fn test_recorded_events() {
+        let (_, recording) = aoc_common::events::record(|| solve(2, EXAMPLE));
+        let parsed = recording.of_kind("parsed").next().unwrap();
+        assert_eq!((parsed.field("steps"), parsed.field("nodes")), (Some("2"), Some("8")));
+        let mut cycles: Vec<(&str, &str)> = recording.of_kind("cycle")
+            .map(|event| (event.field("start").unwrap(), event.field("steps").unwrap()))
+            .collect();
+        cycles.sort();
+        assert_eq!(cycles, vec![("11A", "2"), ("22A", "3")]);
+        // a step event for every step of both ghosts
+        assert_eq!(recording.of_kind("step").count(), 5);
+    }
+
+    #[test]
+    fn test_personal_input() {
+        let Some(input) = aoc_common::fixtures::personal_input(8) else { return };
+        let Some(expected) = aoc_common::fixtures::expected(2023, 8, 2) else { return };
+        assert_eq!(solve(2, &input), Ok(expected));
+    }
+
+    #[test]
+    fn test_brute_force() {
+        assert_eq!(brute_force(2, EXAMPLE), Ok(Answer::U64(6)));
+        assert!(BruteForce.cross_check(EXAMPLE).iter().all(|check| check.agrees()));
+
+        // the ghost never gets to its Z node
+        let stuck = "L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(brute_force(2, stuck), Err(format!("No answer within {} steps", BRUTE_FORCE_STEPS)));
+        assert_eq!(brute_force(2, "L\n\nAAA = (BBB, BBB)\n"), Err(String::from("No node named BBB")));
+        assert!(brute_force(1, EXAMPLE).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| parse_network_and_steps(input).unwrap_err().to_string();
+        assert_eq!(error(""), "No steps line");
+        assert_eq!(error("LR\n\nAAA = (BBB, CCC)\nBBB = CCC\n"), "Line 4: 'BBB = CCC' isn't a node, as in AAA = (BBB, CCC)");
+        assert!(parse_network_and_steps("LR\n\nAAA = (BBB, CCC)\n \n").is_ok());
+    }
+
+    #[test]
+    fn test_parse_map_line() {
+        assert_eq!(
+            parse_map_line("AAA = (BBB, CCC)"),
+            Some((String::from("AAA"), (String::from("BBB"), String::from("CCC")))),
+        );
+        assert_eq!(parse_map_line("A = ()"), None);
+        assert_eq!(parse_map_line("AAA = (B)"), None);
+        assert_eq!(parse_map_line("AAé = (BBB, CCC)"), None);
+    }
+}
@@ -1,74 +1,55 @@
-mod network;
-mod lcm;
-
-use std::collections::HashMap;
+use aoc_common::output::{timed, AnswerPrinter, OutputFormat};
+use aoc_common::solution::{self, Solution};
+use day_8::animate::{animate, Ghosts};
+use day_8::{ghost_steps, parse_network_and_steps, Day8};
 use std::env;
-use std::fs;
-
-use crate::network::{Network, Step};
-
-fn parse_network_and_steps(input: &String) -> Option<(Network, Vec<Step>)> {
-    let mut lines = input.lines();
-    let Some(steps_line) = lines.next() else {
-        return None;
-    };
-    let steps = parse_steps(steps_line);
-    let mut network_map: HashMap<String, (String, String)> = HashMap::new();
-    while let Some(line) = lines.next() {
-        if let Some(map_line) = parse_map_line(line) {
-            network_map.insert(map_line.0, map_line.1);
-        }
-    }
-    let network = Network {
-        nodes: network_map
-    };
-
-    Some((network, steps))
-}
-
-fn parse_steps(input: &str) -> Vec<Step> {
-    input.chars()
-        .filter_map(|c| {
-            match c {
-                'L' => Some(Step::Left),
-                'R' => Some(Step::Right),
-                _ => None
-            }
-        })
-        .collect()
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::time::Duration;
+
+// The value following `--name`, parsed, if the flag was given
+fn flag<T: std::str::FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    let Some(idx) = args.iter().position(|arg| arg == name) else { return Ok(None) };
+    let value = args.get(idx + 1).ok_or(format!("{} needs a value", name))?;
+    value.parse().map(Some).map_err(|_| format!("Invalid value for {}: '{}'", name, value))
 }
 
-fn parse_map_line(input: &str) -> Option<(String, (String, String))> {
-    let mut split_input = input.split("=");
-    let Some(start_split) = split_input.next() else {
-        return None;
-    };
-    let Some(pointers) = split_input.next() else {
-        return None;
-    };
-
-    let Some(open_paren_idx) = pointers.char_indices().find(|c| c.1 == '(').map(|c| c.0) else {
-        return None;
-    };
-
-    let Some(close_paren_idx) = pointers.char_indices().find(|c| c.1 == ')').map(|c| c.0) else {
-        return None;
-    };
-
-    let start = start_split[0..3].to_string();
-    let left = pointers[open_paren_idx + 1..open_paren_idx+4].to_string();
-    let right = pointers[close_paren_idx-3..close_paren_idx].to_string();
-    Some((start, (left, right)))
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let format = OutputFormat::from_args(args)?;
+    let contents = solution::read_input::<Day8>(args)?;
+    let printer = AnswerPrinter::new(format, 2023, 8, &contents);
+    let (network, steps) = parse_network_and_steps(&contents)?;
+    #[cfg(feature = "serde")]
+    if let Some(format) = aoc_common::dump::DumpFormat::from_args(args)? {
+        aoc_common::dump::write(&(&network, &steps), format)?;
+        return Ok(())
+    }
+    // --animate [--speed <steps per second>] [--frames <n>] walks the ghosts
+    // on screen a step at a time instead of solving
+    if args.iter().any(|arg| arg == "--animate") {
+        let speed: f64 = flag(args, "--speed")?.unwrap_or(5.0);
+        let delay = Duration::from_secs_f64(1.0 / speed.max(0.01));
+        let mut ghosts = Ghosts::new(&network, &steps)?;
+        let stdout = io::stdout();
+        let redraw = stdout.is_terminal();
+        let count = animate(&mut stdout.lock(), &mut ghosts, delay, flag(args, "--frames")?, redraw)?;
+        tracing::info!("Stopped after {} steps", count);
+        return Ok(())
+    }
+    let (num_steps_multiple, duration) = timed(|| ghost_steps(&network, &steps));
+    printer.print("num_steps_multiple", &printer.record(2, "cycle-lcm", num_steps_multiple?, duration));
+    if args.iter().any(|arg| arg == "--example") {
+        Day8::check_example()?;
+    }
+    Ok(())
 }
 
 fn main() {
-    let mut args = env::args();
-    args.next();
-    let input = args.next().expect("No input provided");
-    let contents = fs::read_to_string(input).expect("Could not read input file");
-    let (network, steps) = parse_network_and_steps(&contents).expect("Could not parse input");
-    // let num_steps = network.navigate(|n| n == "AAA", |n| n == "ZZZ", &steps);
-    // println!("num_steps: {:?}", num_steps);
-    let num_steps_multiple = network.navigate(|n| n.ends_with("A"), |n| n.ends_with("Z"), &steps);
-    println!("num_steps_multiple: {:?}", num_steps_multiple);
+    let args: Vec<String> = env::args().skip(1).collect();
+    // answers go to stdout, anything logged to stderr
+    aoc_common::logging::init(&args);
+    if let Err(err) = run(&args) {
+        tracing::error!("{}", err);
+        std::process::exit(1);
+    }
 }
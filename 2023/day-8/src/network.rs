@@ -1,26 +1,58 @@
-use std::collections::HashMap;
-use std::iter::Cycle;
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::dot::{DotEdge, ToDot};
+use aoc_common::error::AocError;
 
 use crate::lcm::lcm_all;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Network {
     pub nodes: HashMap<String, (String, String)>
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Step {
     Left,
     Right,
 }
 
+// Repeats the steps forever, tracking its own position in the list
+#[derive(Debug, Clone)]
+pub struct StepCycle<'a> {
+    steps: &'a [Step],
+    position: usize,
+}
+
+impl<'a> StepCycle<'a> {
+    pub fn new(steps: &'a [Step]) -> StepCycle<'a> {
+        StepCycle { steps, position: 0 }
+    }
+
+    // The index of the step `next` gives next
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a> Iterator for StepCycle<'a> {
+    type Item = &'a Step;
+
+    fn next(&mut self) -> Option<&'a Step> {
+        let step = self.steps.get(self.position)?;
+        self.position = (self.position + 1) % self.steps.len();
+        Some(step)
+    }
+}
+
 impl Network {
     pub fn navigate<'a, F1, F2>(
         &'a self, 
         is_start: F1, 
         is_goal: F2, 
-        steps: &'a Vec<Step>
-    ) -> Option<u64> 
+        steps: &'a [Step]
+    ) -> Result<u64, String> 
     where
         F1: Fn(&'a String) -> bool,
         F2: Fn(&'a String) -> bool + Copy
@@ -29,77 +61,72 @@ impl Network {
             .filter(|k| is_start(k))
             .collect();
         match matching.len() {
-            0 => None,
-            1 => {
-                let mut step_iter = steps.iter().cycle();
-                Some(self.navigate_rec(is_goal, matching.first().unwrap(), &mut step_iter, 0))
-            },
+            0 => Err(String::from("No starting nodes found")),
+            1 => Ok(self.walk(matching[0], is_goal, steps)?),
             _ => {
                 let required_steps: Vec<u64> = matching.iter()
                     .map(|m| {
-                        let mut step_iter = steps.iter().cycle();
-                        self.navigate_imp(m, is_goal, &mut step_iter) as u64
+                        let required = self.walk(m, is_goal, steps)?;
+                        aoc_common::event!("cycle", start = m, steps = required);
+                        Ok(required)
                     })
-                    .collect();
-                Some(lcm_all(required_steps))
+                    .collect::<Result<Vec<u64>, String>>()?;
+                lcm_all(required_steps).ok_or(String::from("Number of steps overflows a u64"))
             }
         }
     }
 
-    fn navigate_rec<'a, I, F>(
-        &'a self, 
-        is_goal: F, 
-        current: &'a String,
-        step_iter: &mut Cycle<I>,
-        steps: u64
-    ) -> u64 
+    // Steps from `start` until a goal node, taking at least one. Being back on
+    // a node at the same point in the steps means the walk only goes round in
+    // circles from there, so it stops rather than walking forever.
+    fn walk<'a, F>(&'a self, start: &'a String, is_goal: F, steps: &'a [Step]) -> Result<u64, AocError>
     where
-        I: Iterator<Item = &'a Step> + Clone,
-        F: Fn(&'a String) -> bool + Copy,
+        F: Fn(&'a String) -> bool,
     {
-        let step = step_iter.next();
-        let Some(paths) = self.nodes.get(current) else {
-            panic!("Could not find: {}", current);
-        };
-        let next = match step {
-            Some(Step::Left) => &paths.0,
-            Some(Step::Right) => &paths.1,
-            None => panic!("Unexpected")
-        };
-        if is_goal(next) {
-            steps + 1
-        } else {
-            self.navigate_rec(is_goal, next, step_iter, steps + 1)
+        let mut step_iter = StepCycle::new(steps);
+        let mut seen: HashSet<(&String, usize)> = HashSet::new();
+        let mut current = start;
+        let mut count = 0;
+        loop {
+            if !seen.insert((current, step_iter.position())) {
+                return Err(AocError::Parse(format!("No goal node can be reached from {}", start)))
+            }
+            current = self.follow(current, step_iter.next())?;
+            count += 1;
+            aoc_common::event!("step", start = start, count = count, node = current);
+            if is_goal(current) {
+                return Ok(count)
+            }
         }
     }
 
-
-    fn navigate_imp<'a, I, F>(
-        &'a self, 
-        start: &'a String,
-        is_goal: F, 
-        step_iter: &mut Cycle<I>
-    ) -> u64
-    where
-        I: Iterator<Item = &'a Step> + Clone,
-        F: Fn(&'a String) -> bool,
-    {
-        let mut steps = 0;
-        let mut current: &String = start;
-
-        while !is_goal(current) {
-            let step = step_iter.next();
-            let Some(paths) = self.nodes.get(current) else {
-                panic!("Could not find: {}", current);
-            };
-            current = match step {
-                Some(Step::Left) => &paths.0,
-                Some(Step::Right) => &paths.1,
-                None => panic!("Unexpected")
-            };
-            steps = steps + 1;
+    // The node a step from `current` leads to. A node that's never defined or
+    // no steps at all mean the input doesn't describe a network to walk.
+    fn follow<'a>(&'a self, current: &String, step: Option<&Step>) -> Result<&'a String, AocError> {
+        let paths = self.nodes.get(current)
+            .ok_or_else(|| AocError::Parse(format!("Node {} is never defined", current)))?;
+        match step {
+            Some(Step::Left) => Ok(&paths.0),
+            Some(Step::Right) => Ok(&paths.1),
+            None => Err(AocError::Parse(String::from("No steps to follow"))),
         }
-        steps
+    }
+}
+
+// Every node with its left and right exits, sorted so the output is stable
+impl ToDot for Network {
+    fn dot_name(&self) -> String {
+        String::from("network")
+    }
+
+    fn dot_edges(&self) -> Vec<DotEdge> {
+        let mut nodes: Vec<(&String, &(String, String))> = self.nodes.iter().collect();
+        nodes.sort();
+        nodes.into_iter()
+            .flat_map(|(node, (left, right))| {
+                [DotEdge::labeled(node, left, "L"), DotEdge::labeled(node, right, "R")]
+            })
+            .collect()
     }
 }
 
@@ -119,7 +146,68 @@ mod tests {
 
         let steps = vec![Step::Left, Step::Left, Step::Right];
         let navigated_steps = network.navigate(|n| n == "AAA", |n| n == "ZZZ", &steps);
-        assert_eq!(navigated_steps, Some(6));
+        assert_eq!(navigated_steps, Ok(6));
+    }
+
+    #[test]
+    fn test_malformed_networks() {
+        let network = Network {
+            nodes: HashMap::from([
+                (String::from("AAA"), (String::from("BBB"), String::from("CCC"))),
+                (String::from("BBA"), (String::from("CCC"), String::from("CCC"))),
+            ])
+        };
+        let navigate = |is_start: fn(&String) -> bool, steps: &[Step]| network.navigate(is_start, |n| n.ends_with('Z'), steps);
+        assert_eq!(navigate(|n| n == "AAA", &[Step::Left]), Err(String::from("Node BBB is never defined")));
+        assert_eq!(navigate(|n| n.ends_with('A'), &[Step::Right]), Err(String::from("Node CCC is never defined")));
+        assert_eq!(navigate(|n| n == "AAA", &[]), Err(String::from("No steps to follow")));
+    }
+
+    #[test]
+    fn test_unreachable_goals() {
+        let network = Network {
+            nodes: HashMap::from([
+                (String::from("AAA"), (String::from("AAA"), String::from("AAA"))),
+                (String::from("BBA"), (String::from("BBA"), String::from("BBA"))),
+                (String::from("CCA"), (String::from("CCZ"), String::from("CCA"))),
+                (String::from("CCZ"), (String::from("CCA"), String::from("CCZ"))),
+            ])
+        };
+        let error = |start: &str| Err(format!("No goal node can be reached from {}", start));
+        assert_eq!(network.navigate(|n| n == "AAA", |n| n == "ZZZ", &[Step::Left]), error("AAA"));
+        assert_eq!(network.navigate(|n| n == "BBA" || n == "CCA", |n| n.ends_with('Z'), &[Step::Left]), error("BBA"));
+        // the goal is only there on the second time round the steps
+        assert_eq!(network.navigate(|n| n == "CCA", |n| n.ends_with('Z'), &[Step::Right, Step::Left]), Ok(2));
+    }
+
+    #[test]
+    fn test_step_cycle() {
+        let steps = vec![Step::Left, Step::Right, Step::Right];
+        let cycled: Vec<&Step> = StepCycle::new(&steps).take(7).collect();
+        assert!(matches!(
+            cycled.as_slice(),
+            [Step::Left, Step::Right, Step::Right, Step::Left, Step::Right, Step::Right, Step::Left]
+        ));
+        assert!(StepCycle::new(&[]).next().is_none());
     }
-}
 
+    #[test]
+    fn test_to_dot() {
+        let network = Network {
+            nodes: HashMap::from([
+                (String::from("BBB"), (String::from("AAA"), String::from("ZZZ"))),
+                (String::from("AAA"), (String::from("BBB"), String::from("BBB"))),
+            ])
+        };
+        assert_eq!(
+            network.to_dot(),
+            "digraph \"network\" {
+    \"AAA\" -> \"BBB\" [label=\"L\"];
+    \"AAA\" -> \"BBB\" [label=\"R\"];
+    \"BBB\" -> \"AAA\" [label=\"L\"];
+    \"BBB\" -> \"ZZZ\" [label=\"R\"];
+}
+",
+        );
+    }
+}
@@ -0,0 +1,3 @@
+#![cfg_attr(feature = "libfuzzer", no_main)]
+
+aoc_fuzz::fuzz_main!(aoc_fuzz::day_3);
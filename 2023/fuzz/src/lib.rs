@@ -0,0 +1,130 @@
+// Fuzz targets for every day's parser. Each takes arbitrary bytes, skips
+// anything that isn't UTF-8 since the inputs are read as strings, and only
+// has to not panic or hang.
+//
+// The binaries in fuzz_targets/ are the cargo-fuzz targets when built with the
+// libfuzzer feature. Without it they replay the files given to them, or stdin,
+// which is how a crash found by the fuzzer gets reproduced:
+//
+//     cargo run -p aoc-fuzz --bin day_8 -- fuzz/artifacts/day_8/crash-...
+
+use std::io::Read;
+use std::str;
+
+#[cfg(feature = "libfuzzer")]
+pub use libfuzzer_sys;
+
+pub fn day_1(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        let _ = day_1::solve(2, input);
+    }
+}
+
+pub fn day_2(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        day_2::parse(input);
+    }
+}
+
+pub fn day_3(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        let _ = day_3::parse(input);
+    }
+}
+
+pub fn day_4(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        day_4::parse_contents(input);
+    }
+}
+
+pub fn day_5(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        let _ = day_5::parse_contents(input);
+        let _ = day_5::parse_content_ranges(input);
+    }
+}
+
+pub fn day_8(data: &[u8]) {
+    if let Ok(input) = str::from_utf8(data) {
+        let _ = day_8::parse_network_and_steps(input);
+    }
+}
+
+// Runs the target on each file named in the arguments, or on stdin
+pub fn replay(target: fn(&[u8])) {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        let mut data = vec![];
+        std::io::stdin().read_to_end(&mut data).expect("Couldn't read stdin");
+        target(&data);
+    }
+    for path in paths {
+        let data = std::fs::read(&path).expect("Couldn't read input");
+        target(&data);
+        println!("{}: ok", path);
+    }
+}
+
+#[macro_export]
+macro_rules! fuzz_main {
+    ($target:path) => {
+        #[cfg(feature = "libfuzzer")]
+        $crate::libfuzzer_sys::fuzz_target!(|data: &[u8]| $target(data));
+
+        #[cfg(not(feature = "libfuzzer"))]
+        fn main() {
+            $crate::replay($target);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::rng::Rng;
+
+    use super::*;
+
+    type Target = fn(&[u8]);
+
+    const TARGETS: &[(&str, Target)] = &[
+        ("day_1", day_1),
+        ("day_2", day_2),
+        ("day_3", day_3),
+        ("day_4", day_4),
+        ("day_5", day_5),
+        ("day_8", day_8),
+    ];
+
+    // Pieces of real inputs, so the generated ones get past the first token
+    const FRAGMENTS: &[&str] = &[
+        "Game ", "Card ", "seeds: ", "seed-to-soil map:", "soil-to-location map:", ": ", "; ", ", ", " | ",
+        "red", "green", "blue", "one", "eight", "nine", "AAA = (BBB, CCC)", " = (", ")", "(", "LR", "=",
+        "\n", "\n\n", " ", "*", "#", ".", "..", "1", "42", "99999999999999999999999", "18446744073709551615",
+        "é", "²", "\u{1F600}",
+    ];
+
+    fn generate(rng: &mut Rng) -> Vec<u8> {
+        let mut data = vec![];
+        for _ in 0..rng.below(40) {
+            if rng.below(4) == 0 {
+                data.push(rng.next_u64() as u8);
+            } else {
+                data.extend_from_slice(rng.pick(FRAGMENTS).as_bytes());
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_targets_survive_generated_inputs() {
+        let mut rng = Rng::from_args(&[], 0xf022_1723).unwrap();
+        for _ in 0..3000 {
+            let data = generate(&mut rng);
+            for (name, target) in TARGETS {
+                let result = std::panic::catch_unwind(|| target(&data));
+                assert!(result.is_ok(), "{} panicked on {:?} with seed {}", name, String::from_utf8_lossy(&data), rng.seed());
+            }
+        }
+    }
+}